@@ -0,0 +1,28 @@
+// Trivial external bot for the line-based JSON protocol (see src/game/bot/protocol.rs)
+// it peeks at its first cards, always draws from the deck and discards the drawn card
+// run: cargo build --example echo_bot && cargo run -- --bot target/debug/examples/echo_bot
+use std::io::{self, BufRead, Write};
+
+fn main() {
+    let stdin = io::stdin();
+    let mut stdout = io::stdout();
+
+    for line in stdin.lock().lines() {
+        let Ok(line) = line else { break; };
+        let Ok(message) = serde_json::from_str::<serde_json::Value>(&line) else { continue; };
+
+        // echo what the game sent to stderr, for debugging
+        eprintln!("echo_bot <- {}", line);
+
+        let replies = match message["type"].as_str() {
+            Some("peek") => (0..message["count"].as_u64().unwrap_or(0)).map(|slot| format!(r#"{{"action":"peek","slot":{}}}"#, slot)).collect(),
+            Some("turn") => vec![String::from(r#"{"action":"draw","from":"deck"}"#)],
+            Some("drawn") => vec![String::from(r#"{"action":"discard"}"#)],
+            _ => Vec::new(),
+        };
+
+        if replies.iter().any(|reply| writeln!(stdout, "{}", reply).and_then(|_| stdout.flush()).is_err()) {
+            break;
+        }
+    }
+}
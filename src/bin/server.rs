@@ -0,0 +1,365 @@
+use bevy::prelude::*;
+use bevy::app::ScheduleRunnerPlugin;
+use bevy::input::InputPlugin;
+use bevy::log::LogPlugin;
+use bevy::state::app::StatesPlugin;
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use gamename_test::game::GamePlugin;
+use gamename_test::game::gamestate::AppState;
+use gamename_test::game::player::component::Player;
+use gamename_test::game::match_config::resource::{MatchConfig, GameSpeed};
+use gamename_test::game::net::protocol::DEFAULT_PORT;
+use gamename_test::game::net::resource::{NetHost, NetSession};
+use gamename_test::game::lobby::resource::LobbySettings;
+use gamename_test::game::turn_timer::resource::TurnTimeLimit;
+use gamename_test::game::rules::resource::{Ruleset, TieBreak};
+use gamename_test::game::special_cards::resource::SpecialEffectRegistry;
+use gamename_test::game::deck::resource::DeckKind;
+use gamename_test::game::ai::component::AIThinkSettings;
+use gamename_test::game::round_end::event::StartNewRound;
+use gamename_test::game::round_end::resource::RoundScores;
+use gamename_test::game::round_end::system::calculate_scores;
+use gamename_test::game::turn_player::event::TurnAction;
+
+// headless server options given on the command line
+// (--port <port>, --tables <count>, --players <clients per table>, --ai <AI seats>, --log-dir <dir>,
+//  --open-hands: spectators see every hand, --spectator-delay <seconds>, --turn-time <seconds>,
+//  --peek <cards>, --assist: peeked cards stay visible,
+//  --special <value>=<effect>: effect of a card value, "none" takes it away,
+//  --caller-penalty <points>, --caller-bonus <points>, --king-of-golds-zero, --pairs-cancel, --tie-break <rule>,
+//  --graveyard-must-swap, --no-take-back, --deck <deck>, --decks <count>, --joker-points <points>,
+//  --ai-think [<seat>=]<seconds>[:<jitter>]: how long the AI seats wait before playing, repeatable)
+#[derive(Resource, Clone)]
+struct ServerOptions {
+    port: u16,
+    tables: usize,
+    players: usize,
+    ai_players: usize,
+    log_dir: PathBuf,
+    open_hands: bool,
+    spectator_delay: f32,
+    turn_time: Option<f32>,
+    rules: Ruleset,
+    ai_think: AIThinkSettings,
+    warnings: Vec<String>, // arguments that could not be used, logged once the log is open
+}
+
+impl ServerOptions {
+    fn from_args(mut args: impl Iterator<Item = String>) -> Self {
+        let mut options = Self { port: DEFAULT_PORT, tables: 1, players: 1, ai_players: 1, log_dir: PathBuf::from("logs"), open_hands: false, spectator_delay: 0.0, turn_time: None, rules: Ruleset::default(), ai_think: AIThinkSettings::default(), warnings: Vec::new() };
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--port" => {
+                    if let Some(port) = args.next().and_then(|port| port.parse().ok()) {
+                        options.port = port;
+                    }
+                }
+                "--tables" => {
+                    if let Some(tables) = args.next().and_then(|tables| tables.parse().ok()) {
+                        options.tables = tables;
+                    }
+                }
+                "--players" => {
+                    if let Some(players) = args.next().and_then(|players| players.parse().ok()) {
+                        options.players = players;
+                    }
+                }
+                "--ai" => {
+                    if let Some(ai_players) = args.next().and_then(|ai_players| ai_players.parse().ok()) {
+                        options.ai_players = ai_players;
+                    }
+                }
+                "--log-dir" => {
+                    if let Some(log_dir) = args.next() {
+                        options.log_dir = PathBuf::from(log_dir);
+                    }
+                }
+                "--open-hands" => options.open_hands = true,
+                "--spectator-delay" => {
+                    if let Some(delay) = args.next().and_then(|delay| delay.parse().ok()) {
+                        options.spectator_delay = delay;
+                    }
+                }
+                "--turn-time" => {
+                    if let Some(seconds) = args.next().and_then(|seconds| seconds.parse::<f32>().ok()) {
+                        options.turn_time = (seconds > 0.0).then_some(seconds);
+                    }
+                }
+                "--assist" => options.rules.assist = true,
+                "--special" => {
+                    let special = args.next();
+                    if let Some((value, effect)) = special.as_deref().and_then(|special| special.split_once('='))
+                        && let Ok(value) = value.parse::<u8>() {
+                        // "none" takes the effect away, an unknown name leaves the rules as they are
+                        match SpecialEffectRegistry::built_in().find(effect) {
+                            Some(effect) => { options.rules.specials.insert(value, effect); }
+                            None if effect == "none" => { options.rules.specials.remove(&value); }
+                            None => options.warnings.push(format!("Unknown special {:?} for {}, keeping the rules", effect, value)),
+                        }
+                    }
+                }
+                "--peek" => {
+                    if let Some(count) = args.next().and_then(|count| count.parse::<usize>().ok()) {
+                        options.rules.peek_count = count.min(Ruleset::MAX_PEEK);
+                    }
+                }
+                "--caller-penalty" => {
+                    if let Some(points) = args.next().and_then(|points| points.parse().ok()) {
+                        options.rules.caller_penalty = points;
+                    }
+                }
+                "--caller-bonus" => {
+                    if let Some(points) = args.next().and_then(|points| points.parse().ok()) {
+                        options.rules.caller_bonus = points;
+                    }
+                }
+                "--king-of-golds-zero" => options.rules.king_of_golds_zero = true,
+                "--pairs-cancel" => options.rules.pairs_cancel = true,
+                "--graveyard-must-swap" => options.rules.graveyard_must_swap = true,
+                "--no-take-back" => options.rules.no_take_back = true,
+                "--deck" => {
+                    if let Some(deck) = args.next().as_deref().and_then(DeckKind::from_name) {
+                        options.rules.deck = deck;
+                    }
+                }
+                "--decks" => {
+                    if let Some(count) = args.next().and_then(|count| count.parse::<usize>().ok()) {
+                        options.rules.deck_copies = count.max(1);
+                    }
+                }
+                "--ai-think" => {
+                    if let Some(value) = args.next() && !options.ai_think.set(&value) {
+                        options.warnings.push(format!("Invalid AI think time {:?}", value));
+                    }
+                }
+                "--joker-points" => {
+                    if let Some(points) = args.next().and_then(|points| points.parse().ok()) {
+                        options.rules.joker_points = points;
+                    }
+                }
+                "--tie-break" => {
+                    if let Some(tie_break) = args.next().as_deref().and_then(TieBreak::from_name) {
+                        options.rules.tie_break = tie_break;
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        // the table has room for 2 to 4 players, at least one of them connected
+        options.tables = options.tables.max(1);
+        options.players = options.players.clamp(1, 4);
+        options.ai_players = options.ai_players.min(4 - options.players);
+        if options.players + options.ai_players < 2 {
+            options.ai_players = 2 - options.players;
+        }
+        // a special on a value the deck does not have is kept, but it never happens
+        for (value, effect) in options.rules.specials_off_deck() {
+            options.warnings.push(format!("The {} deck has no {}, the {} special is never played", options.rules.deck.name(), value, effect.name()));
+        }
+        options
+    }
+}
+
+// one table hosted by this app
+#[derive(Resource)]
+struct Table {
+    index: usize,
+    port: u16,
+}
+
+// match log of the table, one line per event
+#[derive(Resource)]
+struct MatchLog {
+    file: Option<File>,
+    round: u32,
+}
+
+impl MatchLog {
+    fn open(log_dir: &PathBuf, table: usize) -> Self {
+        let path = log_dir.join(format!("table-{}.log", table));
+        let file = fs::create_dir_all(log_dir)
+            .and_then(|_| OpenOptions::new().create(true).append(true).open(&path));
+        match file {
+            Ok(file) => Self { file: Some(file), round: 0 },
+            Err(error) => {
+                warn!(target: "mygame", "Could not open match log {}: {}", path.display(), error);
+                Self { file: None, round: 0 }
+            }
+        }
+    }
+
+    fn write(&mut self, line: &str) {
+        let Some(file) = self.file.as_mut() else { return; };
+        let time = SystemTime::now().duration_since(UNIX_EPOCH).map(|time| time.as_secs()).unwrap_or_default();
+        if let Err(error) = writeln!(file, "[{}] round {}: {}", time, self.round, line) {
+            warn!(target: "mygame", "Could not write match log: {}", error);
+            self.file = None;
+        }
+    }
+}
+
+// pause between rounds so clients can read the scores
+#[derive(Resource)]
+struct NextRoundTimer(Timer);
+
+fn main() {
+    let options = ServerOptions::from_args(std::env::args().skip(1));
+
+    // logging is global, only the first table installs it
+    let mut first_table = build_table(&options, 0, true);
+
+    let other_tables: Vec<_> = (1..options.tables)
+        .map(|index| {
+            let options = options.clone();
+            std::thread::spawn(move || { build_table(&options, index, false).run(); })
+        })
+        .collect();
+
+    first_table.run();
+    for table in other_tables {
+        let _ = table.join();
+    }
+}
+
+// headless app for one table: game rules and networking, no window, audio or assets
+fn build_table(options: &ServerOptions, index: usize, with_log: bool) -> App {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins.set(ScheduleRunnerPlugin::run_loop(Duration::from_secs_f64(1.0 / 60.0))));
+    if with_log {
+        app.add_plugins(LogPlugin::default());
+        for warning in &options.warnings {
+            warn!(target: "mygame", "{}", warning);
+        }
+    }
+
+    app.add_plugins((StatesPlugin, InputPlugin))
+    .add_plugins(GamePlugin)
+    .insert_resource(options.clone())
+    .insert_resource(Table { index, port: options.port + index as u16 })
+    // announced to the lobby of the clients, the AI seats are counted as seats to fill
+    .insert_resource(LobbySettings {
+        name: format!("Server table {}", index + 1),
+        seats: options.players + options.ai_players,
+        ai_fill: options.ai_players > 0,
+        host_plays: false,
+        open_hands_for_spectators: options.open_hands,
+        spectator_delay: options.spectator_delay,
+        lockstep: false,
+    })
+    .insert_resource(TurnTimeLimit(options.turn_time))
+    .insert_resource(options.rules.clone())
+    .insert_resource(options.ai_think.clone())
+    .insert_resource(MatchLog::open(&options.log_dir, index))
+    .insert_resource(NextRoundTimer(Timer::from_seconds(5.0, TimerMode::Once)))
+    .add_systems(Update, open_table.run_if(in_state(AppState::MainMenu)))
+    .add_systems(OnEnter(AppState::PlayerTurn), log_round_start)
+    .add_systems(Update, log_turn_actions)
+    .add_systems(OnEnter(AppState::RoundEnd), log_round_end.after(calculate_scores))
+    .add_systems(Update, next_round.run_if(in_state(AppState::RoundEnd)));
+    app
+}
+
+// listen for clients and start the match once the table is full
+fn open_table(
+    mut net_session: ResMut<NetSession>,
+    options: Res<ServerOptions>,
+    table: Res<Table>,
+    mut next_state: ResMut<NextState<AppState>>,
+    mut exit: MessageWriter<AppExit>,
+    mut commands: Commands,
+) {
+    match &*net_session {
+        NetSession::Host(host) => {
+            let connected = host.peers.iter().filter(|peer| peer.connected).count();
+            if connected >= options.players {
+                commands.insert_resource(MatchConfig::server(options.players, options.ai_players).with_think_times(&options.ai_think));
+                commands.insert_resource(GameSpeed::default());
+                next_state.set(AppState::Setup);
+                info!(target: "mygame", "Table {}: starting with {} clients", table.index, options.players);
+            }
+        }
+        _ => match NetHost::bind(table.port) {
+            Ok(host) => {
+                *net_session = NetSession::Host(host);
+                info!(target: "mygame", "Table {}: waiting for {} clients on port {}", table.index, options.players, table.port);
+            }
+            Err(error) => {
+                error!(target: "mygame", "Table {}: could not listen on port {}: {}", table.index, table.port, error);
+                exit.write(AppExit::error());
+            }
+        },
+    }
+}
+
+fn log_round_start(
+    mut match_log: ResMut<MatchLog>,
+    player_query: Query<&Player>,
+    net_session: Res<NetSession>,
+) {
+    // PlayerTurn is entered once per round, after the deal
+    match_log.round += 1;
+
+    let mut players: Vec<&Player> = player_query.iter().collect();
+    players.sort_by_key(|player| player.seat);
+    let seats: Vec<String> = players.iter().map(|player| {
+        let peer_name = match &*net_session {
+            NetSession::Host(host) => host.peers.iter().find(|peer| peer.seat == Some(player.seat)).map(|peer| peer.name.clone()),
+            _ => None,
+        };
+        format!("{} ({})", player.name, peer_name.unwrap_or_else(|| String::from("AI")))
+    }).collect();
+    match_log.write(&format!("start, seats: {}", seats.join(", ")));
+}
+
+fn log_turn_actions(
+    mut match_log: ResMut<MatchLog>,
+    mut action_messages: MessageReader<TurnAction>,
+    player_query: Query<&Player>,
+) {
+    for action in action_messages.read() {
+        let name = player_query.get(action.player).map(|player| player.name.clone()).unwrap_or_default();
+        match_log.write(&format!("{} {:?}", name, action.kind));
+    }
+}
+
+fn log_round_end(
+    mut match_log: ResMut<MatchLog>,
+    mut next_round_timer: ResMut<NextRoundTimer>,
+    round_scores: Res<RoundScores>,
+) {
+    next_round_timer.0.reset();
+
+    let line: Vec<String> = round_scores.players.iter().map(|score| score.breakdown()).collect();
+    match_log.write(&format!("end, scores: {}", line.join("; ")));
+    for winner in round_scores.winners() {
+        match_log.write(&format!("winner: {} with {} points", winner.name, winner.total));
+    }
+}
+
+// start the next round, or wait for new clients once everybody left
+fn next_round(
+    time: Res<Time>,
+    mut next_round_timer: ResMut<NextRoundTimer>,
+    net_session: Res<NetSession>,
+    table: Res<Table>,
+    mut next_state: ResMut<NextState<AppState>>,
+    mut start_messages: MessageWriter<StartNewRound>,
+) {
+    if !next_round_timer.0.tick(time.delta()).just_finished() { return; }
+
+    let connected = match &*net_session {
+        NetSession::Host(host) => host.peers.iter().any(|peer| peer.connected),
+        _ => false,
+    };
+    if connected {
+        start_messages.write(StartNewRound);
+    } else {
+        info!(target: "mygame", "Table {}: every client left, back to the lobby", table.index);
+        next_state.set(AppState::MainMenu);
+    }
+}
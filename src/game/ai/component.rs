@@ -1,10 +1,86 @@
-use bevy::{ecs::entity::Entity, prelude::Component};
+use bevy::{ecs::entity::Entity, prelude::{Component, Resource}};
+use rand::Rng;
 use std::collections::HashMap;
+use crate::game::ai::opponent_model::OpponentModel;
+use crate::game::rules::resource::Ruleset;
 
 // select what player is AI
 #[derive(Component)]
 pub struct AIPlayer {
     pub difficulty: AIDifficulty,
+    pub think_time: AIThinkTime,
+}
+
+// how long the AI waits before drawing and before deciding what to do with the drawn card
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct AIThinkTime {
+    pub base: f32, // seconds
+    pub jitter: f32, // random extra seconds, in the range -jitter..=jitter
+}
+
+// for default, one second with a small jitter so the AI feels natural
+impl Default for AIThinkTime {
+    fn default() -> Self {
+        Self { base: 1.0, jitter: 0.3 }
+    }
+}
+
+impl AIThinkTime {
+    // read "<base>" or "<base>:<jitter>" in seconds, with no jitter the delay is always the same
+    pub fn parse(text: &str) -> Option<Self> {
+        let (base, jitter) = text.split_once(':').unwrap_or((text, "0"));
+        let base: f32 = base.trim().parse().ok()?;
+        let jitter: f32 = jitter.trim().parse().ok()?;
+        Some(Self { base: base.max(0.0), jitter: jitter.max(0.0) })
+    }
+
+    // obtain a new delay, never negative
+    pub fn sample(&self) -> f32 {
+        if self.jitter <= 0.0 {
+            return self.base.max(0.0);
+        }
+        let offset = rand::rng().random_range(-self.jitter..=self.jitter);
+        (self.base + offset).max(0.0)
+    }
+}
+
+// think time of each AI seat, given on the command line (--ai-think [<seat>=]<base>[:<jitter>], repeatable)
+#[derive(Resource, Clone, Default, Debug)]
+pub struct AIThinkSettings {
+    pub all: AIThinkTime, // seats without a value of their own
+    pub seats: HashMap<usize, AIThinkTime>, // by seat index, "--ai-think 2=0.5" sets the seat of Player 2
+}
+
+impl AIThinkSettings {
+    pub fn from_args(mut args: impl Iterator<Item = String>) -> Self {
+        let mut settings = Self::default();
+        while let Some(arg) = args.next() {
+            if arg == "--ai-think" && let Some(value) = args.next() {
+                settings.set(&value);
+            }
+        }
+        settings
+    }
+
+    // read "<base>[:<jitter>]" for every seat or "<seat>=<base>[:<jitter>]" for one, false when it is not valid
+    pub fn set(&mut self, text: &str) -> bool {
+        match text.split_once('=') {
+            Some((seat, think_time)) => {
+                let seat = seat.trim().parse::<usize>().ok().filter(|&seat| seat > 0);
+                let (Some(seat), Some(think_time)) = (seat, AIThinkTime::parse(think_time)) else { return false; };
+                self.seats.insert(seat - 1, think_time);
+            }
+            None => {
+                let Some(think_time) = AIThinkTime::parse(text) else { return false; };
+                self.all = think_time;
+            }
+        }
+        true
+    }
+
+    pub fn for_seat(&self, seat: usize) -> AIThinkTime {
+        self.seats.get(&seat).copied().unwrap_or(self.all)
+    }
 }
 
 // remember revealed cards
@@ -14,7 +90,49 @@ pub struct AIMemory {
     pub initial_cards: Vec<(Entity, u8)>, // two initial cards
     pub seen_discards: Vec<u8>, // cards discarded in graveyard
     pub opponent_known_cards: HashMap<Entity, u8>, // opponent cards revealed by using special actions
+    pub opponent_model: OpponentModel, // estimates of unknown opponent cards inferred from their actions
     pub turns_played: u32, // count of turns have played, 0 for default
+    pub deck: DeckOdds, // cards of the deck in play
+}
+
+// how many cards of each value the deck in play has, and their points (index = value, jokers at 0),
+// the points of a value are the average of its cards, the 12 of golds can be worth 0
+#[derive(Clone, Debug)]
+pub struct DeckOdds {
+    pub counts: [f32; 13],
+    pub points: [f32; 13],
+}
+
+impl DeckOdds {
+    pub fn from_ruleset(ruleset: &Ruleset) -> Self {
+        let mut counts = [0.0; 13];
+        let mut total_points = [0.0; 13];
+        for (suit, value) in ruleset.deck.definition().cards(ruleset.deck_copies) {
+            if let (Some(count), Some(points)) = (counts.get_mut(value as usize), total_points.get_mut(value as usize)) {
+                *count += 1.0;
+                *points += ruleset.face_points(&suit, value) as f32;
+            }
+        }
+        // a value missing from the deck keeps its face points
+        let points = std::array::from_fn(|value| {
+            if counts[value] > 0.0 { total_points[value] / counts[value] } else { value as f32 }
+        });
+        Self { counts, points }
+    }
+
+    // average points of a card of the deck
+    pub fn average(&self) -> f32 {
+        let cards: f32 = self.counts.iter().sum();
+        if cards == 0.0 { return 0.0; }
+        self.counts.iter().zip(&self.points).map(|(count, points)| count * points).sum::<f32>() / cards
+    }
+}
+
+// for default, the classic 48 cards deck
+impl Default for DeckOdds {
+    fn default() -> Self {
+        Self::from_ruleset(&Ruleset::default())
+    }
 }
 
 // for default, create Vectors and HashMaps
@@ -25,11 +143,38 @@ impl Default for AIMemory {
             initial_cards: Vec::new(),
             seen_discards: Vec::new(),
             opponent_known_cards: HashMap::new(),
+            opponent_model: OpponentModel::default(),
             turns_played: 0,
+            deck: DeckOdds::default(),
         }
     }
 }
 
+impl AIMemory {
+    // empty memory for a round played with the deck of the rules
+    pub fn for_rules(ruleset: &Ruleset) -> Self {
+        Self { deck: DeckOdds::from_ruleset(ruleset), ..Self::default() }
+    }
+
+    // move knowledge with the cards exchanged by a special
+    pub fn swap_knowledge(&mut self, own: Entity, target: Entity) {
+        if let Some(value) = self.known_cards.remove(&own) {
+            self.opponent_known_cards.insert(own, value);
+        }
+        if let Some(value) = self.opponent_known_cards.remove(&target) {
+            self.known_cards.insert(target, value);
+        }
+        self.opponent_model.memorised.remove(&target);
+    }
+}
+
+// reaction of the AI to the graveyard top, with the snap rule
+#[derive(Component, Default)]
+pub struct AISnapReaction {
+    pub top: Option<Entity>, // graveyard top the AI is reacting to
+    pub timer: f32, // seconds before snapping, 0 once the AI has reacted
+}
+
 // AI state
 #[derive(Component, Debug)]
 pub enum AIState {
@@ -3,6 +3,7 @@ use crate::game::ai::component::AIMemory;
 use crate::game::graveyard::component::Graveyard;
 use crate::game::hand::component::Hand;
 use crate::game::card::component::Card;
+use rand::seq::IteratorRandom;
 
 /*
 ANALYSIS OF SCORES - calculation functions
@@ -31,36 +32,52 @@ pub fn estimate_opponent_score(
     let mut total = 0.0;
     
     for &card_entity in &hand.cards {
-        if let Some(&value) = ai_memory.opponent_known_cards.get(&card_entity) {
-            total += value as f32;
-        } else {
-            total += calculate_expected_value(ai_memory);
-        }
+        total += estimate_opponent_card(ai_memory, card_entity);
     }
     total
 }
 
-fn calculate_expected_value(ai_memory: &AIMemory,) -> f32 {
-    // number of known cards
-    let known_cards_count = ai_memory.known_cards.len(); // known cards
-    let graveyard_cards_count = ai_memory.seen_discards.len(); // cards seen in graveyard
-    let opponent_cards_count = ai_memory.opponent_known_cards.len(); // known opponent cards
+// value of one opponent card: known value, else inferred from the opponent model, else the average
+pub fn estimate_opponent_card(
+    ai_memory: &AIMemory,
+    card_entity: Entity,
+) -> f32 {
+    if let Some(&value) = ai_memory.opponent_known_cards.get(&card_entity) {
+        return value as f32;
+    }
+
+    let prior = remaining_value_counts(ai_memory);
+    ai_memory.opponent_model.estimate(card_entity, &prior, &ai_memory.deck.points)
+        .unwrap_or_else(|| calculate_expected_value(ai_memory))
+}
 
-    // sum known card values
-    let known_cards_values = ai_memory.known_cards.values().copied().sum::<u8>() as f32; // known cards
-    let graveyard_cards_values = ai_memory.seen_discards.iter().sum::<u8>() as f32; // cards seen in graveyard
-    let opponent_cards_values = ai_memory.opponent_known_cards.values().copied().sum::<u8>() as f32; // known opponent cards
+// how many cards of each value could still be hidden (index = value, jokers at 0), out of the deck in play
+fn remaining_value_counts(ai_memory: &AIMemory) -> [f32; 13] {
+    let mut counts = ai_memory.deck.counts;
 
-    // total number of cards and values
-    let total_count = known_cards_count + graveyard_cards_count + opponent_cards_count;
-    let total_values = known_cards_values + graveyard_cards_values + opponent_cards_values;
+    let seen = ai_memory.known_cards.values()
+        .chain(ai_memory.seen_discards.iter())
+        .chain(ai_memory.opponent_known_cards.values());
 
-    let remaining_cards = (48 - total_count) as f32;
-    let remaining_values = 312.0 - total_values;
+    for &value in seen {
+        if let Some(count) = counts.get_mut(value as usize) {
+            *count = (*count - 1.0).max(0.0);
+        }
+    }
+    counts
+}
 
-    if remaining_cards == 0.0 {return 6.5;};
+// average points of the cards the AI hasn't seen yet
+fn calculate_expected_value(ai_memory: &AIMemory,) -> f32 {
+    let counts = remaining_value_counts(ai_memory);
 
-    remaining_values as f32 / remaining_cards as f32
+    // total number of cards and points left
+    let remaining_cards: f32 = counts.iter().sum();
+    let remaining_values: f32 = counts.iter().zip(&ai_memory.deck.points).map(|(count, points)| count * points).sum();
+
+    if remaining_cards == 0.0 { return ai_memory.deck.average(); }
+
+    remaining_values / remaining_cards
 }
 
 
@@ -187,6 +204,20 @@ pub fn get_best_card_swap(
     None // if it doesn't achieve the conditions, discard
 }
 
+// card to give up when the drawn card must be swapped: the worst known card if it's above the average, else an unknown one
+pub fn get_forced_swap(
+    ai_memory: &AIMemory,
+    hand: &Hand,
+) -> Option<Entity> {
+    let expected_value = calculate_expected_value(ai_memory);
+    let worst_card = get_worst_known_card_hand(ai_memory, hand);
+
+    match worst_card {
+        Some((entity, worst_value)) if worst_value as f32 > expected_value => Some(entity),
+        _ => get_unknown_card_hand(ai_memory, hand).or(worst_card.map(|(entity, _)| entity)),
+    }
+}
+
 // obtain the first unknown card in hand
 fn get_unknown_card_hand(
     ai_memory: &AIMemory,
@@ -202,6 +233,158 @@ fn get_unknown_card_hand(
     None
 }
 
+/*
+SPECIAL CARDS - weigh each special against simply keeping or discarding the card
+*/
+// minimum expected gain to use a 7 instead of just discarding it
+const SWAP_SPECIAL_MIN_GAIN: f32 = 2.0;
+// extra risk added to opponent cards whose value is only estimated
+const UNKNOWN_CARD_RISK: f32 = 1.5;
+// opponent cards that must be memorised before a shuffle (11) is worth it
+const SHUFFLE_MIN_MEMORISED: usize = 2;
+// how far above the average an opponent card must be to freeze it in their hand
+const FREEZE_MIN_EXCESS: f32 = 2.0;
+
+// decide own card and opponent card to exchange with a 7, None if the swap would not help
+pub fn choose_swap_special(
+    ai_memory: &AIMemory,
+    ai_hand: &Hand,
+    opponent_hands: &[&Hand],
+) -> Option<(Entity, Entity)> {
+    let (own_card, own_value, target_card, target_value) = swap_candidates(ai_memory, ai_hand, opponent_hands)?;
+
+    // give up the swap if it would not clearly improve the hand
+    if own_value - target_value < SWAP_SPECIAL_MIN_GAIN {
+        return None;
+    }
+    Some((own_card, target_card))
+}
+
+// decide the cards to look at with a look and swap, looking is free so any pair that might help is worth it
+pub fn choose_look_and_swap(
+    ai_memory: &AIMemory,
+    ai_hand: &Hand,
+    opponent_hands: &[&Hand],
+) -> Option<(Entity, Entity)> {
+    let (own_card, own_value, target_card, target_value) = swap_candidates(ai_memory, ai_hand, opponent_hands)?;
+    (own_value > target_value).then_some((own_card, target_card))
+}
+
+// once both cards of a look and swap are seen, swap only if the opponent card is lower
+pub fn should_swap_looked(
+    ai_memory: &AIMemory,
+    own_card: Entity,
+    target_card: Entity,
+) -> bool {
+    let own_value = ai_memory.known_cards.get(&own_card).map(|&value| value as f32)
+        .unwrap_or_else(|| calculate_expected_value(ai_memory));
+    own_value > estimate_opponent_card(ai_memory, target_card)
+}
+
+// auxiliar function - highest own card and lowest opponent card, with their estimated values
+fn swap_candidates(
+    ai_memory: &AIMemory,
+    ai_hand: &Hand,
+    opponent_hands: &[&Hand],
+) -> Option<(Entity, f32, Entity, f32)> {
+    // own card to give away: the highest known card, or an unknown one at its expected value
+    let expected_value = calculate_expected_value(ai_memory);
+    let (own_card, own_value) = ai_hand.cards.iter()
+        .map(|&card_entity| {
+            let value = ai_memory.known_cards.get(&card_entity)
+                .map(|&value| value as f32)
+                .unwrap_or(expected_value);
+            (card_entity, value)
+        })
+        .max_by(|(_, a), (_, b)| a.total_cmp(b))?;
+
+    // opponent card to steal: the lowest one, preferring cards whose value is known
+    let (target_card, target_value) = opponent_hands.iter()
+        .flat_map(|hand| hand.cards.iter())
+        .map(|&card_entity| {
+            let risk = if ai_memory.opponent_known_cards.contains_key(&card_entity) { 0.0 } else { UNKNOWN_CARD_RISK };
+            (card_entity, estimate_opponent_card(ai_memory, card_entity) + risk)
+        })
+        .min_by(|(_, a), (_, b)| a.total_cmp(b))?;
+
+    Some((own_card, own_value, target_card, target_value))
+}
+
+// decide whose hand to shuffle with an 11, only if they have demonstrably memorised it
+pub fn choose_shuffle_target(
+    ai_memory: &AIMemory,
+    opponents: &[(Entity, &Hand)],
+) -> Option<Entity> {
+    opponents.iter()
+        .map(|(player_entity, hand)| (*player_entity, ai_memory.opponent_model.memorised_count(&hand.cards)))
+        .filter(|(_, memorised)| *memorised >= SHUFFLE_MIN_MEMORISED)
+        .max_by_key(|(_, memorised)| *memorised)
+        .map(|(player_entity, _)| player_entity)
+}
+
+// decide which own card to look at with a 9, None if the whole hand is known and the 9 is not worth using
+pub fn choose_reveal_card(
+    ai_memory: &AIMemory,
+    ai_hand: &Hand,
+) -> Option<Entity> {
+    get_unknown_card_hand(ai_memory, ai_hand)
+}
+
+// decide which opponent card to look at with a spy: in the hand of the opponent closest to winning,
+// the unknown card the opponent model is least sure about
+pub fn choose_spy_card(
+    ai_memory: &AIMemory,
+    opponents: &[(Entity, &Hand)],
+) -> Option<Entity> {
+    let prior = remaining_value_counts(ai_memory);
+    let unknown_cards = |hand: &Hand| -> Vec<Entity> {
+        hand.cards.iter()
+            .filter(|card_entity| !ai_memory.opponent_known_cards.contains_key(card_entity))
+            .copied()
+            .collect()
+    };
+
+    let (_, hand) = opponents.iter()
+        .filter(|(_, hand)| !unknown_cards(hand).is_empty())
+        .min_by(|(_, a), (_, b)| estimate_opponent_score(ai_memory, a).total_cmp(&estimate_opponent_score(ai_memory, b)))?;
+
+    unknown_cards(hand).into_iter()
+        .max_by(|a, b| ai_memory.opponent_model.uncertainty(*a, &prior, &ai_memory.deck.points).total_cmp(&ai_memory.opponent_model.uncertainty(*b, &prior, &ai_memory.deck.points)))
+}
+
+// decide which opponent card to freeze: a high card the opponent will be stuck with, None if no card is clearly high
+pub fn choose_freeze_target(
+    ai_memory: &AIMemory,
+    opponent_hands: &[&Hand],
+) -> Option<Entity> {
+    let expected_value = calculate_expected_value(ai_memory);
+    opponent_hands.iter()
+        .flat_map(|hand| hand.cards.iter())
+        .map(|&card_entity| (card_entity, estimate_opponent_card(ai_memory, card_entity)))
+        .filter(|(_, value)| *value - expected_value >= FREEZE_MIN_EXCESS)
+        .max_by(|(_, a), (_, b)| a.total_cmp(b))
+        .map(|(card_entity, _)| card_entity)
+}
+
+// decide which own card to snap on the graveyard top, only a card whose value the AI is sure about
+pub fn choose_snap_card(
+    ai_memory: &AIMemory,
+    ai_hand: &Hand,
+    top_value: u8,
+) -> Option<Entity> {
+    ai_hand.cards.iter()
+        .copied()
+        .find(|card_entity| ai_memory.known_cards.get(card_entity) == Some(&top_value))
+}
+
+// decide which cards to peek at before the first turn, nothing is known yet so the AI spreads its choice at random
+pub fn choose_peek_cards(
+    ai_hand: &Hand,
+    count: usize,
+) -> Vec<Entity> {
+    ai_hand.cards.iter().copied().choose_multiple(&mut rand::rng(), count)
+}
+
 // decide when shound end the round
 pub fn should_end_round(
     ai_memory: &AIMemory,
@@ -1,6 +1,7 @@
 use bevy::prelude::*;
 pub mod component;
-mod decision;
+pub mod decision;
+mod opponent_model;
 mod system;
 
 use decision::*;
@@ -8,20 +9,25 @@ use system::*;
 
 use crate::game::hand::system::deal_initial_hands;
 use crate::game::gamestate::AppState;
+use crate::game::peek::is_peeking;
+use crate::game::snap::is_snapping;
 
 pub struct AIPlugin;
 
 impl Plugin for AIPlugin {
     fn build(&self, app: &mut App) {
         app
+        .init_resource::<component::AIThinkSettings>()
         .add_systems(
             Update,
-            update_ai_memory.run_if(in_state(AppState::PlayerTurn)).before(ai_turn_controller)
+            (update_ai_memory, observe_opponent_actions).run_if(in_state(AppState::PlayerTurn)).before(ai_turn_controller)
         )
         .add_systems(
             Update,
-            ai_turn_controller.run_if(in_state(AppState::PlayerTurn))
+            ai_turn_controller.run_if(in_state(AppState::PlayerTurn).and(not(is_peeking)))
         )
+        .add_systems(Update, ai_peek.run_if(in_state(AppState::PlayerTurn).and(is_peeking)))
+        .add_systems(Update, ai_snap.after(update_ai_memory).run_if(in_state(AppState::PlayerTurn).and(is_snapping).and(not(is_peeking))))
         .add_systems(OnEnter(AppState::PlayerTurn), initialize_ai_memory.after(deal_initial_hands));
     }
 }
\ No newline at end of file
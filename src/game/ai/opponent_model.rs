@@ -0,0 +1,125 @@
+use bevy::prelude::Entity;
+use std::collections::{HashMap, HashSet};
+
+/*
+OPPONENT MODEL - probabilistic estimate of each opponent card
+the AI can't see face-down cards, but it can infer from what the opponent does:
+    - discarding a card straight away means their hand is probably lower than it
+    - swapping a card out means it was one of their worst cards, so the rest are probably lower
+    - swapping a deck card in means the new card is probably lower than the one thrown away
+*/
+
+// likelihood multipliers applied to values above the observed threshold
+const SWAPPED_IN_ABOVE_REPLACED: f32 = 0.25; // new card is rarely worse than the card it replaced
+const KEPT_ABOVE_REPLACED: f32 = 0.6; // other slots are usually better than the card thrown away
+const KEPT_ABOVE_DISCARDED: f32 = 0.75; // a discarded deck card hints the hand is lower
+const KEPT_ABOVE_PASSED: f32 = 0.85; // a graveyard card taken back and discarded is a weak hint
+
+// relative likelihood of each card value (index = value, jokers at 0)
+#[derive(Clone, Debug)]
+pub struct CardEstimate {
+    pub weights: [f32; 13],
+}
+
+impl Default for CardEstimate {
+    fn default() -> Self {
+        Self { weights: [1.0; 13] }
+    }
+}
+
+impl CardEstimate {
+    // lower the likelihood of every value greater than the bound
+    pub fn penalize_above(&mut self, bound: u8, factor: f32) {
+        for weight in self.weights.iter_mut().skip(bound as usize + 1) {
+            *weight *= factor;
+        }
+    }
+
+    // expected points combining the remaining card counts (prior) with the observed weights
+    pub fn expected_value(&self, prior: &[f32; 13], points: &[f32; 13]) -> Option<f32> {
+        let mut total_weight = 0.0;
+        let mut total_value = 0.0;
+
+        for ((weight, count), value) in self.weights.iter().zip(prior).zip(points) {
+            let weight = weight * count;
+            total_weight += weight;
+            total_value += weight * value;
+        }
+
+        if total_weight <= 0.0 { return None; }
+        Some(total_value / total_weight)
+    }
+
+    // spread of the possible values around the expected one, higher means less is known about the card
+    pub fn variance(&self, prior: &[f32; 13], points: &[f32; 13]) -> Option<f32> {
+        let mean = self.expected_value(prior, points)?;
+        let mut total_weight = 0.0;
+        let mut total_spread = 0.0;
+
+        for ((weight, count), value) in self.weights.iter().zip(prior).zip(points) {
+            let weight = weight * count;
+            total_weight += weight;
+            total_spread += weight * (value - mean).powi(2);
+        }
+        Some(total_spread / total_weight)
+    }
+}
+
+#[derive(Default, Clone, Debug)]
+pub struct OpponentModel {
+    pub slots: HashMap<Entity, CardEstimate>,
+    pub memorised: HashSet<Entity>, // opponent cards the opponent has demonstrably seen
+}
+
+impl OpponentModel {
+    // opponent discarded the drawn card, so the cards they hold are probably lower
+    pub fn observe_discard(&mut self, hand: &[Entity], value: u8, from_deck: bool) {
+        let factor = if from_deck { KEPT_ABOVE_DISCARDED } else { KEPT_ABOVE_PASSED };
+        for &card_entity in hand {
+            self.slots.entry(card_entity).or_default().penalize_above(value, factor);
+        }
+    }
+
+    // opponent swapped a card out of their hand
+    pub fn observe_swap(&mut self, hand: &[Entity], drawn_card: Entity, drawn_value: Option<u8>, replaced_card: Entity, replaced_value: u8) {
+        // the replaced card is in the graveyard now, the drawn card was seen by the opponent
+        self.slots.remove(&replaced_card);
+        self.memorised.remove(&replaced_card);
+        self.memorised.insert(drawn_card);
+
+        for &card_entity in hand {
+            if card_entity == drawn_card {
+                // a known drawn value does not need an estimate
+                if drawn_value.is_none() {
+                    self.slots.entry(card_entity).or_default().penalize_above(replaced_value.saturating_sub(1), SWAPPED_IN_ABOVE_REPLACED);
+                }
+            } else {
+                self.slots.entry(card_entity).or_default().penalize_above(replaced_value, KEPT_ABOVE_REPLACED);
+            }
+        }
+    }
+
+    // number of cards in a hand the opponent has memorised
+    pub fn memorised_count(&self, hand: &[Entity]) -> usize {
+        hand.iter().filter(|card_entity| self.memorised.contains(card_entity)).count()
+    }
+
+    // after a shuffle the opponent no longer knows where their cards are
+    pub fn forget_memorised(&mut self, hand: &[Entity]) {
+        for card_entity in hand {
+            self.memorised.remove(card_entity);
+        }
+    }
+
+    // estimated value of an opponent card, None if there is no information about it
+    pub fn estimate(&self, card_entity: Entity, prior: &[f32; 13], points: &[f32; 13]) -> Option<f32> {
+        self.slots.get(&card_entity).and_then(|estimate| estimate.expected_value(prior, points))
+    }
+
+    // how unsure the model is about an opponent card, a card never observed is as open as the prior
+    pub fn uncertainty(&self, card_entity: Entity, prior: &[f32; 13], points: &[f32; 13]) -> f32 {
+        self.slots.get(&card_entity).cloned().unwrap_or_default()
+            .variance(prior, points)
+            .unwrap_or(0.0)
+    }
+}
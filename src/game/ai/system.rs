@@ -1,6 +1,6 @@
 use bevy::prelude::*;
-use bevy::window::PrimaryWindow;
-use crate::game::ai::component::{AIPlayer, AIMemory, AIState};
+use crate::game::layout::TableLayout;
+use crate::game::ai::component::{AIPlayer, AIMemory, AIState, AISnapReaction};
 use crate::game::ai::decision::should_end_round;
 use crate::game::card::utils::{card_swap, discard_card};
 use crate::game::player::component::Player;
@@ -11,85 +11,134 @@ use crate::game::graveyard::component::Graveyard;
 use crate::game::deck::component::Deck;
 use crate::game::AppState;
 use crate::ui::soundtrack::event::{PlayCardDraw, PlayCardPlace};
-use crate::game::special_cards::resource::{SpecialCardEffect, SpecialEffect};
+use crate::game::special_cards::resource::{SpecialCardEffect, SpecialEffectRegistry};
+use crate::game::special_cards::handler::AiSpecialView;
+use crate::game::turn_player::event::{TurnAction, TurnActionKind};
+use crate::game::match_config::resource::GameSpeed;
 
 use crate::game::ai::{estimate_own_score, estimate_opponent_score};
 use crate::game::card::refactor_handles::{handle_graveyard_logic, handle_deck_logic};
-use crate::game::ai::{should_draw, should_swap, get_worst_known_card_hand, get_best_card_swap};
-
-// start ai memory
+use crate::game::ai::{should_draw, should_swap, get_worst_known_card_hand, get_best_card_swap, get_forced_swap};
+use crate::game::ai::{choose_peek_cards, choose_snap_card};
+use crate::game::snap::{event::SnapAttempt, resource::SnapState};
+use crate::game::rules::resource::Ruleset;
+use crate::game::peek::resource::PeekPhase;
+use crate::game::round_end::resource::RoundCaller;
+
+// start ai memory, the cards are learnt in the peek
 pub fn initialize_ai_memory(
     mut commands: Commands,
-    ai_query: Query<(Entity, &Player), With<AIPlayer>>,
-    hand_query: Query<&Hand>,
-    card_query: Query<&Card>,
+    ai_query: Query<Entity, With<AIPlayer>>,
+    ruleset: Res<Ruleset>,
 ) {
-    info!(target: "mygame", "initialize_ai_memory called!");
-    for (ai_entity, player) in ai_query.iter() {
-        info!(target: "mygame", "Found AI player: {:?}", ai_entity);
-
-        // obtain player hand
-        let Ok(hand) = hand_query.get(player.hand) else { 
-            warn!(target: "mygame", "Could not get AI hand");
-            continue; 
-        };
-        
-        // leave memory as default
-        let mut memory = AIMemory::default();
-        
-        // iterate first two cards and save in the memory
-        for (i, &card_entity) in hand.cards.iter().take(2).enumerate() {
-            if let Ok(card) = card_query.get(card_entity) {
-                memory.known_cards.insert(card_entity, card.value);
-                memory.initial_cards.push((card_entity, card.value));
-                info!(target: "mygame", "AI memorized initial card {}: value {}", i, card.value);
-            }
-        }
-        
-        // select Idle state
+    for ai_entity in ai_query.iter() {
         commands.entity(ai_entity).insert((
-            memory,
+            AIMemory::for_rules(&ruleset),
             AIState::Idle,
+            AISnapReaction::default(),
         ));
-        
         info!(target: "mygame", "AI memory initialized for player: {:?}", ai_entity);
     }
 }
 
+// the AI chooses the cards it peeks at, and memorizes them as its initial cards
+pub fn ai_peek(
+    mut peek_phase: ResMut<PeekPhase>,
+    mut ai_query: Query<(&Player, &mut AIMemory), With<AIPlayer>>,
+    hand_query: Query<&Hand>,
+    mut card_query: Query<&mut Card>,
+    time: Res<Time>,
+) {
+    let count = peek_phase.count;
+    for peeking in peek_phase.players.iter_mut().filter(|peeking| peeking.is_choosing()) {
+        let Ok((player, mut memory)) = ai_query.get_mut(peeking.player) else { continue; };
+        let Ok(hand) = hand_query.get(player.hand) else { continue; };
+
+        for card_entity in choose_peek_cards(hand, count) {
+            let Ok(mut card) = card_query.get_mut(card_entity) else { continue; };
+            card.reveal_to(peeking.player);
+            memory.known_cards.insert(card_entity, card.value);
+            memory.initial_cards.push((card_entity, card.value));
+            peeking.pick(card_entity);
+            info!(target: "mygame", "AI peeked at a card: value {}", card.value);
+        }
+        peeking.start_showing(count, hand.cards.len(), time.elapsed_secs());
+    }
+}
+
 // update ai memory during the game
 pub fn update_ai_memory(
     mut ai_query: Query<(Entity, &Player, &mut AIMemory), With<AIPlayer>>,
     card_query: Query<(Entity, &Card)>,
     hand_query: Query<&Hand>,
 ) {
-    let Ok((ai_entity, player, mut memory)) = ai_query.single_mut() else { return; };
-
-    // iterate OWN cards with face_up = true and save in memory
-    if let Ok(hand) = hand_query.get(player.hand) {
-        for &card_entity in &hand.cards {
-            if let Ok((entity, card)) = card_query.get(card_entity) {
-                if card.face_up {
-                    memory.known_cards.insert(entity, card.value);
+    for (ai_entity, player, mut memory) in ai_query.iter_mut() {
+        // iterate OWN cards the AI can see and save in memory
+        if let Ok(hand) = hand_query.get(player.hand) {
+            for &card_entity in &hand.cards {
+                if let Ok((entity, card)) = card_query.get(card_entity) {
+                    if card.is_visible_to(ai_entity) {
+                        memory.known_cards.insert(entity, card.value);
+                    }
+                }
+            }
+        }
+            
+        // save discarded cards of graveyard to memory
+        for (_, card) in card_query.iter() {
+            if matches!(card.position, CardPosition::Graveyard) {
+                // verify if have card values in graveyard
+                if !memory.seen_discards.contains(&card.value) {
+                    memory.seen_discards.push(card.value);
                 }
             }
         }
-    }
         
-    // save discarded cards of graveyard to memory
-    for (_, card) in card_query.iter() {
-        if matches!(card.position, CardPosition::Graveyard) {
-            // verify if have card values in graveyard
-            if !memory.seen_discards.contains(&card.value) {
-                memory.seen_discards.push(card.value);
+        // save opponent cards to memory when the AI can see them
+        for (entity, card) in card_query.iter() {
+            if let CardPosition::Hand(owner) = card.position {
+                if owner == ai_entity { continue; }
+                if card.is_visible_to(ai_entity) {
+                    memory.opponent_known_cards.insert(entity, card.value);
+                }
+                // the owner has seen this card (public or private reveal)
+                if card.is_visible_to(owner) {
+                    memory.opponent_model.memorised.insert(entity);
+                }
             }
         }
     }
-    
-    // save opponent cards to memory when it turns over (face_up = true)
-    for (entity, card) in card_query.iter() {
-        if let CardPosition::Hand(owner) = card.position {
-            if owner != ai_entity && card.face_up {
-                memory.opponent_known_cards.insert(entity, card.value);
+}
+
+// infer opponent cards from their draws, swaps and discards
+pub fn observe_opponent_actions(
+    mut ai_query: Query<(Entity, &mut AIMemory), With<AIPlayer>>,
+    mut action_messages: MessageReader<TurnAction>,
+    player_query: Query<&Player>,
+    hand_query: Query<&Hand>,
+) {
+    for action in action_messages.read() {
+        // obtain the hand of the player who acted
+        let Ok(player) = player_query.get(action.player) else { continue; };
+        let Ok(hand) = hand_query.get(player.hand) else { continue; };
+
+        for (ai_entity, mut memory) in ai_query.iter_mut() {
+            // ignore own actions
+            if ai_entity == action.player { continue; }
+
+            match &action.kind {
+                TurnActionKind::Discard { value, from_deck } => {
+                    memory.opponent_model.observe_discard(&hand.cards, *value, *from_deck);
+                }
+                TurnActionKind::Swap { drawn_card, drawn_value, replaced_card, replaced_value } => {
+                    memory.opponent_known_cards.remove(replaced_card);
+                    memory.opponent_model.observe_swap(&hand.cards, *drawn_card, *drawn_value, *replaced_card, *replaced_value);
+
+                    // a card taken from the graveyard is public, remember it exactly
+                    if let Some(value) = drawn_value {
+                        memory.opponent_known_cards.insert(*drawn_card, *value);
+                    }
+                }
             }
         }
     }
@@ -100,54 +149,43 @@ pub fn ai_turn_controller(
     mut commands: Commands,
     time: Res<Time>,
     turn_query: ResMut<Turn>,
-    mut ai_query: Query<(Entity, &Player, &mut AIState, &mut AIMemory), With<AIPlayer>>,
+    mut ai_query: Query<(Entity, &Player, &AIPlayer, &mut AIState, &mut AIMemory)>,
     mut hand_query: Query<&mut Hand>,
     mut graveyard_query: Query<&mut Graveyard>,
     mut card_query: Query<(Entity, &mut Transform, &mut Card), With<Card>>,
     deck_query: Query<&mut Deck>,
     player_query: Query<(Entity, &Player)>,
-    windows: Query<&Window, With<PrimaryWindow>>,
-    mut next_state: ResMut<NextState<AppState>>,
-    draw_message: MessageWriter<PlayCardDraw>,
-    place_message: MessageWriter<PlayCardPlace>,
+    (layout, ruleset, registry): (Res<TableLayout>, Res<Ruleset>, Res<SpecialEffectRegistry>),
+    (mut next_state, mut round_caller): (ResMut<NextState<AppState>>, ResMut<RoundCaller>),
+    (draw_message, place_message, action_message): (MessageWriter<PlayCardDraw>, MessageWriter<PlayCardPlace>, MessageWriter<TurnAction>),
     selected_query: Query<Entity, With<Selected>>,
+    special_effect: Option<ResMut<SpecialCardEffect>>,
+    game_speed: Res<GameSpeed>,
 ) {
-    // search AI player
-    let Ok((ai_entity, ai_player, mut ai_state, mut ai_memory)) = ai_query.single_mut() else {
-        info!(target: "mygame", "No AI player found in query!");
-        return;  // there is no AI
-    };
-
-    // check if it's the AI's turn
-    if turn_query.current_player != ai_entity {
-        if !matches!(*ai_state, AIState::Idle) {
+    // AI players waiting their turn stay in Idle
+    for (ai_entity, _, _, mut ai_state, _) in ai_query.iter_mut() {
+        if turn_query.current_player != ai_entity && !matches!(*ai_state, AIState::Idle) {
             *ai_state = AIState::Idle; // if is not turn, make sure it's in Idle
         }
-        return;
     }
 
+    // search the AI player whose turn it is
+    let Ok((ai_entity, ai_player, ai_settings, mut ai_state, mut ai_memory)) = ai_query.get_mut(turn_query.current_player) else {
+        return;  // it's not an AI turn
+    };
+
+    // do nothing while the game is paused
+    if !game_speed.is_running() { return; }
+
     // match of ai states
     match &mut *ai_state {
         AIState::Idle => {
-            // save the first two cards and the cards with face up in memory
-            if ai_memory.initial_cards.is_empty() {
-                let Ok(ai_hand) = hand_query.get(ai_player.hand) else { return; };
-                for (_, &card_entity) in ai_hand.cards.iter().take(2).enumerate() {
-                    if let Ok((_, _, card)) = card_query.get(card_entity) {
-                        if card.face_up {
-                            ai_memory.known_cards.insert(card_entity, card.value);
-                            ai_memory.initial_cards.push((card_entity, card.value));
-                        }
-                    }
-                }
-            }
-
-            *ai_state = AIState::Thinking { timer: 1.0 }; // wait a few seconds
+            *ai_state = AIState::Thinking { timer: ai_settings.think_time.sample() }; // wait a few seconds
             info!(target: "mygame", "AI turn started, thinking...");
         }
 
         AIState::Thinking { timer } => {
-            *timer -= time.delta_secs();
+            *timer -= game_speed.delta_secs(&time);
 
             if *timer <= 0.0 { // when timer finish, change to DecidingDraw state
                 *ai_state = AIState::DecidingDraw;
@@ -163,8 +201,9 @@ pub fn ai_turn_controller(
             let worst_card = get_worst_known_card_hand(&ai_memory, ai_hand)
                 .map(|(_, value)| value);
 
-            // deciding
-            let should_draw = should_draw(&ai_memory, &graveyard_query, worst_card, &card_query);
+            // deciding, the AI can't take back the card it just discarded
+            let can_take = graveyard_query.single().is_ok_and(|graveyard| ruleset.can_take_from_graveyard(graveyard, ai_entity));
+            let should_draw = can_take && should_draw(&ai_memory, &graveyard_query, worst_card, &card_query);
 
             // if should_draw = true, draw from graveyard, if it's false, from deck
             if should_draw {
@@ -185,14 +224,14 @@ pub fn ai_turn_controller(
 
             // if AI draw a card, change to DecidingSwap state (passing drawn_entity parameter)
             if let Some((drawn_entity,_, drawn_card)) = drawn_card {
-                *ai_state = AIState::ThinkingSwap { timer: 1.0, drawn_card: drawn_entity };
+                *ai_state = AIState::ThinkingSwap { timer: ai_settings.think_time.sample(), drawn_card: drawn_entity };
 
                 info!(target: "mygame", "AI drew card with value {}", drawn_card.value);
             };
         }
 
         AIState::ThinkingSwap { timer, drawn_card } => {
-            *timer -= time.delta_secs();
+            *timer -= game_speed.delta_secs(&time);
 
             if *timer <= 0.0 {
                 // obtain drawn card
@@ -202,17 +241,10 @@ pub fn ai_turn_controller(
 
                 // verify if drawn card is special card
                 if card.from_deck {
-                    let special_effect_type = match card.value {
-                        11 => Some(SpecialEffect::Shuffle),
-                        9 => Some(SpecialEffect::Reveal), 
-                        7 => Some(SpecialEffect::Swap),
-                        _ => None,
-                    };
-
-                    // if it is, use special effects
-                    if let Some(effect) = special_effect_type {
+                    // if it is, weigh using special effects
+                    if let Some(effect) = ruleset.special_for(card.value) {
                         *ai_state = AIState::ActivatingSpecial { drawn_card: *drawn_card };
-                        info!(target: "mygame", "AI activated special card: {:?}", effect);
+                        info!(target: "mygame", "AI considering special card: {:?}", effect);
                         return;
                     }
                 }
@@ -227,73 +259,34 @@ pub fn ai_turn_controller(
             // obtain drawn card
             let Ok((_, _, card)) = card_query.get(*drawn_card) else { return; };
 
-            // use verify card value of drawn card and include the specific effect
-            match card.value {
-                11 => {
-                    // obtain opponent
-                    let opponent = player_query.iter()
-                        .find(|(entity, _)| *entity != ai_entity);
-
-                    // insert Shuffle effect
-                    if let Some((opponent_entity, _)) = opponent {
-                        commands.insert_resource(SpecialCardEffect {
-                            card_entity: Some(*drawn_card),
-                            effect_type: Some(SpecialEffect::Shuffle),
-                            awaiting_target: false,
-                            target_player: Some(opponent_entity),
-                            ..Default::default()
-                        });
-
-                        info!(target: "mygame", "AI shuffled opponent's hand");
-                    }
-                }
-                7 => {
-                    // obtain opponent
-                    let opponent = player_query.iter()
-                        .find(|(entity, _)| *entity != ai_entity);
-
-                    if let Some((_, opponent_player)) = opponent {
-                        // obtain opponent hand
-                        let Ok(opponent_hand) = hand_query.get(opponent_player.hand) else { return; };
-                        
-                        // select a opponent card of his hand
-                        let target_card = ai_memory.opponent_known_cards.iter()
-                            .max_by_key(|(_, value)| *value)
-                            .map(|(entity, _)| *entity)
-                            .or(opponent_hand.cards.first().copied());
-                        
-                        // obtain ai hand
-                        let Ok(ai_hand) = hand_query.get(ai_player.hand) else { return; };
-                        
-                        // select own worst known card in hand
-                        let own_card = get_worst_known_card_hand(&ai_memory, ai_hand)
-                            .map(|(entity, _)| entity);
-                        
-                        // insert swap effect
-                        if let (Some(target), Some(own)) = (target_card, own_card) {
-                            commands.insert_resource(SpecialCardEffect {
-                                card_entity: Some(*drawn_card),
-                                effect_type: Some(SpecialEffect::Swap),
-                                awaiting_target: false,
-                                awaiting_own_card: false,
-                                target_card: Some(target),
-                                own_card: Some(own),
-                                ..Default::default()
-                            });
-                            info!(target: "mygame", "AI will swap cards");
-                        }
-                    }
-                }
-                9 => {
-                    // insert reveal effect
-                    commands.insert_resource(SpecialCardEffect {
-                        card_entity: Some(*drawn_card),
-                        effect_type: Some(SpecialEffect::Reveal),
-                        awaiting_target: false,
-                        ..Default::default()
-                    });
+            // obtain AI's hand and opponents hands
+            let Ok(ai_hand) = hand_query.get(ai_player.hand) else { return; };
+            let opponents: Vec<(Entity, &Hand)> = player_query.iter()
+                .filter(|(entity, _)| *entity != ai_entity)
+                .filter_map(|(entity, player)| hand_query.get(player.hand).ok().map(|hand| (entity, hand)))
+                .collect();
+            let opponent_hands: Vec<&Hand> = opponents.iter().map(|(_, hand)| *hand).collect();
+
+            // frozen cards can't take part in a swap
+            let unfrozen = |hand: &Hand| Hand {
+                cards: hand.cards.iter().copied().filter(|&card_entity| card_query.get(card_entity).is_ok_and(|(_, _, card)| !card.frozen)).collect(),
+            };
+            let free_hand = unfrozen(ai_hand);
+            let free_opponents: Vec<Hand> = opponent_hands.iter().map(|hand| unfrozen(hand)).collect();
+            let free_opponent_hands: Vec<&Hand> = free_opponents.iter().collect();
+
+            // weigh the special against simply keeping or discarding the card
+            if let Some(effect) = ruleset.special_for(card.value) && let Some(handler) = registry.get(effect) {
+                let mut view = AiSpecialView {
+                    memory: &mut ai_memory,
+                    hand: ai_hand,
+                    free_hand: &free_hand,
+                    opponents: &opponents,
+                    free_opponent_hands: &free_opponent_hands,
+                };
+                if let Some(targets) = handler.ai_targets(&mut view) {
+                    commands.insert_resource(SpecialCardEffect::targeted(*drawn_card, effect, targets));
                 }
-                _ => {}
             }
 
             // change to deciding swap state
@@ -301,17 +294,43 @@ pub fn ai_turn_controller(
         }
 
         AIState::DecidingSwap { drawn_card } => {
+            // wait for the special to finish, a look and swap asks for a decision once both cards are seen
+            if let Some(mut effect) = special_effect && effect.effect_type.is_some() {
+                if effect.awaiting_decision && effect.swap_decided.is_none()
+                    && let (Some(own), Some(target)) = (effect.own_card, effect.target_card) {
+                    for card_entity in [own, target] {
+                        if let Ok((_, _, card)) = card_query.get(card_entity) && card.is_visible_to(ai_entity) {
+                            let memory = if card_entity == own { &mut ai_memory.known_cards } else { &mut ai_memory.opponent_known_cards };
+                            memory.insert(card_entity, card.value);
+                        }
+                    }
+                    let Some(handler) = effect.effect_type.and_then(|effect_type| registry.get(effect_type)) else { return; };
+                    let swap = handler.ai_decide(&mut ai_memory, &effect);
+                    effect.swap_decided = Some(swap);
+                    info!(target: "mygame", "AI looked at both cards and {} them", if swap { "swaps" } else { "keeps" });
+                }
+                return;
+            }
+
             // obtain drawn card
             let Ok((_, _, drawn_card_comp)) = card_query.get(*drawn_card) else { return; };
             let drawn_value = drawn_card_comp.value;
+            let must_swap = ruleset.must_swap(drawn_card_comp);
 
-            // obtain AI's hand
+            // obtain AI's hand, a frozen card stays where it is
             let Ok(ai_hand) = hand_query.get(ai_player.hand) else { return; };
+            let ai_hand = &Hand {
+                cards: ai_hand.cards.iter().copied().filter(|&card_entity| card_query.get(card_entity).is_ok_and(|(_, _, card)| !card.frozen)).collect(),
+            };
 
-            // deciding
+            // deciding, a card taken from the graveyard replaces the least useful card when the rules say so
             let should_swap = should_swap(drawn_value, &ai_memory, ai_hand);
 
-            if should_swap {
+            if must_swap && !ai_hand.cards.is_empty() {
+                let target_card = get_best_card_swap(drawn_value, &ai_memory, ai_hand)
+                    .or_else(|| get_forced_swap(&ai_memory, ai_hand));
+                *ai_state = AIState::ExecutingSwap { drawn_card_entity: *drawn_card, target_card_entity: target_card };
+            } else if should_swap {
                 // obtain best card to swap
                 let target_card = get_best_card_swap(drawn_value, &ai_memory, ai_hand);
 
@@ -333,10 +352,15 @@ pub fn ai_turn_controller(
             // if have target_card, execute card_swap system, if not, discard_card system
             match target_card_entity {
                 Some(target) => {
-                    card_swap(*target, &mut card_query, &mut graveyard_query, turn_query, hand_query.reborrow(), &player_query, windows, &mut commands, &selected_query);
+                    // the drawn card goes face down, but the AI has seen it
+                    if let Ok((_, _, drawn_card)) = card_query.get(*drawn_card_entity) {
+                        ai_memory.known_cards.insert(*drawn_card_entity, drawn_card.value);
+                    }
+                    ai_memory.known_cards.remove(target);
+                    card_swap(*target, &mut card_query, &mut graveyard_query, turn_query, hand_query.reborrow(), &player_query, &layout, &mut commands, &selected_query, action_message);
                 }
                 None => {
-                    discard_card(*drawn_card_entity, &mut card_query, &mut graveyard_query, turn_query, &player_query, &mut commands, &selected_query, place_message);
+                    discard_card(*drawn_card_entity, &mut card_query, &mut graveyard_query, turn_query, &player_query, &mut commands, &selected_query, place_message, action_message);
                 }
             }
 
@@ -352,11 +376,14 @@ pub fn ai_turn_controller(
                 return;
             };
 
-            // obtain opponent player
+            // obtain the opponent with the lowest estimated score
             let opponent = player_query.iter()
-                .find(|(entity, _)| *entity != ai_entity);
+                .filter(|(entity, _)| *entity != ai_entity)
+                .filter_map(|(_, player)| hand_query.get(player.hand).ok().map(|hand| (player, estimate_opponent_score(&ai_memory, hand))))
+                .min_by(|(_, a), (_, b)| a.total_cmp(b))
+                .map(|(player, _)| player);
 
-            if let Some((_, opponent_player)) = opponent {
+            if let Some(opponent_player) = opponent {
                 // get opponent hand
                 let Ok(opponent_hand) = hand_query.get(opponent_player.hand) else {
                     *ai_state = AIState::Idle;
@@ -377,6 +404,7 @@ pub fn ai_turn_controller(
 
                 // if should_end = true, finish round, if not, change turn
                 if should_end {
+                    round_caller.0 = Some(ai_entity);
                     next_state.set(AppState::RoundEnd);
                     info!(target: "mygame", "AI decided to end the round!");
                 } else {
@@ -386,4 +414,46 @@ pub fn ai_turn_controller(
             *ai_state = AIState::Idle;
         }
     }
-}
\ No newline at end of file
+}
+
+// snap rule: throw a known card matching the graveyard top, after a moment to react
+pub fn ai_snap(
+    time: Res<Time>,
+    game_speed: Res<GameSpeed>,
+    turn: Res<Turn>,
+    snap_state: Res<SnapState>,
+    graveyard_query: Query<&Graveyard>,
+    card_query: Query<&Card>,
+    hand_query: Query<&Hand>,
+    mut ai_query: Query<(Entity, &Player, &AIPlayer, &AIMemory, &mut AISnapReaction)>,
+    mut snap_messages: MessageWriter<SnapAttempt>,
+) {
+    if !game_speed.is_running() { return; }
+    let Some(&top) = graveyard_query.single().ok().and_then(|graveyard| graveyard.cards.last()) else { return; };
+    if snap_state.claimed == Some(top) { return; }
+    let Ok(top_card) = card_query.get(top) else { return; };
+
+    for (ai_entity, player, ai_settings, ai_memory, mut reaction) in ai_query.iter_mut() {
+        // a new graveyard top, the AI takes a moment to notice it
+        if reaction.top != Some(top) {
+            *reaction = AISnapReaction { top: Some(top), timer: ai_settings.think_time.sample().max(f32::EPSILON) };
+            continue;
+        }
+        if reaction.timer <= 0.0 { continue; }
+        reaction.timer -= game_speed.delta_secs(&time);
+        if reaction.timer > 0.0 { continue; }
+        reaction.timer = 0.0;
+
+        // the AI finishes its turn before snapping, a frozen card stays where it is
+        if turn.current_player == ai_entity && turn.has_drawn_card { continue; }
+        let Ok(ai_hand) = hand_query.get(player.hand) else { continue; };
+        let free_hand = Hand {
+            cards: ai_hand.cards.iter().copied().filter(|&card_entity| card_query.get(card_entity).is_ok_and(|card| !card.frozen)).collect(),
+        };
+
+        if let Some(card_entity) = choose_snap_card(ai_memory, &free_hand, top_card.value) {
+            snap_messages.write(SnapAttempt { player: ai_entity, card: card_entity, top });
+            info!(target: "mygame", "AI snaps a {} on the graveyard", top_card.value);
+        }
+    }
+}
@@ -0,0 +1,113 @@
+use bevy::prelude::*;
+use std::collections::HashMap;
+use std::io::{self, BufRead, BufReader, Write};
+use std::process::{Child, ChildStdin, Command, Stdio};
+use std::sync::Mutex;
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use crate::game::bot::protocol::GameToBot;
+
+// player controlled by an external bot process (or a remote player, see net)
+#[derive(Component)]
+pub struct BotPlayer {
+    pub link: Box<dyn BotLink>,
+    pub known_cards: HashMap<Entity, u8>, // own cards the bot has seen
+}
+
+// bot state, same flow as AIState but waiting for the bot replies
+#[derive(Component, Debug, Default)]
+pub enum BotState {
+    #[default]
+    Idle,                                      // wait turn
+    AwaitingDraw,                              // turn sent, waiting draw or call
+    ExecutingDraw,                             // draw card
+    AwaitingChoice { drawn_card: Entity },     // drawn card sent, waiting swap, discard or special
+    ResolvingSpecial { drawn_card: Entity },   // wait until the special effect is applied
+    DecidingLook { drawn_card: Entity },       // look and swap values sent, waiting the decision
+}
+
+// connection speaking the line-based JSON protocol
+pub trait BotLink: Send + Sync {
+    fn send_line(&mut self, line: &str) -> io::Result<()>;
+
+    // obtain the next reply line without blocking
+    fn try_recv(&self) -> Result<String, TryRecvError>;
+
+    // send one message as a JSON line
+    fn send(&mut self, message: &GameToBot) -> io::Result<()> {
+        let line = serde_json::to_string(message).map_err(io::Error::other)?;
+        self.send_line(&line)
+    }
+}
+
+// child process speaking the line-based JSON protocol
+pub struct BotProcess {
+    child: Child,
+    stdin: ChildStdin,
+    lines: Mutex<Receiver<String>>,
+}
+
+impl BotProcess {
+    // start the bot, the command is split by whitespace (program and arguments)
+    pub fn spawn(command: &str) -> io::Result<Self> {
+        let mut parts = command.split_whitespace();
+        let program = parts.next().ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "empty bot command"))?;
+
+        let mut child = Command::new(program)
+            .args(parts)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()?;
+
+        let stdin = child.stdin.take().ok_or_else(|| io::Error::other("bot stdin not available"))?;
+        let stdout = child.stdout.take().ok_or_else(|| io::Error::other("bot stdout not available"))?;
+
+        // read replies in a thread so the game never blocks on the bot
+        let (sender, receiver) = mpsc::channel();
+        std::thread::spawn(move || {
+            for line in BufReader::new(stdout).lines() {
+                let Ok(line) = line else { break; };
+                if sender.send(line).is_err() { break; }
+            }
+        });
+
+        Ok(Self { child, stdin, lines: Mutex::new(receiver) })
+    }
+}
+
+impl BotLink for BotProcess {
+    fn send_line(&mut self, line: &str) -> io::Result<()> {
+        writeln!(self.stdin, "{}", line)?;
+        self.stdin.flush()
+    }
+
+    fn try_recv(&self) -> Result<String, TryRecvError> {
+        match self.lines.lock() {
+            Ok(lines) => lines.try_recv(),
+            Err(_) => Err(TryRecvError::Disconnected),
+        }
+    }
+}
+
+// stop the bot when the player is despawned
+impl Drop for BotProcess {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+// bot commands given on the command line (--bot "<command>")
+#[derive(Resource, Default)]
+pub struct BotCommands(pub Vec<String>);
+
+impl BotCommands {
+    pub fn from_args(mut args: impl Iterator<Item = String>) -> Self {
+        let mut commands = Vec::new();
+        while let Some(arg) = args.next() {
+            if arg == "--bot" && let Some(command) = args.next() {
+                commands.push(command);
+            }
+        }
+        Self(commands)
+    }
+}
@@ -0,0 +1,29 @@
+use bevy::prelude::*;
+pub mod component;
+pub mod protocol;
+mod system;
+
+use system::{initialize_bot_memory, bot_peek, update_bot_memory, forward_turn_actions, notify_round_end, bot_turn_controller};
+use crate::game::hand::system::deal_initial_hands;
+use crate::game::gamestate::AppState;
+use crate::game::peek::is_peeking;
+
+// external bots: players controlled by another process through stdin/stdout
+pub struct BotPlugin;
+
+impl Plugin for BotPlugin {
+    fn build(&self, app: &mut App) {
+        app
+        .add_systems(
+            Update,
+            (update_bot_memory, forward_turn_actions).run_if(in_state(AppState::PlayerTurn)).before(bot_turn_controller)
+        )
+        .add_systems(
+            Update,
+            bot_turn_controller.run_if(in_state(AppState::PlayerTurn).and(not(is_peeking)))
+        )
+        .add_systems(Update, bot_peek.before(update_bot_memory).run_if(in_state(AppState::PlayerTurn).and(is_peeking)))
+        .add_systems(OnEnter(AppState::PlayerTurn), initialize_bot_memory.after(deal_initial_hands))
+        .add_systems(OnEnter(AppState::RoundEnd), notify_round_end);
+    }
+}
@@ -0,0 +1,97 @@
+use serde::{Deserialize, Serialize};
+
+/*
+EXTERNAL BOT PROTOCOL - one JSON object per line over the bot's stdin/stdout
+game -> bot:
+    {"type":"hello","protocol":1,"seat":1,"players":2}
+    {"type":"peek","count":2,"hand":[null,null,null,null],"graveyard_top":null,"deck_count":40}
+        before the first turn, bot replies one {"action":"peek","slot":0} per card it wants to look at
+    {"type":"turn","hand":[null,5,null,2],"graveyard_top":7,"deck_count":30}
+        bot replies {"action":"draw","from":"deck"} / {"action":"draw","from":"graveyard"} / {"action":"call"}
+    {"type":"drawn","value":7,"from_deck":true,"special":"swap","hand":[...],"graveyard_top":7,"deck_count":29}
+        bot replies {"action":"swap","slot":2} / {"action":"discard"}
+        or, if special is not null, {"action":"special","target_player":0,"target_slot":1,"own_slot":2}
+        ("reveal" needs own_slot, "spy" and "freeze" target_player and target_slot, "shuffle" target_player,
+        "swap" and "look_and_swap" all three, "skip" nothing; frozen cards can't be swapped)
+        (an effect on several opponent cards takes "target_cards":[[0,1],[2,3]], a seat and a slot per card)
+        (after a special the game sends "drawn" again, with "special":null)
+    {"type":"looked","own_value":9,"target_value":2}
+        after a look_and_swap, bot replies {"action":"decide","swap":true} / {"action":"decide","swap":false}
+    {"type":"opponent","player":0,"action":"discard","value":5,"from_deck":true}
+    {"type":"opponent","player":0,"action":"swap","drawn_value":null,"replaced_value":9}
+    {"type":"round_end"}
+hand slots are the player's cards in order, null when the bot doesn't know the value
+players and target_player are seat numbers
+*/
+
+pub const PROTOCOL_VERSION: u32 = 1;
+
+// what the bot can see of the table
+#[derive(Serialize, Debug)]
+pub struct BotView {
+    pub hand: Vec<Option<u8>>,
+    pub graveyard_top: Option<u8>,
+    pub deck_count: usize,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum GameToBot {
+    Hello { protocol: u32, seat: usize, players: usize },
+    Peek {
+        count: usize,
+        #[serde(flatten)]
+        view: BotView,
+    },
+    Turn {
+        #[serde(flatten)]
+        view: BotView,
+    },
+    Drawn {
+        value: u8,
+        from_deck: bool,
+        special: Option<&'static str>,
+        #[serde(flatten)]
+        view: BotView,
+    },
+    Looked { own_value: u8, target_value: u8 },
+    Opponent {
+        player: usize,
+        #[serde(flatten)]
+        action: ObservedAction,
+    },
+    RoundEnd,
+}
+
+// opponent actions forwarded to the bot
+#[derive(Serialize, Debug)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum ObservedAction {
+    Discard { value: u8, from_deck: bool },
+    Swap { drawn_value: Option<u8>, replaced_value: u8 },
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum DrawSource {
+    Deck,
+    Graveyard,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum BotReply {
+    Draw { from: DrawSource },
+    Call,
+    Swap { slot: usize },
+    Discard,
+    Special {
+        target_player: Option<usize>,
+        target_slot: Option<usize>,
+        own_slot: Option<usize>,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        target_cards: Vec<(usize, usize)>, // several: seat and slot of each opponent card
+    },
+    Peek { slot: usize },
+    Decide { swap: bool },
+}
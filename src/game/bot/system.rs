@@ -0,0 +1,422 @@
+use bevy::prelude::*;
+use crate::game::layout::TableLayout;
+use std::sync::mpsc::TryRecvError;
+use crate::game::bot::component::{BotPlayer, BotState};
+use crate::game::bot::protocol::{BotReply, BotView, DrawSource, GameToBot, ObservedAction};
+use crate::game::ai::component::{AIPlayer, AIMemory, AIState, AIDifficulty, AIThinkTime, AIThinkSettings};
+use crate::game::card::utils::{card_swap, discard_card, free_hand_cards};
+use crate::game::card::refactor_handles::{handle_graveyard_logic, handle_deck_logic};
+use crate::game::card::component::{Card, CardPosition, Selected};
+use crate::game::player::component::Player;
+use crate::game::hand::component::Hand;
+use crate::game::graveyard::component::Graveyard;
+use crate::game::deck::component::Deck;
+use crate::game::turn_player::component::Turn;
+use crate::game::turn_player::event::{TurnAction, TurnActionKind};
+use crate::game::special_cards::resource::{SpecialCardEffect, SpecialEffectRegistry};
+use crate::game::special_cards::handler::{Targeting, SpecialTargets};
+use crate::game::match_config::resource::GameSpeed;
+use crate::game::AppState;
+use crate::game::peek::resource::PeekPhase;
+use crate::game::rules::resource::Ruleset;
+use crate::game::round_end::resource::RoundCaller;
+use crate::ui::soundtrack::event::{PlayCardDraw, PlayCardPlace};
+
+// bots start every round knowing nothing, they learn their cards in the peek
+pub fn initialize_bot_memory(
+    mut bot_query: Query<(&mut BotPlayer, &mut BotState)>,
+) {
+    for (mut bot, mut state) in bot_query.iter_mut() {
+        bot.known_cards.clear();
+        *state = BotState::Idle;
+    }
+}
+
+// bots (and LAN players, on the host) are asked which cards to peek at, one reply per card
+pub fn bot_peek(
+    mut peek_phase: ResMut<PeekPhase>,
+    mut bot_query: Query<(&Player, &mut BotPlayer)>,
+    hand_query: Query<&Hand>,
+    mut card_query: Query<&mut Card>,
+    graveyard_query: Query<&Graveyard>,
+    deck_query: Query<&Deck>,
+    time: Res<Time>,
+) {
+    let count = peek_phase.count;
+    for peeking in peek_phase.players.iter_mut().filter(|peeking| !peeking.local && peeking.is_choosing()) {
+        let Ok((player, mut bot)) = bot_query.get_mut(peeking.player) else { continue; };
+        let Ok(hand) = hand_query.get(player.hand) else { continue; };
+
+        if !peeking.asked {
+            let view = BotView {
+                hand: vec![None; hand.cards.len()],
+                graveyard_top: graveyard_query.single().ok()
+                    .and_then(|graveyard| graveyard.cards.last())
+                    .and_then(|&card_entity| card_query.get(card_entity).ok())
+                    .map(|card| card.value),
+                deck_count: deck_query.single().map(|deck| deck.cards_values.len()).unwrap_or(0),
+            };
+            send(&mut bot, &GameToBot::Peek { count, view });
+            peeking.asked = true;
+        }
+
+        // a lost bot gets random cards when the time is over
+        while let Ok(line) = bot.link.try_recv() {
+            let Ok(BotReply::Peek { slot }) = serde_json::from_str::<BotReply>(&line) else {
+                warn!(target: "mygame", "Bot must peek, ignoring {:?}", line);
+                continue;
+            };
+            let Some(&card_entity) = hand.cards.get(slot) else { continue; };
+            if peeking.pick(card_entity) && let Ok(mut card) = card_query.get_mut(card_entity) {
+                card.reveal_to(peeking.player);
+            }
+            peeking.start_showing(count, hand.cards.len(), time.elapsed_secs());
+            if !peeking.is_choosing() { break; }
+        }
+    }
+}
+
+// remember own cards the bot can see (peeked, revealed by a 9)
+pub fn update_bot_memory(
+    mut bot_query: Query<(Entity, &Player, &mut BotPlayer)>,
+    hand_query: Query<&Hand>,
+    card_query: Query<&Card>,
+) {
+    for (bot_entity, player, mut bot) in bot_query.iter_mut() {
+        let Ok(hand) = hand_query.get(player.hand) else { continue; };
+        for &card_entity in &hand.cards {
+            if let Ok(card) = card_query.get(card_entity) && card.is_visible_to(bot_entity) {
+                bot.known_cards.insert(card_entity, card.value);
+            }
+        }
+    }
+}
+
+// send opponent actions to every bot
+pub fn forward_turn_actions(
+    mut bot_query: Query<(Entity, &mut BotPlayer)>,
+    mut action_messages: MessageReader<TurnAction>,
+    player_query: Query<&Player>,
+) {
+    for action in action_messages.read() {
+        let Ok(player) = player_query.get(action.player) else { continue; };
+
+        for (bot_entity, mut bot) in bot_query.iter_mut() {
+            if bot_entity == action.player { continue; }
+
+            let observed = match action.kind {
+                TurnActionKind::Discard { value, from_deck } => ObservedAction::Discard { value, from_deck },
+                TurnActionKind::Swap { drawn_value, replaced_value, .. } => ObservedAction::Swap { drawn_value, replaced_value },
+            };
+            if let Err(error) = bot.link.send(&GameToBot::Opponent { player: player.seat, action: observed }) {
+                warn!(target: "mygame", "Could not send action to bot: {}", error);
+            }
+        }
+    }
+}
+
+// tell every bot the round is over
+pub fn notify_round_end(
+    mut bot_query: Query<&mut BotPlayer>,
+) {
+    for mut bot in bot_query.iter_mut() {
+        let _ = bot.link.send(&GameToBot::RoundEnd);
+    }
+}
+
+// control bot turn
+pub fn bot_turn_controller(
+    mut commands: Commands,
+    turn_query: ResMut<Turn>,
+    mut bot_query: Query<(Entity, &Player, &mut BotPlayer, &mut BotState)>,
+    mut hand_query: Query<&mut Hand>,
+    mut graveyard_query: Query<&mut Graveyard>,
+    mut card_query: Query<(Entity, &mut Transform, &mut Card), With<Card>>,
+    deck_query: Query<&mut Deck>,
+    player_query: Query<(Entity, &Player)>,
+    layout: Res<TableLayout>,
+    (mut next_state, mut round_caller): (ResMut<NextState<AppState>>, ResMut<RoundCaller>),
+    (draw_message, place_message, action_message): (MessageWriter<PlayCardDraw>, MessageWriter<PlayCardPlace>, MessageWriter<TurnAction>),
+    selected_query: Query<Entity, With<Selected>>,
+    (special_effect, ruleset, registry): (Option<ResMut<SpecialCardEffect>>, Res<Ruleset>, Res<SpecialEffectRegistry>),
+    (game_speed, ai_think): (Res<GameSpeed>, Res<AIThinkSettings>),
+) {
+    // bots waiting their turn stay in Idle
+    for (bot_entity, _, _, mut state) in bot_query.iter_mut() {
+        if turn_query.current_player != bot_entity && !matches!(*state, BotState::Idle) {
+            *state = BotState::Idle;
+        }
+    }
+
+    // search the bot whose turn it is
+    let Ok((bot_entity, bot_player, mut bot, mut state)) = bot_query.get_mut(turn_query.current_player) else {
+        return; // it's not a bot turn
+    };
+
+    // do nothing while the game is paused
+    if !game_speed.is_running() { return; }
+
+    // obtain what the bot can see
+    let view = {
+        let Ok(hand) = hand_query.get(bot_player.hand) else { return; };
+        let graveyard_top = graveyard_query.single().ok()
+            .and_then(|graveyard| graveyard.cards.last().copied())
+            .and_then(|card_entity| card_query.get(card_entity).ok())
+            .map(|(_, _, card)| card.value);
+        let deck_count = deck_query.single().map(|deck| deck.cards_values.len()).unwrap_or(0);
+
+        BotView {
+            hand: hand.cards.iter().map(|card_entity| bot.known_cards.get(card_entity).copied()).collect(),
+            graveyard_top,
+            deck_count,
+        }
+    };
+
+    match *state {
+        BotState::Idle => {
+            send(&mut bot, &GameToBot::Turn { view });
+            *state = BotState::AwaitingDraw;
+        }
+
+        BotState::AwaitingDraw => {
+            let Some(reply) = read_reply(&mut commands, bot_entity, bot_player, &bot, (&ruleset, ai_think.for_seat(bot_player.seat))) else { return; };
+            // an unreadable line draws from the deck
+            let reply = reply.unwrap_or_else(|_| {
+                warn!(target: "mygame", "Unreadable bot reply, drawing from deck");
+                BotReply::Draw { from: DrawSource::Deck }
+            });
+
+            match reply {
+                BotReply::Call => {
+                    round_caller.0 = Some(bot_entity);
+                    next_state.set(AppState::RoundEnd);
+                    *state = BotState::Idle;
+                    info!(target: "mygame", "Bot decided to end the round!");
+                }
+                BotReply::Draw { from: DrawSource::Graveyard } if view.graveyard_top.is_some()
+                    && graveyard_query.single().is_ok_and(|graveyard| ruleset.can_take_from_graveyard(graveyard, bot_entity)) => {
+                    handle_graveyard_logic(&mut graveyard_query, turn_query.into_inner(), &mut card_query, bot_entity);
+                    *state = BotState::ExecutingDraw;
+                }
+                other => {
+                    // draw from deck, also the fallback for replies that don't fit the state
+                    if !matches!(other, BotReply::Draw { .. }) {
+                        warn!(target: "mygame", "Bot must draw or call, drawing from deck");
+                    }
+                    handle_deck_logic(deck_query, turn_query, card_query, draw_message, bot_entity);
+                    *state = BotState::ExecutingDraw;
+                }
+            }
+        }
+
+        BotState::ExecutingDraw => {
+            // search drawn card by the bot
+            let drawn_card = card_query.iter()
+                .find(|(_, _, card)| matches!(card.position, CardPosition::DrawnCard(player) if player == bot_entity));
+
+            if let Some((drawn_entity, _, card)) = drawn_card {
+                let special = ruleset.special_for(card.value).filter(|_| card.from_deck).map(|effect| effect.name());
+                send(&mut bot, &GameToBot::Drawn { value: card.value, from_deck: card.from_deck, special, view });
+                *state = BotState::AwaitingChoice { drawn_card: drawn_entity };
+            }
+        }
+
+        BotState::AwaitingChoice { drawn_card } => {
+            let Some(reply) = read_reply(&mut commands, bot_entity, bot_player, &bot, (&ruleset, ai_think.for_seat(bot_player.seat))) else { return; };
+            let Ok((_, _, drawn)) = card_query.get(drawn_card) else { return; };
+            let (drawn_value, drawn_from_deck, must_swap) = (drawn.value, drawn.from_deck, ruleset.must_swap(drawn));
+            // an unreadable line discards the card, a card taken from the graveyard is swapped instead
+            let reply = reply.unwrap_or_else(|_| {
+                match ruleset.special_for(drawn_value).filter(|_| drawn_from_deck) {
+                    Some(effect) => warn!(target: "mygame", "Unreadable bot reply, discarding the card without its {} special", effect.name()),
+                    None if must_swap => warn!(target: "mygame", "Unreadable bot reply, the card taken from the graveyard must be swapped"),
+                    None => warn!(target: "mygame", "Unreadable bot reply, discarding the card"),
+                }
+                BotReply::Discard
+            });
+
+            // special: map seats and slots to cards
+            if matches!(reply, BotReply::Special { .. }) {
+                let effect_type = ruleset.special_for(drawn_value).filter(|_| drawn_from_deck);
+                let targets = effect_type.and_then(|effect_type| special_targets_from_reply(
+                    &reply, registry.targeting(effect_type), registry.exchanges_cards(effect_type), bot_entity, &player_query, &hand_query, &card_query,
+                ));
+                if let (Some(effect_type), Some(targets)) = (effect_type, targets) {
+                    commands.insert_resource(SpecialCardEffect::targeted(drawn_card, effect_type, targets));
+                    *state = BotState::ResolvingSpecial { drawn_card };
+                    return;
+                }
+                warn!(target: "mygame", "Invalid special from bot, discarding the card");
+            }
+
+            // swap: the bot knows the card it puts in its hand, a frozen card stays where it is
+            let swap_target = match reply {
+                BotReply::Swap { slot } => hand_query.get(bot_player.hand).ok().and_then(|hand| hand.cards.get(slot).copied()),
+                _ => None,
+            };
+            let swap_target = swap_target.filter(|&target| {
+                let frozen = card_query.get(target).is_ok_and(|(_, _, card)| card.frozen);
+                if frozen {
+                    warn!(target: "mygame", "Bot chose a frozen card, discarding the card");
+                }
+                !frozen
+            });
+
+            // a card taken from the graveyard can't be discarded again, it replaces the first free card
+            let swap_target = swap_target.or_else(|| {
+                if !must_swap { return None; }
+                let free_card = hand_query.get(bot_player.hand).ok()
+                    .and_then(|hand| free_hand_cards(hand, &card_query).first().copied());
+                if free_card.is_some() {
+                    warn!(target: "mygame", "Bot must swap a card taken from the graveyard, swapping its first free card");
+                }
+                free_card
+            });
+
+            match swap_target {
+                Some(target) => {
+                    bot.known_cards.insert(drawn_card, drawn_value);
+                    bot.known_cards.remove(&target);
+                    card_swap(target, &mut card_query, &mut graveyard_query, turn_query, hand_query.reborrow(), &player_query, &layout, &mut commands, &selected_query, action_message);
+                }
+                None => {
+                    discard_card(drawn_card, &mut card_query, &mut graveyard_query, turn_query, &player_query, &mut commands, &selected_query, place_message, action_message);
+                }
+            }
+            *state = BotState::Idle;
+        }
+
+        BotState::ResolvingSpecial { drawn_card } => {
+            // a look and swap shows both values, the bot decides whether to swap
+            if let Some(effect) = special_effect.as_ref() && effect.awaiting_decision && effect.swap_decided.is_none()
+                && let (Some(own), Some(target)) = (effect.own_card, effect.target_card)
+                && let (Ok((_, _, own_card)), Ok((_, _, target_card))) = (card_query.get(own), card_query.get(target)) {
+                send(&mut bot, &GameToBot::Looked { own_value: own_card.value, target_value: target_card.value });
+                *state = BotState::DecidingLook { drawn_card };
+                return;
+            }
+
+            // wait until the effect systems clean the effect
+            if special_effect.is_some_and(|effect| effect.effect_type.is_some()) { return; }
+
+            // ask again what to do with the card, its effect is spent
+            let Ok((_, _, card)) = card_query.get(drawn_card) else { return; };
+            send(&mut bot, &GameToBot::Drawn { value: card.value, from_deck: card.from_deck, special: None, view });
+            *state = BotState::AwaitingChoice { drawn_card };
+        }
+
+        BotState::DecidingLook { drawn_card } => {
+            let Some(reply) = read_reply(&mut commands, bot_entity, bot_player, &bot, (&ruleset, ai_think.for_seat(bot_player.seat))) else { return; };
+            let Some(mut effect) = special_effect else { return; };
+
+            let swap = match reply {
+                Ok(BotReply::Decide { swap }) => swap,
+                Err(_) => {
+                    warn!(target: "mygame", "Unreadable bot reply, keeping the cards");
+                    false
+                }
+                Ok(_) => {
+                    warn!(target: "mygame", "Bot must decide the look and swap, keeping the cards");
+                    false
+                }
+            };
+
+            // the bot takes the opponent card it has just seen
+            if swap && let (Some(own), Some(target)) = (effect.own_card, effect.target_card) {
+                bot.known_cards.remove(&own);
+                if let Ok((_, _, card)) = card_query.get(target) {
+                    bot.known_cards.insert(target, card.value);
+                }
+            }
+            effect.swap_decided = Some(swap);
+            *state = BotState::ResolvingSpecial { drawn_card };
+        }
+    }
+}
+
+// auxiliar function - obtain the bot reply, if the bot is gone the built-in AI takes the seat
+// an unreadable line is an error, each state has its own fallback for it
+fn read_reply(
+    commands: &mut Commands,
+    bot_entity: Entity,
+    bot_player: &Player,
+    bot: &BotPlayer,
+    (ruleset, think_time): (&Ruleset, AIThinkTime),
+) -> Option<Result<BotReply, serde_json::Error>> {
+    match bot.link.try_recv() {
+        Ok(line) => match serde_json::from_str::<BotReply>(&line) {
+            // a peek that came too late
+            Ok(BotReply::Peek { .. }) => None,
+            Ok(reply) => Some(Ok(reply)),
+            Err(error) => {
+                warn!(target: "mygame", "Invalid bot reply {:?}: {}", line, error);
+                Some(Err(error))
+            }
+        },
+        Err(TryRecvError::Empty) => None,
+        Err(TryRecvError::Disconnected) => {
+            warn!(target: "mygame", "Bot disconnected, AI takes over {}", bot_player.name);
+            commands.entity(bot_entity)
+                .remove::<(BotPlayer, BotState)>()
+                .insert((
+                    AIPlayer { difficulty: AIDifficulty::Hard, think_time },
+                    AIMemory::for_rules(ruleset),
+                    AIState::Idle,
+                ));
+            None
+        }
+    }
+}
+
+// auxiliar function - send a message, logging errors
+fn send(bot: &mut BotPlayer, message: &GameToBot) {
+    if let Err(error) = bot.link.send(message) {
+        warn!(target: "mygame", "Could not send message to bot: {}", error);
+    }
+}
+
+// auxiliar function - map the targets requested by the bot to cards, as the effect needs them
+fn special_targets_from_reply(
+    reply: &BotReply,
+    targeting: Targeting,
+    exchanges_cards: bool,
+    bot_entity: Entity,
+    player_query: &Query<(Entity, &Player)>,
+    hand_query: &Query<&mut Hand>,
+    card_query: &Query<(Entity, &mut Transform, &mut Card), With<Card>>,
+) -> Option<SpecialTargets> {
+    let BotReply::Special { target_player, target_slot, own_slot, target_cards } = reply else { return None; };
+
+    // find target player by seat, never the bot itself
+    let opponent_at = |seat: usize| player_query.iter().find(|(entity, player)| player.seat == seat && *entity != bot_entity);
+    let target = target_player.and_then(opponent_at);
+    let (_, own_player) = player_query.get(bot_entity).ok()?;
+
+    // frozen cards can't take part in a swap
+    let card_at = |player: &Player, slot: Option<usize>| -> Option<Entity> {
+        hand_query.get(player.hand).ok()
+            .and_then(|hand| hand.cards.get(slot?).copied())
+            .filter(|&card_entity| !exchanges_cards || card_query.get(card_entity).is_ok_and(|(_, _, card)| !card.frozen))
+    };
+    let target_card = target.and_then(|(_, target_player)| card_at(target_player, *target_slot));
+    let own_card = card_at(own_player, *own_slot);
+
+    let mut targets = SpecialTargets { target_player: target.map(|(entity, _)| entity), ..Default::default() };
+    if targeting == Targeting::OpponentPlayer {
+        targets.target_player?;
+    }
+    if matches!(targeting, Targeting::OpponentCard | Targeting::OpponentAndOwnCard) {
+        targets.target_card = Some(target_card?);
+    }
+    if targeting.needs_own_card() {
+        targets.own_card = Some(own_card?);
+    }
+    if let Targeting::Several(count) = targeting {
+        for &(seat, slot) in target_cards {
+            let (_, target_player) = opponent_at(seat)?;
+            targets.target_cards.push(card_at(target_player, Some(slot))?);
+        }
+        if targets.target_cards.len() != count as usize { return None; }
+    }
+    Some(targets)
+}
@@ -1,21 +1,65 @@
-use bevy::{ecs::entity::Entity, prelude::{Component, Resource, Handle, Image, Vec3}};
+use bevy::{ecs::entity::Entity, prelude::{Component, Resource, Handle, Image, Vec3}, time::{Timer, TimerMode}};
+use std::collections::HashSet;
 
 // COMPONENTS
 #[derive(Component)]
 pub struct Card {
     pub value: u8,
-    pub face_up: bool,
+    pub face_up: bool, // public, every player can see the card
+    pub known_by: KnownBy, // private, only these players can see the card
     pub owner_id: Option<Entity>,
     pub position: CardPosition,
     pub suit: Suit,
     pub front_face: Handle<Image>,
     pub from_deck: bool,
     pub is_being_dealt: bool,
+    pub frozen: bool, // locked by a freeze, it can't be swapped until the end of the round
 }
 
+impl Card {
+    // private reveal, only this player sees the card
+    pub fn reveal_to(&mut self, player: Entity) {
+        self.known_by.0.insert(player);
+    }
+
+    // the player no longer sees the face-down card, the others keep what they know
+    pub fn forget(&mut self, player: Entity) {
+        self.known_by.0.remove(&player);
+    }
+
+    // turn the card face down for everyone
+    pub fn hide(&mut self) {
+        self.face_up = false;
+        self.known_by.0.clear();
+    }
+
+    pub fn is_visible_to(&self, player: Entity) -> bool {
+        self.face_up || self.known_by.0.contains(&player)
+    }
+}
+
+// players that can see a face-down card
+#[derive(Default, Clone, Debug)]
+pub struct KnownBy(pub HashSet<Entity>);
+
 #[derive(Component)]
 pub struct Selected;
 
+// classic memory: a card shown to a player is turned face-down again when the timer ends
+#[derive(Component)]
+pub struct TemporaryReveal {
+    pub player: Entity,
+    pub timer: Timer,
+}
+
+impl TemporaryReveal {
+    pub const SECONDS: f32 = 3.0;
+
+    pub fn new(player: Entity) -> Self {
+        Self { player, timer: Timer::from_seconds(Self::SECONDS, TimerMode::Once) }
+    }
+}
+
 #[derive(Component)]
 pub struct PreviousCardPosition(pub CardPosition);
 
@@ -40,6 +84,38 @@ pub enum Suit {
     Cup,
     Gold,
     Sword,
+    Joker, // value 0, only in the decks with jokers
+}
+
+impl Suit {
+    // index in the texture order (CardHandles)
+    pub fn index(&self) -> usize {
+        match self {
+            Suit::Coarse => 0,
+            Suit::Cup => 1,
+            Suit::Gold => 2,
+            Suit::Sword => 3,
+            Suit::Joker => 4,
+        }
+    }
+
+    // index of a card face in CardHandles, the joker goes after the 48 suited faces
+    pub fn face_index(&self, value: u8) -> usize {
+        match self {
+            Suit::Joker => 48,
+            suit => suit.index() * 12 + (value as usize).saturating_sub(1),
+        }
+    }
+
+    pub fn from_index(index: usize) -> Self {
+        match index {
+            0 => Suit::Coarse,
+            1 => Suit::Cup,
+            2 => Suit::Gold,
+            4 => Suit::Joker,
+            _ => Suit::Sword,
+        }
+    }
 }
 
 // RESOURCES
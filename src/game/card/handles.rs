@@ -1,8 +1,10 @@
 use bevy::prelude::*;
-use bevy::window::PrimaryWindow;
 use crate::game::card::component::{Card, CardPosition, Selected, DoubleClick};
 use crate::game::{graveyard::component::Graveyard, turn_player::component::Turn, deck::component::Deck, hand::component::Hand, player::component::Player};
-use crate::game::card::utils::{card_swap, discard_card};
+use crate::game::card::utils::{card_swap, discard_card, free_hand_cards};
+use crate::game::rules::resource::Ruleset;
+use crate::game::layout::TableLayout;
+use crate::game::turn_player::event::TurnAction;
 use crate::ui::soundtrack::event::{PlayCardDraw, PlayCardPlace};
 
 // HANDLE CLICK SYSTEMS
@@ -17,8 +19,10 @@ pub fn handle_card_click(
     graveyard_query: &mut Query<&mut Graveyard>,
     player_query: &Query<(Entity, &Player)>,
     hand_query: Query<&mut Hand>,
-    windows: Query<&Window, With<PrimaryWindow>>,
+    layout: &TableLayout,
     place_message: MessageWriter<PlayCardPlace>,
+    action_message: MessageWriter<TurnAction>,
+    ruleset: &Ruleset,
 ) {
     // verify: if it is direct discard
     let card_comp = card_query.iter()
@@ -27,7 +31,15 @@ pub fn handle_card_click(
 
     if let Some(card_comp) = card_comp {
         if matches!(card_comp.position, CardPosition::DrawnCard(player_id) if player_id == turn_query.current_player) {
-            discard_card(clicked_entity, card_query, graveyard_query, turn_query, player_query, commands, selected_query, place_message);
+            // a card taken from the graveyard goes into the hand, unless every card of the hand is frozen
+            let must_swap = ruleset.must_swap(card_comp) && player_query.get(turn_query.current_player).ok()
+                .and_then(|(_, player)| hand_query.get(player.hand).ok())
+                .is_some_and(|hand| !free_hand_cards(hand, card_query).is_empty());
+            if must_swap {
+                info!(target: "mygame", "A card taken from the graveyard must be swapped into your hand");
+                return;
+            }
+            discard_card(clicked_entity, card_query, graveyard_query, turn_query, player_query, commands, selected_query, place_message, action_message);
             return;
         }
     }
@@ -51,7 +63,7 @@ pub fn handle_card_click(
             commands.entity(selected_entity).remove::<Selected>();
         }
 
-        card_swap(clicked_entity, card_query, graveyard_query, turn_query, hand_query, player_query, windows, commands, selected_query);
+        card_swap(clicked_entity, card_query, graveyard_query, turn_query, hand_query, player_query, layout, commands, selected_query, action_message);
         double_click.last_card = None; // reset double click
         return;
     } else {
@@ -108,7 +120,7 @@ pub fn handle_deck_click(
         card.is_being_dealt = true;
         card.position = CardPosition::DrawnCard(turn_query.current_player);
         card.owner_id = Some(turn_query.current_player);
-        card.face_up = true; // show card taken
+        card.reveal_to(turn_query.current_player); // only the player sees a deck card
         card.from_deck = true; // card taken from deck
         
         turn_query.has_drawn_card = true; // player already drew a card
@@ -122,6 +134,7 @@ pub fn handle_graveyard_click(
     mut graveyard_query: Query<&mut Graveyard>,
     mut turn_query: ResMut<Turn>,
     card_query: &mut Query<(Entity, &mut Transform, &mut Card), With<Card>>,
+    ruleset: &Ruleset,
 ) {
     // verify if player already drew a card
     if turn_query.has_drawn_card {
@@ -151,6 +164,12 @@ pub fn handle_graveyard_click(
         warn!(target: "mygame", "Graveyard is empty");
         return;
     }
+
+    // verify: the player can't take back the card they just discarded
+    if !ruleset.can_take_from_graveyard(&graveyard, turn_query.current_player) {
+        info!(target: "mygame", "You can't take back the card you just discarded");
+        return;
+    }
     
     // take last card of the graveyard
     let drawn_card_entity = match graveyard.cards.pop() {
@@ -160,6 +179,7 @@ pub fn handle_graveyard_click(
             return;
         }
     };
+    graveyard.discarded_by = None; // the card below was put there earlier
 
     if let Ok((_, _, mut card)) = card_query.get_mut(drawn_card_entity) {
         card.is_being_dealt = true;
@@ -16,6 +16,7 @@ pub fn handle_graveyard_logic(
     if graveyard.cards.is_empty() { return; }
     
     let drawn_card_entity = graveyard.cards.pop().unwrap();
+    graveyard.discarded_by = None; // the card below was put there earlier
     
     if let Ok((_, _, mut card)) = card_query.get_mut(drawn_card_entity) {
         card.position = CardPosition::DrawnCard(ai_entity);
@@ -44,7 +45,7 @@ pub fn handle_deck_logic(
         card.is_being_dealt = true;
         card.position = CardPosition::DrawnCard(ai_entity);
         card.owner_id = Some(ai_entity);
-        card.face_up = true;
+        card.reveal_to(ai_entity);
         card.from_deck = true;
         turn_query.has_drawn_card = true;
         draw_message.write(PlayCardDraw);
@@ -1,19 +1,23 @@
 use bevy::prelude::*;
 use bevy::window::PrimaryWindow;
-use crate::game::card::component::{Card, CardPosition, CardHandles, CardBack, Suit, Selected, DoubleClick};
-use crate::game::special_cards::resource::SpecialEffect;
-use crate::game::{graveyard::component::Graveyard, turn_player::component::Turn, deck::component::Deck, hand::component::Hand, player::component::Player, special_cards::resource::SpecialCardEffect};
+use crate::game::card::component::{Card, CardPosition, CardHandles, CardBack, Selected, DoubleClick, TemporaryReveal};
+use crate::game::{graveyard::component::Graveyard, turn_player::component::Turn, deck::component::Deck, hand::component::Hand, player::component::Player, special_cards::resource::{SpecialCardEffect, SpecialEffectRegistry}};
+use crate::game::special_cards::handler::Targeting;
 use crate::game::card::handles::{handle_deck_click, handle_card_click, handle_graveyard_click};
+use crate::game::hand::system::get_player_positions;
+use crate::game::match_config::resource::{MatchConfig, Viewer, GameSpeed};
+use crate::game::layout::TableLayout;
+use crate::game::rules::resource::Ruleset;
 use crate::ui::card_animation::component::{AnimationType, CardAnimation};
+use crate::game::turn_player::event::TurnAction;
 use crate::ui::soundtrack::event::{PlayCardDraw, PlayCardPlace};
 use bevy::asset::Assets;
 use bevy::image::{Image, ImageSampler};
-use rand::seq::SliceRandom;
-use rand::rng;
 
-// spawn card values after setup and allocate suit/value and textures
-// iterate and mix all cards front/back
-pub fn setup_cards(mut commands: Commands, asset_server: Res<AssetServer>) {
+// load the textures of every card face, in the order of Suit::face_index
+// the deck of the rules is built from them in spawn_cards
+// without an asset server (headless server) the cards keep empty handles
+pub fn setup_cards(mut commands: Commands, asset_server: Option<Res<AssetServer>>) {
     let suits = ["coarse", "cup", "gold", "sword"];
     let mut card_routes = Vec::new();
 
@@ -23,32 +27,27 @@ pub fn setup_cards(mut commands: Commands, asset_server: Res<AssetServer>) {
             card_routes.push(route);
         }
     }
+    card_routes.push(String::from("textures/deck/joker/0.PNG"));
 
     // load textures
     let card_handles: Vec<Handle<Image>> = card_routes
         .into_iter()
-        .map(|route| asset_server.load(route))
+        .map(|route| asset_server.as_ref().map(|server| server.load(route)).unwrap_or_default())
         .collect();
 
     // card back texture
-    let card_back_handle = asset_server.load("textures/deck/back/back.PNG");
+    let card_back_handle = asset_server.as_ref().map(|server| server.load("textures/deck/back/back.PNG")).unwrap_or_default();
 
     // insert handles resources
     commands.insert_resource(CardHandles(card_handles.clone()));
     commands.insert_resource(CardBack(card_back_handle));
-
-    let suits_logic = [Suit::Coarse, Suit::Cup, Suit::Gold, Suit::Sword];
-    let mut cards_data: Vec<(Suit, u8)> = suits_logic
-        .iter()
-        .flat_map(|suit| (1..=12).map(move |value| (suit.clone(), value)))
-        .collect();
-
-    cards_data.shuffle(&mut rng());
 }
 
 
 pub fn card_face(
     card_back: Option<Res<CardBack>>,
+    match_config: Res<MatchConfig>,
+    viewer: Res<Viewer>,
     mut query: Query<(&Card, &mut Sprite, Option<&CardAnimation>)>
 ) {
     let Some(card_back) = card_back else { return; };
@@ -68,7 +67,10 @@ pub fn card_face(
             }
         }
         
-        if card.face_up {
+        // spectators can choose to see every hand
+        let open_hand = match_config.show_hands && matches!(card.position, CardPosition::Hand(_) | CardPosition::DrawnCard(_));
+
+        if viewer.can_see(card) || open_hand {
             // asign front
             sprite.image = card.front_face.clone();
         } else {
@@ -94,7 +96,7 @@ pub fn card_face(
 pub fn card_selection(
     mut commands: Commands,
     mouse_input: Res<ButtonInput<MouseButton>>,
-    windows: Query<&Window, With<PrimaryWindow>>,
+    (windows, layout): (Query<&Window, With<PrimaryWindow>>, Res<TableLayout>),
     camera_query: Query<(&Camera, &GlobalTransform)>,
     mut card_query: Query<(Entity, &mut Transform, &mut Card), With<Card>>,
     selected_query: Query<Entity, With<Selected>>,
@@ -105,14 +107,17 @@ pub fn card_selection(
     mut graveyard_query: Query<&mut Graveyard>,
     deck_query: Query<&mut Deck>,
     player_query: Query<(Entity, &Player)>,
-    special_effect: Option<ResMut<SpecialCardEffect>>,
-    draw_message: MessageWriter<PlayCardDraw>,
-    place_message: MessageWriter<PlayCardPlace>,
+    (special_effect, registry, ruleset): (Option<ResMut<SpecialCardEffect>>, Res<SpecialEffectRegistry>, Res<Ruleset>),
+    viewer: Res<Viewer>,
+    (draw_message, place_message, action_message): (MessageWriter<PlayCardDraw>, MessageWriter<PlayCardPlace>, MessageWriter<TurnAction>),
 ) {
     if !mouse_input.just_pressed(MouseButton::Left) {
         return;
     }
 
+    // only the player looking at the screen can act, and only on their turn
+    if viewer.0 != Some(turn_query.current_player) { return; }
+
     // obtain window, camera, cursor/world position
     let Ok(window) = windows.single() else { return; };
     let Ok((camera, camera_transform)) = camera_query.single() else { return; };
@@ -121,28 +126,44 @@ pub fn card_selection(
 
     // verify if it has special effect, run if awaiting_target = true, also awating_own_card = true
     if let Some(mut effect) = special_effect {
-        if effect.awaiting_target {
+        // clicking the drawn card while choosing targets discards it without using the special
+        let drawn_clicked = detect_card_click(&card_query, world_pos)
+            .and_then(|clicked_entity| card_query.get(clicked_entity).ok())
+            .is_some_and(|(_, _, card)| card.position == CardPosition::DrawnCard(turn_query.current_player));
+        if drawn_clicked && (effect.awaiting_target || effect.awaiting_own_card) {
+            info!(target: "mygame", "Special not used, discarding the card");
+            *effect = SpecialCardEffect::default();
+        } else if effect.awaiting_target {
             if let Some(clicked_entity) = detect_card_click(&card_query, world_pos) {
                 // verify if clicked card belongs to the rival
                 if let Ok((_card_entity, _card_tr, card)) = card_query.get(clicked_entity) {
                     if let CardPosition::Hand(hand_owner) = card.position {
-                        if hand_owner != turn_query.current_player {
+                        if hand_owner == turn_query.current_player {
+                            info!(target: "mygame", "Cannot target your own cards");
+                        } else if card.frozen && effect.effect_type.is_some_and(|e| registry.exchanges_cards(e)) {
+                            info!(target: "mygame", "This card is frozen, choose another one");
+                        } else if let Some(Targeting::Several(count)) = effect.effect_type.map(|e| registry.targeting(e)) {
+                            // several cards are chosen one click at a time
+                            if effect.target_cards.contains(&clicked_entity) {
+                                info!(target: "mygame", "This card is already chosen");
+                            } else {
+                                effect.target_cards.push(clicked_entity);
+                                effect.awaiting_target = effect.target_cards.len() < count as usize;
+                                info!(target: "mygame", "Target card {} of {} selected for special effect", effect.target_cards.len(), count);
+                            }
+                        } else {
                             // save card selected
                             effect.target_card = Some(clicked_entity);
+                            effect.target_player = Some(hand_owner);
+                            effect.awaiting_target = false;
 
-                            // verify effect (Swap or Shuffle)
-                            if matches!(effect.effect_type, Some(SpecialEffect::Swap)) {
-                                effect.awaiting_target = false;
+                            // swap effects go on with one of your cards
+                            if effect.effect_type.is_some_and(|e| registry.targeting(e).needs_own_card()) {
                                 effect.awaiting_own_card = true;
                                 info!(target: "mygame", "Now select one of your cards to swap");
-                            } else {
-                                effect.target_player = Some(hand_owner);
-                                effect.awaiting_target = false;
                             }
 
                             info!(target: "mygame", "Target player selected for special effect");
-                        } else {
-                            info!(target: "mygame", "Cannot target your own cards");
                         }
                     }
                 }
@@ -154,11 +175,13 @@ pub fn card_selection(
                 // verify if clicked card is yours
                 if let Ok((_card_entity, _card_tr, card)) = card_query.get(clicked_entity) {
                     if let CardPosition::Hand(owner) = card.position {
-                        if owner == turn_query.current_player {
+                        if owner != turn_query.current_player {
+                            info!(target: "mygame", "Select one of your cards");
+                        } else if card.frozen && effect.effect_type.is_some_and(|e| registry.exchanges_cards(e)) {
+                            info!(target: "mygame", "This card is frozen, choose another one");
+                        } else {
                             effect.own_card = Some(clicked_entity);
                             effect.awaiting_own_card = false;
-                        } else {
-                            info!(target: "mygame", "Select one of your cards");
                         }
                     }
                 }
@@ -169,14 +192,14 @@ pub fn card_selection(
     }
 
     // detect click in deck
-    if detect_deck_click(world_pos, window) {
+    if detect_deck_click(world_pos, &layout) {
         handle_deck_click(deck_query, turn_query, &mut card_query, draw_message);
         return;
     }
 
     // detect click in graveyard 
-    if detect_graveyard_click(world_pos, window) {
-        handle_graveyard_click(graveyard_query, turn_query, &mut card_query);
+    if detect_graveyard_click(world_pos, &layout) {
+        handle_graveyard_click(graveyard_query, turn_query, &mut card_query, &ruleset);
         return;
     }
 
@@ -184,13 +207,13 @@ pub fn card_selection(
     if let Some(clicked_entity) = detect_card_click(&card_query, world_pos) {
         handle_card_click(
             clicked_entity, &mut commands, &selected_query, &mut double_click,
-            &time, turn_query, &mut card_query, &mut graveyard_query, &player_query, hand_query, windows, place_message
+            &time, turn_query, &mut card_query, &mut graveyard_query, &player_query, hand_query, &layout, place_message, action_message, &ruleset
         );
         return;
     }
 
     // if is not detected any entity
-    if detect_card_click(&card_query, world_pos).is_none() && !detect_deck_click(world_pos, window) && !detect_graveyard_click(world_pos, window) {
+    if detect_card_click(&card_query, world_pos).is_none() && !detect_deck_click(world_pos, &layout) && !detect_graveyard_click(world_pos, &layout) {
         // deselect all cards
         for selected_entity in selected_query.iter() {
             commands.entity(selected_entity).remove::<Selected>();
@@ -198,33 +221,43 @@ pub fn card_selection(
     }
 }
 
+// turn face-down again the cards that were only shown for a while
+pub fn expire_temporary_reveals(
+    mut commands: Commands,
+    mut card_query: Query<(Entity, &mut Card, &mut TemporaryReveal)>,
+    game_speed: Res<GameSpeed>,
+    time: Res<Time>,
+) {
+    let delta = std::time::Duration::from_secs_f32(game_speed.delta_secs(&time));
+    for (entity, mut card, mut reveal) in card_query.iter_mut() {
+        if !reveal.timer.tick(delta).is_finished() { continue; }
+        card.forget(reveal.player);
+        commands.entity(entity).remove::<TemporaryReveal>();
+    }
+}
+
 // feedback visual when selecting
 pub fn card_visual(
     mut card_query: Query<(&mut Transform, Option<&Selected>, &Card), With<Card>>,
     turn_query: Res<Turn>,
-    windows: Query<&Window, With<PrimaryWindow>>,
+    player_query: Query<&Player>,
+    layout: Res<TableLayout>,
 ) {
-    let Ok(window) = windows.single() else { return; };
 
     for (mut transform, selected, card) in card_query.iter_mut() {
         if let CardPosition::Hand(owner) = card.position {
             if owner == turn_query.current_player {
-                let is_sec_player = transform.translation.y > 0.0;
+                // base row of the player's seat
+                let Ok(player) = player_query.get(owner) else { continue; };
+                let base_y = get_player_positions(player.seat, Hand::SIZE, layout.width, layout.height)[0].y;
                 
                 if selected.is_some() {
-                    if is_sec_player {
-                        transform.translation.y = window.height() * 0.13; // if is second player
-                    } else {
-                        transform.translation.y = window.height() * -0.13; // if is local player
-                    }
+                    // raise the card towards the center of the table
+                    let lift = layout.height * 0.02;
+                    transform.translation.y = if base_y > 0.0 { base_y - lift } else { base_y + lift };
                     transform.translation.z = 50.0;
                 } else {
                     // return default position
-                    let base_y = if is_sec_player {
-                        window.height() * 0.15
-                    } else {
-                        window.height() * -0.15
-                    };
                     transform.translation.y = base_y;
                     transform.translation.z = 10.0;
                 }
@@ -241,10 +274,11 @@ pub fn card_visual(
 
 // sets the front and back sprite cards
 pub fn configure_texture(
-    mut images: ResMut<Assets<Image>>,
+    images: Option<ResMut<Assets<Image>>>,
     card_handles: Option<Res<CardHandles>>,
     card_back: Option<Res<CardBack>>,
 ) {
+    let Some(mut images) = images else { return; };
     let Some(card_handles) = card_handles else { return; };
     let Some(card_back) = card_back else { return; };
     
@@ -262,7 +296,7 @@ pub fn configure_texture(
 }
 
 // DETECTION CLICK SYSTEMS
-fn detect_card_click(
+pub fn detect_card_click(
     card_query: &Query<(Entity, &mut Transform, &mut Card), With<Card>>,
     world_pos: Vec2
 ) -> Option<Entity> {
@@ -281,9 +315,9 @@ fn detect_card_click(
     None
 }
 
-fn detect_deck_click(world_pos: Vec2, window: &Window) -> bool {
-    let deck_x = window.width() * 0.15;
-    let deck_y = window.height() * 0.0;
+pub fn detect_deck_click(world_pos: Vec2, layout: &TableLayout) -> bool {
+    let deck_x = layout.width * 0.15;
+    let deck_y = layout.height * 0.0;
     let deck_pos = Vec3::new(deck_x, deck_y, 0.0);
     let deck_size = Vec2::new(80.0, 120.0);
     
@@ -293,9 +327,9 @@ fn detect_deck_click(world_pos: Vec2, window: &Window) -> bool {
     && world_pos.y <= deck_pos.y + deck_size.y / 2.0
 }
 
-fn detect_graveyard_click(world_pos: Vec2, window: &Window) -> bool {
-    let graveyard_x = window.width() * -0.06;
-    let graveyard_y = window.height() * 0.0;
+pub fn detect_graveyard_click(world_pos: Vec2, layout: &TableLayout) -> bool {
+    let graveyard_x = layout.width * -0.06;
+    let graveyard_y = layout.height * 0.0;
     let graveyard_pos = Vec3::new(graveyard_x, graveyard_y, 0.0);
     let graveyard_size = Vec2::new(80.0, 120.0);
     
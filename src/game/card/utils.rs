@@ -1,10 +1,22 @@
 use bevy::prelude::*;
-use bevy::window::PrimaryWindow;
 use crate::game::card::component::{Card, CardPosition, Selected};
 use crate::game::{graveyard::component::Graveyard, turn_player::component::Turn, hand::component::Hand, player::component::Player};
+use crate::game::turn_player::event::{TurnAction, TurnActionKind};
+use crate::game::hand::system::get_player_positions;
+use crate::game::layout::TableLayout;
 use crate::ui::soundtrack::event::PlayCardPlace;
 
 // AUXILIAR SYSTEMS
+// cards of a hand that can be swapped, a frozen card stays where it is
+pub fn free_hand_cards(
+    hand: &Hand,
+    card_query: &Query<(Entity, &mut Transform, &mut Card), With<Card>>,
+) -> Vec<Entity> {
+    hand.cards.iter().copied()
+        .filter(|&card_entity| card_query.get(card_entity).is_ok_and(|(_, _, card)| !card.frozen))
+        .collect()
+}
+
 pub fn discard_card(
     clicked_entity: Entity,
     card_query: &mut Query<(Entity, &mut Transform, &mut Card), With<Card>>,
@@ -14,6 +26,7 @@ pub fn discard_card(
     commands: &mut Commands,
     selected_query: &Query<Entity, With<Selected>>,
     mut place_message: MessageWriter<PlayCardPlace>,
+    mut action_message: MessageWriter<TurnAction>,
 ) {
     if let Ok((_, _transform, card)) = card_query.get_mut(clicked_entity) {
         if matches!(card.position, CardPosition::DrawnCard(player_id) if player_id == turn_query.current_player) {
@@ -25,11 +38,18 @@ pub fn discard_card(
                 // update graveyard
                 if let Ok(mut graveyard) = graveyard_query.single_mut() {
                     graveyard.cards.push(clicked_entity);
+                    graveyard.discarded_by = Some(turn_query.current_player);
                     
                     place_message.write(PlayCardPlace);
                     info!(target: "mygame", "Card discarded directly to graveyard: {:?}", clicked_entity);
                 }
 
+                // notify observers (AI memory)
+                action_message.write(TurnAction {
+                    player: turn_query.current_player,
+                    kind: TurnActionKind::Discard { value: card.value, from_deck: card.from_deck },
+                });
+
                 // remove card selection
                 for selected_entity in selected_query.iter() {
                     commands.entity(selected_entity).remove::<Selected>();
@@ -49,9 +69,10 @@ pub fn card_swap(
     turn_query: ResMut<Turn>,
     mut hand_query: Query<&mut Hand>,
     player_query: &Query<(Entity, &Player)>,
-    windows: Query<&Window, With<PrimaryWindow>>,
+    layout: &TableLayout,
     commands: &mut Commands,
     selected_query: &Query<Entity, With<Selected>>,
+    mut action_message: MessageWriter<TurnAction>,
 ) {
     let clicked_card = card_query.iter()
         .find(|(entity, _, _)| *entity == clicked_entity)
@@ -63,6 +84,12 @@ pub fn card_swap(
             info!(target: "mygame", "Card is not in current player's hand");
             return;
         }
+
+        // verify: a frozen card stays in the hand
+        if clicked_card.frozen {
+            info!(target: "mygame", "Card is frozen, it can't be swapped this round");
+            return;
+        }
     
         // search the drawn card
         let drawn_card = card_query.iter()
@@ -82,23 +109,19 @@ pub fn card_swap(
             
             let clicked_pos = clicked_transform.translation; // obtain position
             
-            let Ok(window) = windows.single() else { return; };
-            let is_sec_player = clicked_pos.y > 0.0;
-            let base_y = if is_sec_player {
-                window.height() * 0.15
-            } else {
-                window.height() * -0.15
-            };
+            // base row of the current player's seat
+            let seat = player_query.get(turn_query.current_player).map(|(_, player)| player.seat).unwrap_or(0);
+            let base_y = get_player_positions(seat, Hand::SIZE, layout.width, layout.height)[0].y;
 
             drawn_card.position = CardPosition::Hand(turn_query.current_player);
-            drawn_card.face_up = false;
+            drawn_card.hide();
             drawn_transform.translation = Vec3::new(clicked_pos.x, base_y, 10.0);
             clicked_card.position = CardPosition::Graveyard; // card of hand selected to graveyard
             clicked_card.face_up = true; // card front
             
-            let Ok(window) = windows.single() else { return; };
             if let Ok(mut graveyard) = graveyard_query.single_mut() {
                 graveyard.cards.push(clicked_entity); // update changes
+                graveyard.discarded_by = Some(turn_query.current_player);
                 
                 let stack_index = (graveyard.cards.len() - 1) as f32;
                 let max_stack = 8.0;
@@ -114,20 +137,31 @@ pub fn card_swap(
                 clicked_transform.rotation = Quat::from_rotation_z(random_rotation);
                 
                 clicked_transform.translation = Vec3::new(
-                    window.width() * -0.06 + offset_x,
-                    window.height() * 0.0 + offset_y,
+                    layout.width * -0.06 + offset_x,
+                    layout.height * 0.0 + offset_y,
                     10.0 + stack_index
                 );
                 
                 if let Some((_, player)) = player_query.iter().find(|(entity, _)| *entity == turn_query.current_player) {
                     if let Ok(mut hand) = hand_query.get_mut(player.hand) {
-                        // remove hand
-                        hand.cards.retain(|&card_entity| card_entity != clicked_entity);
-                        // add new card in hand
-                        hand.cards.push(drawn_card_entity);
+                        // new card takes the slot of the replaced card, so hand order follows the table
+                        for card_entity in hand.cards.iter_mut().filter(|card_entity| **card_entity == clicked_entity) {
+                            *card_entity = drawn_card_entity;
+                        }
                     }
                 }
                 info!(target: "mygame", "Card swap completed: {:?} -> Hand, {:?} -> Graveyard", drawn_card_entity, clicked_entity);
+
+                // notify observers (AI memory), the drawn value is only public if it came from the graveyard
+                action_message.write(TurnAction {
+                    player: turn_query.current_player,
+                    kind: TurnActionKind::Swap {
+                        drawn_card: drawn_card_entity,
+                        drawn_value: (!drawn_card.from_deck).then_some(drawn_card.value),
+                        replaced_card: clicked_entity,
+                        replaced_value: clicked_card.value,
+                    },
+                });
                 
                 // remove card selection
                 for selected_entity in selected_query.iter() {
@@ -146,15 +180,24 @@ fn change_turn(
     mut turn: ResMut<Turn>,
     players: &Query<(Entity, &Player)>,
 ) {
-    // obtain player list
-    let players: Vec<Entity> = players.iter()
+    // obtain player list, in seat order
+    let mut players: Vec<(Entity, &Player)> = players.iter().collect();
+    players.sort_by_key(|(_, player)| player.seat);
+    let players: Vec<Entity> = players.into_iter()
         .map(|(entity,_)| entity)
         .collect();
 
     if let Some(pos) = players.iter().position(|&p| p == turn.current_player) {
-        let next_index = (pos + 1) % players.len();
+        // a skip jumps over the next player
+        let step = if turn.skip_next { 2 } else { 1 };
+        if turn.skip_next {
+            info!(target: "mygame", "Player {:?} loses their turn", players[(pos + 1) % players.len()]);
+        }
+        let next_index = (pos + step) % players.len();
         turn.current_player = players[next_index];
         turn.has_drawn_card = false;
+        turn.skip_next = false;
+        turn.number += 1;
 
         info!(target: "mygame", "Turn automatically changed to player: {:?}", turn.current_player);
     }
@@ -0,0 +1,65 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+// preset emotes of the quick chat wheel
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Emote {
+    Nice,
+    Ouch,
+    HurryUp,
+    WellPlayed,
+}
+
+impl Emote {
+    pub const ALL: [Emote; 4] = [Emote::Nice, Emote::Ouch, Emote::HurryUp, Emote::WellPlayed];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Emote::Nice => "Nice!",
+            Emote::Ouch => "Ouch",
+            Emote::HurryUp => "Hurry up",
+            Emote::WellPlayed => "Well played",
+        }
+    }
+
+    // what an AI answers to this emote
+    pub fn reply(&self) -> Emote {
+        match self {
+            Emote::Nice => Emote::WellPlayed,
+            Emote::Ouch => Emote::Nice,
+            Emote::HurryUp => Emote::Ouch,
+            Emote::WellPlayed => Emote::Nice,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ChatContent {
+    Emote(Emote),
+    Text(String), // free text, cut to MAX_TEXT_LENGTH characters
+}
+
+impl ChatContent {
+    pub fn label(&self) -> &str {
+        match self {
+            ChatContent::Emote(emote) => emote.label(),
+            ChatContent::Text(text) => text,
+        }
+    }
+}
+
+// a seat asks to say something, it is shown only if the rate limit allows it
+#[derive(Event, Message, Clone, Debug)]
+pub struct SayChat {
+    pub seat: usize,
+    pub content: ChatContent,
+}
+
+// chat accepted at the table, by seat so any link (local, AI, LAN) can carry it
+#[derive(Event, Message, Serialize, Deserialize, Clone, Debug)]
+pub struct ChatMessage {
+    pub seat: usize,
+    pub content: ChatContent,
+}
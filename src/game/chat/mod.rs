@@ -0,0 +1,28 @@
+use bevy::prelude::*;
+use crate::game::gamestate::AppState;
+use crate::game::round_end::system::calculate_scores;
+
+pub mod event;
+pub mod resource;
+mod system;
+
+use event::{SayChat, ChatMessage};
+use resource::{ChatLimiter, AIChatReplies};
+use system::{limit_chat, ai_answer_chat, ai_react_to_round_end, say_ai_replies, clear_chat};
+
+// quick chat and emotes, the UI shows them and the LAN carries them (see net)
+pub struct ChatPlugin;
+
+impl Plugin for ChatPlugin {
+    fn build(&self, app: &mut App) {
+        app
+        .add_message::<SayChat>()
+        .add_message::<ChatMessage>()
+        .init_resource::<ChatLimiter>()
+        .init_resource::<AIChatReplies>()
+        .add_systems(Update, (limit_chat, ai_answer_chat, say_ai_replies).chain()
+            .run_if(in_state(AppState::Setup).or(in_state(AppState::PlayerTurn)).or(in_state(AppState::RoundEnd))))
+        .add_systems(OnEnter(AppState::RoundEnd), ai_react_to_round_end.after(calculate_scores))
+        .add_systems(OnEnter(AppState::MainMenu), clear_chat);
+    }
+}
@@ -0,0 +1,37 @@
+use bevy::prelude::*;
+use std::collections::{HashMap, VecDeque};
+use crate::game::chat::event::Emote;
+
+// longest free text message, in characters
+pub const MAX_TEXT_LENGTH: usize = 60;
+
+// recent messages of each seat, against chat spam
+#[derive(Resource, Default)]
+pub struct ChatLimiter {
+    recent: HashMap<usize, VecDeque<f32>>,
+}
+
+impl ChatLimiter {
+    // at most MAX_MESSAGES messages every WINDOW seconds
+    pub const MAX_MESSAGES: usize = 3;
+    pub const WINDOW: f32 = 5.0;
+
+    // verify if the seat can talk now (elapsed seconds), and count the message
+    pub fn allow(&mut self, seat: usize, now: f32) -> bool {
+        let recent = self.recent.entry(seat).or_default();
+        while recent.front().is_some_and(|&time| now - time >= Self::WINDOW) {
+            recent.pop_front();
+        }
+        if recent.len() >= Self::MAX_MESSAGES {
+            return false;
+        }
+        recent.push_back(now);
+        true
+    }
+}
+
+// emotes the AI players will say, after a short pause
+#[derive(Resource, Default)]
+pub struct AIChatReplies {
+    pub pending: Vec<(f32, usize, Emote)>, // time (elapsed seconds), seat and emote
+}
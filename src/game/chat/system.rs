@@ -0,0 +1,98 @@
+use bevy::prelude::*;
+use rand::Rng;
+use rand::seq::IndexedRandom;
+use crate::game::chat::event::{ChatContent, ChatMessage, Emote, SayChat};
+use crate::game::chat::resource::{AIChatReplies, ChatLimiter, MAX_TEXT_LENGTH};
+use crate::game::ai::component::AIPlayer;
+use crate::game::player::component::Player;
+use crate::game::round_end::resource::RoundScores;
+
+// accept the chat of every seat that is under the rate limit
+pub fn limit_chat(
+    time: Res<Time>,
+    mut limiter: ResMut<ChatLimiter>,
+    mut say_messages: MessageReader<SayChat>,
+    mut chat_messages: MessageWriter<ChatMessage>,
+) {
+    for say in say_messages.read() {
+        let content = match &say.content {
+            ChatContent::Text(text) => {
+                let text: String = text.trim().chars().filter(|c| !c.is_control()).take(MAX_TEXT_LENGTH).collect();
+                if text.is_empty() { continue; }
+                ChatContent::Text(text)
+            }
+            emote => emote.clone(),
+        };
+
+        if !limiter.allow(say.seat, time.elapsed_secs()) {
+            info!(target: "mygame", "Seat {} is talking too fast, message dropped", say.seat);
+            continue;
+        }
+        chat_messages.write(ChatMessage { seat: say.seat, content });
+    }
+}
+
+// an AI answers the other players now and then
+pub fn ai_answer_chat(
+    time: Res<Time>,
+    mut chat_messages: MessageReader<ChatMessage>,
+    ai_query: Query<&Player, With<AIPlayer>>,
+    mut replies: ResMut<AIChatReplies>,
+) {
+    let mut rng = rand::rng();
+    for message in chat_messages.read() {
+        // never answer another AI, or the table never goes quiet
+        if ai_query.iter().any(|player| player.seat == message.seat) || !replies.pending.is_empty() { continue; }
+
+        let (emote, chance) = match &message.content {
+            ChatContent::Emote(emote) => (emote.reply(), 0.6),
+            ChatContent::Text(_) => (Emote::Nice, 0.3),
+        };
+        let seats: Vec<usize> = ai_query.iter().map(|player| player.seat).collect();
+        if let Some(&seat) = seats.choose(&mut rng) && rng.random_bool(chance) {
+            let at = time.elapsed_secs() + rng.random_range(1.0..2.5);
+            replies.pending.push((at, seat, emote));
+        }
+    }
+}
+
+// the AI with the lowest score cheers, the others may congratulate
+pub fn ai_react_to_round_end(
+    time: Res<Time>,
+    ai_query: Query<(Entity, &Player), With<AIPlayer>>,
+    round_scores: Res<RoundScores>,
+    mut replies: ResMut<AIChatReplies>,
+) {
+    let mut rng = rand::rng();
+    for (player_entity, player) in ai_query.iter() {
+        let at = time.elapsed_secs() + rng.random_range(0.5..2.0);
+        if round_scores.is_winner(player_entity) {
+            replies.pending.push((at, player.seat, Emote::Nice));
+        } else if rng.random_bool(0.5) {
+            replies.pending.push((at, player.seat, Emote::WellPlayed));
+        }
+    }
+}
+
+// say the AI emotes whose time has come
+pub fn say_ai_replies(
+    time: Res<Time>,
+    mut replies: ResMut<AIChatReplies>,
+    mut say_messages: MessageWriter<SayChat>,
+) {
+    let now = time.elapsed_secs();
+    replies.pending.retain(|&(at, seat, emote)| {
+        if at > now { return true; }
+        say_messages.write(SayChat { seat, content: ChatContent::Emote(emote) });
+        false
+    });
+}
+
+// forget the chat of the last game
+pub fn clear_chat(
+    mut limiter: ResMut<ChatLimiter>,
+    mut replies: ResMut<AIChatReplies>,
+) {
+    *limiter = ChatLimiter::default();
+    replies.pending.clear();
+}
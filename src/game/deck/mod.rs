@@ -2,6 +2,7 @@ use bevy::prelude::*;
 use crate::game::{deck::system::spawn_cards, gamestate::AppState};
 use crate::game::card::system::setup_cards;
 pub mod component;
+pub mod resource;
 mod system;
 
 // deckset
@@ -0,0 +1,71 @@
+use serde::{Deserialize, Serialize};
+use crate::game::card::component::Suit;
+
+// cards of one deck: every value of the list in the 4 suits, plus the jokers
+#[derive(Debug, PartialEq)]
+pub struct DeckDefinition {
+    pub values: &'static [u8],
+    pub jokers: usize, // value 0, their points are a rule of the table
+}
+
+impl DeckDefinition {
+    pub const SUITS: [Suit; 4] = [Suit::Coarse, Suit::Cup, Suit::Gold, Suit::Sword];
+    pub const SPANISH_48: DeckDefinition = DeckDefinition { values: &[1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12], jokers: 0 };
+    pub const SPANISH_40: DeckDefinition = DeckDefinition { values: &[1, 2, 3, 4, 5, 6, 7, 10, 11, 12], jokers: 0 };
+    pub const WITH_JOKERS: DeckDefinition = DeckDefinition { values: &[1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12], jokers: 2 };
+
+    pub fn has_value(&self, value: u8) -> bool {
+        self.values.contains(&value)
+    }
+
+    // every card of the definition, copies decks shuffled together
+    pub fn cards(&self, copies: usize) -> Vec<(Suit, u8)> {
+        let one_deck = Self::SUITS.iter()
+            .flat_map(|suit| self.values.iter().map(move |&value| (suit.clone(), value)))
+            .chain((0..self.jokers).map(|_| (Suit::Joker, 0)));
+        let one_deck: Vec<(Suit, u8)> = one_deck.collect();
+        (0..copies.max(1)).flat_map(|_| one_deck.iter().cloned()).collect()
+    }
+}
+
+// deck chosen in the rules
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Default)]
+pub enum DeckKind {
+    #[default]
+    Spanish48,
+    Spanish40, // no 8s and 9s
+    WithJokers, // 48 cards and 2 jokers
+}
+
+impl DeckKind {
+    pub const ALL: [DeckKind; 3] = [DeckKind::Spanish48, DeckKind::Spanish40, DeckKind::WithJokers];
+
+    pub fn definition(&self) -> &'static DeckDefinition {
+        match self {
+            DeckKind::Spanish48 => &DeckDefinition::SPANISH_48,
+            DeckKind::Spanish40 => &DeckDefinition::SPANISH_40,
+            DeckKind::WithJokers => &DeckDefinition::WITH_JOKERS,
+        }
+    }
+
+    // name on the server command line
+    pub fn name(&self) -> &'static str {
+        match self {
+            DeckKind::Spanish48 => "spanish48",
+            DeckKind::Spanish40 => "spanish40",
+            DeckKind::WithJokers => "jokers",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|kind| kind.name() == name)
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            DeckKind::Spanish48 => "48 CARDS",
+            DeckKind::Spanish40 => "40 CARDS",
+            DeckKind::WithJokers => "50 WITH JOKERS",
+        }
+    }
+}
@@ -1,22 +1,18 @@
 use bevy::prelude::*;
 use rand::seq::SliceRandom;
-use bevy::window::PrimaryWindow;
 
 use super::component::Deck;
-use crate::game::{card::component::{Card, CardBack, CardHandles, CardPosition, Suit}, gamestate::GameEntity};
+use crate::game::layout::TableLayout;
+use crate::game::rng::GameRng;
+use crate::game::rules::resource::Ruleset;
+use crate::game::{card::component::{Card, CardBack, CardHandles, CardPosition, KnownBy, Suit}, gamestate::GameEntity};
 
-pub fn spawn_cards(mut commands: Commands, card_handles: Option<Res<CardHandles>>, card_back: Option<Res<CardBack>>, windows: Query<&Window, With<PrimaryWindow>>,) {
-    let suits = [Suit::Coarse, Suit::Cup, Suit::Gold, Suit::Sword];
-
-    // generate all combinations (suit-value)
-    let mut cards: Vec<(Suit, u8)> = suits
-        .iter()
-        .flat_map(|suit| (1..=12).map(move |value| (suit.clone(), value)))
-        .collect();
+pub fn spawn_cards(mut commands: Commands, card_handles: Option<Res<CardHandles>>, card_back: Option<Res<CardBack>>, layout: Res<TableLayout>, mut game_rng: ResMut<GameRng>, ruleset: Res<Ruleset>) {
+    // generate all combinations (suit-value) of the deck in the rules
+    let mut cards: Vec<(Suit, u8)> = ruleset.deck.definition().cards(ruleset.deck_copies);
 
     // randomize cards
-    let mut rng = rand::rng();
-    cards.shuffle(&mut rng);
+    cards.shuffle(&mut game_rng.0);
 
     // spawn card entities and save in Vec
     let mut card_entities = Vec::new();
@@ -27,22 +23,10 @@ pub fn spawn_cards(mut commands: Commands, card_handles: Option<Res<CardHandles>
 
     for (suit, value) in cards {
 
-        let suit_idx = match suit {
-            Suit::Coarse => 0,
-            Suit::Cup    => 1,
-            Suit::Gold   => 2,
-            Suit::Sword  => 3,
-        };
-        let idx = suit_idx * 12 + (value as usize - 1);
+        let idx = suit.face_index(value);
         let front = card_handles.0[idx].clone();
         let handle = front;
 
-        // obtain window
-        let Ok(window) = windows.single() else { 
-            warn!("No primary window found");
-            return; 
-        };
-
         // offsets
         let stack_index = card_entities.len() as f32;  // use card spawned index
         let max_stack_effect = 15.0;  // first 15 cards add offset
@@ -58,19 +42,21 @@ pub fn spawn_cards(mut commands: Commands, card_handles: Option<Res<CardHandles>
         let card_entity = commands.spawn((
             Sprite::from_image(card_back.0.clone()),
             Transform::from_xyz(
-                window.width() * 0.15 + stack_offset_x, 
-                window.height() * 0.0 + stack_offset_y, 
+                layout.width * 0.15 + stack_offset_x, 
+                layout.height * 0.0 + stack_offset_y, 
                 idx as f32
             ).with_scale(Vec3::splat(0.7)),
             Card {
                 suit,
                 value,
                 face_up: false,
+                known_by: KnownBy::default(),
                 owner_id: None,
                 position: CardPosition::Deck,
                 front_face: handle.clone(),
                 from_deck: false,
                 is_being_dealt: false,
+                frozen: false,
             },
             GameEntity,
         )).id();
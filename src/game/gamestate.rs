@@ -4,6 +4,7 @@ use bevy::prelude::*;
 pub enum AppState {
     #[default]
     MainMenu, // game menu
+    Lobby, // LAN lobby and table browser
     Setup, // in game
     PlayerTurn, // local player turn
     RoundEnd // end button
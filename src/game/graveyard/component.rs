@@ -2,5 +2,6 @@ use bevy::prelude::{Component, Entity};
 
 #[derive(Component)]
 pub struct Graveyard {
-    pub cards: Vec<Entity>
+    pub cards: Vec<Entity>,
+    pub discarded_by: Option<Entity>, // player who put the top card there
 }
\ No newline at end of file
@@ -1,18 +1,13 @@
 use bevy::prelude::*;
-use bevy::window::PrimaryWindow;
 
 use crate::game::gamestate::GameEntity;
 use crate::game::graveyard::component::Graveyard;
+use crate::game::layout::TableLayout;
 
-pub fn spawn_graveyard(mut commands: Commands, windows: Query<&Window, With<PrimaryWindow>>,) {
-    let Ok(window) = windows.single() else { 
-        warn!("No primary window found");
-        return; 
-    };
-
+pub fn spawn_graveyard(mut commands: Commands, layout: Res<TableLayout>) {
     commands.spawn((
-        Graveyard { cards: Vec::new() },
-        Transform::from_xyz(window.width() * -0.06, window.height() * 0.0, 5.0),
+        Graveyard { cards: Vec::new(), discarded_by: None },
+        Transform::from_xyz(layout.width * -0.06, layout.height * 0.0, 5.0),
         GameEntity,
     ));
     info!(target: "mygame", "Graveyard spawned");
@@ -3,4 +3,8 @@ use bevy::prelude::{Component, Entity};
 #[derive(Component)]
 pub struct Hand {
     pub cards: Vec<Entity>,
+}
+
+impl Hand {
+    pub const SIZE: usize = 4; // cards dealt to each player, snaps and penalties change it during the round
 }
\ No newline at end of file
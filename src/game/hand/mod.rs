@@ -2,6 +2,7 @@ use bevy::prelude::*;
 use crate::game::{hand::system::deal_initial_hands, gamestate::AppState};
 use crate::game::player::system::spawn_player;
 use crate::game::deck::DeckSet;
+use crate::game::net::{is_net_client, is_lockstep};
 pub mod component;
 pub mod system;
 
@@ -9,6 +10,6 @@ pub struct HandPlugin;
 
 impl Plugin for HandPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(OnEnter(AppState::Setup), deal_initial_hands.after(DeckSet).after(spawn_player));
+        app.add_systems(OnEnter(AppState::Setup), deal_initial_hands.after(DeckSet).after(spawn_player).run_if(not(is_net_client).or(is_lockstep)));
     }
 }
\ No newline at end of file
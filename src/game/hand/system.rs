@@ -1,11 +1,12 @@
 use bevy::prelude::*;
-use bevy::window::PrimaryWindow;
 
 use super::component::Hand;
 
 use crate::game::deck::component::Deck;
 use crate::game::player::component::Player;
 use crate::game::card::component::{Card, CardPosition};
+use crate::game::layout::TableLayout;
+use crate::game::net::resource::NetSession;
 use crate::ui::card_animation::component::{CardAnimation, AnimationType, AnimationState};
 
 pub fn deal_initial_hands (
@@ -14,7 +15,8 @@ pub fn deal_initial_hands (
     player_query: Query<(Entity, &Player), With<Player>>,
     mut card_query: Query<(&mut Card, &mut Transform)>,
     mut hand_query: Query<&mut Hand>,
-    windows: Query<&Window, With<PrimaryWindow>>,
+    layout: Res<TableLayout>,
+    net_session: Res<NetSession>,
 ) {
     // search deck
     let mut deck = match deck_query.single_mut() {
@@ -25,34 +27,31 @@ pub fn deal_initial_hands (
         }
     };
 
+    // deal in host seat order, so every lockstep peer gives the same cards to the same seat
+    let mut players: Vec<(Entity, &Player)> = player_query.iter().collect();
+    players.sort_by_key(|(_, player)| net_session.host_seat(player.seat));
+
     // verify if player have 4 cards
-    for (i, (player_entity, player_component)) in player_query.iter().enumerate() {
-        if deck.cards_values.len() < 4 {
+    for (i, (player_entity, player_component)) in players.into_iter().enumerate() {
+        if deck.cards_values.len() < Hand::SIZE {
             warn!(target: "mygame","⚠️ There are not enough cards for this player");
             continue;
         }
         // take 4 deck cards
-        let hand_cards: Vec<Entity> = deck.cards_values.drain(0..4).collect();
-
-        // obtain window dimensions
-        let Ok(window) = windows.single() else { return; };
+        let hand_cards: Vec<Entity> = deck.cards_values.drain(0..Hand::SIZE).collect();
 
         // player positions
-        let positions = match i {
-            0 => get_player_positions(0, window.width(), window.height()),
-            1 => get_player_positions(1, window.width(), window.height()),
-            _ => get_player_positions(0, window.width(), window.height()),// default
-        };
+        let positions = get_player_positions(player_component.seat, Hand::SIZE, layout.width, layout.height);
 
         // obtain deck position
-        let deck_position = Vec3::new(window.width() * 0.15, 0.0, 20.0);
+        let deck_position = Vec3::new(layout.width * 0.15, 0.0, 20.0);
 
         // iterate deck cards and distribute it to players
         for (j, &card_e) in hand_cards.iter().enumerate() {
             if let Ok((mut card, mut tf)) = card_query.get_mut(card_e) {
                 card.owner_id = Some(player_entity);
                 card.position = CardPosition::Hand(player_entity);
-                card.face_up  = i == 0 && j < 2;
+                card.hide(); // the players choose what to look at in the peek
                 card.is_being_dealt = true;
 
                 let target_pos = positions[j]; // save target position
@@ -61,7 +60,7 @@ pub fn deal_initial_hands (
                 tf.scale = Vec3::splat(0.7);
 
                 // calculate delay
-                let delay = (i * 4 + j) as f32 * 0.15;
+                let delay = (i * Hand::SIZE + j) as f32 * 0.15;
                 
                 // insert deal animation
                 commands.entity(card_e).insert(CardAnimation {
@@ -85,31 +84,35 @@ pub fn deal_initial_hands (
     }
 }
 
-// player positions auxiliar system
-pub fn get_player_positions(player_i: usize, window_width: f32, window_height: f32) -> [Vec3; 4] {
-    match player_i {
-        0 => {
-            let base_y = window_height * -0.15;  // 15% down
-            let base_x = window_width * -0.06;  // 8% left
-            let gap = window_width * 0.06;       // 7% win width
-            [
-                Vec3::new(base_x, base_y, 10.0),
-                Vec3::new(base_x + gap, base_y, 11.0),
-                Vec3::new(base_x + gap * 2.0, base_y, 12.0),
-                Vec3::new(base_x + gap * 3.0, base_y, 13.0),
-            ]
-        },
-        1 => {
-            let base_y = window_height * 0.15;
-            let base_x = window_width * -0.06;
-            let gap = window_width * 0.06;
-            [
-                Vec3::new(base_x, base_y, 10.0),
-                Vec3::new(base_x + gap, base_y, 11.0),
-                Vec3::new(base_x + gap * 2.0, base_y, 12.0),
-                Vec3::new(base_x + gap * 3.0, base_y, 13.0),
-            ]
-        },
-        _ => get_player_positions(0, window_width, window_height)
+// player positions auxiliar system (0 bottom, 1 top, 2 left, 3 right), one per card of the hand
+// a hand bigger than the dealt one squeezes into the same width
+pub fn get_player_positions(player_i: usize, hand_size: usize, window_width: f32, window_height: f32) -> Vec<Vec3> {
+    let (base_x, base_y) = match player_i {
+        1 => (window_width * -0.06, window_height * 0.15),  // 15% up
+        2 => (window_width * -0.45, window_height * 0.0),   // left side
+        3 => (window_width * 0.27, window_height * 0.0),    // right side
+        _ => (window_width * -0.06, window_height * -0.15), // 15% down
+    };
+    let gap = window_width * 0.06; // 6% win width
+    let gap = if hand_size > Hand::SIZE { gap * (Hand::SIZE - 1) as f32 / (hand_size - 1) as f32 } else { gap };
+
+    (0..hand_size)
+        .map(|slot| Vec3::new(base_x + gap * slot as f32, base_y, 10.0 + slot as f32))
+        .collect()
+}
+
+// move the cards of a hand to their slots, after the hand grew or shrank
+pub fn arrange_hand(
+    commands: &mut Commands,
+    hand: &Hand,
+    seat: usize,
+    layout: &TableLayout,
+    card_query: &Query<(Entity, &mut Transform, &mut Card), With<Card>>,
+) {
+    let positions = get_player_positions(seat, hand.cards.len(), layout.width, layout.height);
+    for (&card_entity, target) in hand.cards.iter().zip(positions) {
+        if let Ok((_, transform, _)) = card_query.get(card_entity) && transform.translation.truncate() != target.truncate() {
+            commands.entity(card_entity).insert(CardAnimation::movement(transform, target));
+        }
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,15 @@
+use bevy::prelude::*;
+
+// size of the table, every card position is relative to it
+// the windowed client keeps it in sync with the window, a headless server keeps the default
+#[derive(Resource, Clone, Copy, Debug)]
+pub struct TableLayout {
+    pub width: f32,
+    pub height: f32,
+}
+
+impl Default for TableLayout {
+    fn default() -> Self {
+        Self { width: 1280.0, height: 720.0 }
+    }
+}
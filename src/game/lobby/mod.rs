@@ -0,0 +1,29 @@
+use bevy::prelude::*;
+use crate::game::gamestate::AppState;
+
+pub mod protocol;
+pub mod resource;
+mod system;
+
+use resource::{LobbyDiscovery, LobbySettings};
+use system::{answer_discovery, browse_tables, broadcast_lobby, close_discovery};
+
+// LAN lobby: hosts announce their table over UDP, browsers list them before joining
+pub struct LobbyPlugin;
+
+impl Plugin for LobbyPlugin {
+    fn build(&self, app: &mut App) {
+        app
+        .init_resource::<LobbyDiscovery>()
+        .init_resource::<LobbySettings>()
+        .add_systems(Update, answer_discovery)
+        .add_systems(Update, broadcast_lobby.run_if(in_lobby))
+        .add_systems(Update, browse_tables.run_if(in_state(AppState::Lobby)))
+        .add_systems(OnExit(AppState::Lobby), close_discovery);
+    }
+}
+
+// the dedicated server waits for its clients in the main menu
+pub fn in_lobby(state: Res<State<AppState>>) -> bool {
+    matches!(state.get(), AppState::MainMenu | AppState::Lobby)
+}
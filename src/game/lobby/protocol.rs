@@ -0,0 +1,51 @@
+use serde::{Deserialize, Serialize};
+
+/*
+LOBBY DISCOVERY - one JSON object per UDP datagram
+browser -> broadcast (and loopback) on DISCOVERY_PORT..DISCOVERY_PORT + DISCOVERY_PORTS:
+    {"type":"query"}
+host -> browser:
+    {"type":"table","name":"ana's table","port":7777,"seats":3,"joined":2,"ai_fill":true,"rules":"classic","playing":false}
+several hosts on one machine answer on the next free discovery ports, a running game keeps answering for spectators
+once joined, the lobby goes over the LAN connection (see net/protocol.rs):
+    client -> host {"type":"ready","ready":true}
+    host -> client {"type":"lobby","seats":3,"ai_fill":true,"players":[{"name":"ana","ready":true,"host":true}]}
+*/
+
+pub const DISCOVERY_PORT: u16 = 7787;
+pub const DISCOVERY_PORTS: u16 = 4;
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Discovery {
+    Query,
+    Table(TableInfo),
+}
+
+// table announced by a host
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct TableInfo {
+    pub name: String,
+    pub port: u16, // LAN port of the table, on the address that answered
+    pub seats: usize,
+    pub joined: usize,
+    pub ai_fill: bool, // empty seats are played by the AI
+    pub rules: String,
+    #[serde(default)]
+    pub playing: bool, // the game started, it can only be watched
+}
+
+// players waiting at the table, sent to every client when it changes
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct LobbyView {
+    pub seats: usize,
+    pub ai_fill: bool,
+    pub players: Vec<LobbyPlayer>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct LobbyPlayer {
+    pub name: String,
+    pub ready: bool,
+    pub host: bool,
+}
@@ -0,0 +1,115 @@
+use bevy::prelude::*;
+use std::io;
+use std::net::{Ipv4Addr, SocketAddr, UdpSocket};
+use crate::game::lobby::protocol::{Discovery, TableInfo, DISCOVERY_PORT, DISCOVERY_PORTS};
+use crate::game::rules::resource::Ruleset;
+
+// table settings chosen by the host in the lobby
+#[derive(Resource, Clone)]
+pub struct LobbySettings {
+    pub name: String,
+    pub seats: usize,
+    pub ai_fill: bool,
+    pub host_plays: bool, // false on the dedicated server
+    pub open_hands_for_spectators: bool,
+    pub spectator_delay: f32, // seconds
+    pub lockstep: bool, // every peer runs the rules with the same seed, the host only relays the actions
+}
+
+impl Default for LobbySettings {
+    fn default() -> Self {
+        Self { name: format!("{}'s table", local_name()), seats: 2, ai_fill: true, host_plays: true, open_hands_for_spectators: false, spectator_delay: 0.0, lockstep: false }
+    }
+}
+
+impl LobbySettings {
+    pub const MIN_SEATS: usize = 2;
+    pub const MAX_SEATS: usize = 4;
+    pub const SPECTATOR_DELAYS: [f32; 4] = [0.0, 5.0, 15.0, 30.0];
+
+    // rule options shown in the table list
+    pub fn rules(&self, ruleset: &Ruleset) -> String {
+        let mut rules = ruleset.variants();
+        if rules.is_empty() {
+            rules.push(String::from("classic"));
+        }
+        if self.lockstep {
+            rules.push(String::from("lockstep"));
+        }
+        rules.join(", ")
+    }
+
+    // the AI can't play a lockstep seat, its choices are not the same on every peer
+    pub fn fills_with_ai(&self) -> bool {
+        self.ai_fill && !self.lockstep
+    }
+
+    // the host can start once every client is ready and the empty seats can be filled
+    pub fn can_start(&self, peers: usize, all_ready: bool) -> bool {
+        let players = peers + self.host_plays as usize;
+        peers > 0 && all_ready && players <= self.seats && (self.fills_with_ai() || players == self.seats)
+    }
+}
+
+// name shown to the other players, taken from the user account
+pub fn local_name() -> String {
+    std::env::var("USER")
+        .or_else(|_| std::env::var("USERNAME"))
+        .unwrap_or_else(|_| String::from("Guest"))
+}
+
+// table found on the LAN
+#[derive(Clone, Debug)]
+pub struct FoundTable {
+    pub address: SocketAddr,
+    pub info: TableInfo,
+    pub last_seen: f32, // seconds since startup
+}
+
+// UDP sockets of the lobby: hosts answer queries, browsers send them
+#[derive(Resource, Default)]
+pub struct LobbyDiscovery {
+    pub responder: Option<UdpSocket>,
+    pub browser: Option<UdpSocket>,
+    pub tables: Vec<FoundTable>,
+    pub generation: u32, // changes whenever the table list changes
+    pub next_query: f32,
+}
+
+impl LobbyDiscovery {
+    pub const QUERY_INTERVAL: f32 = 2.0;
+    pub const TABLE_TIMEOUT: f32 = 6.0;
+
+    // hosts take the first free discovery port, so several can run on one machine
+    pub fn bind_responder() -> io::Result<UdpSocket> {
+        let mut last_error = io::Error::other("no discovery port");
+        for port in DISCOVERY_PORT..DISCOVERY_PORT + DISCOVERY_PORTS {
+            match UdpSocket::bind((Ipv4Addr::UNSPECIFIED, port)) {
+                Ok(socket) => {
+                    socket.set_nonblocking(true)?;
+                    return Ok(socket);
+                }
+                Err(error) => last_error = error,
+            }
+        }
+        Err(last_error)
+    }
+
+    pub fn bind_browser() -> io::Result<UdpSocket> {
+        let socket = UdpSocket::bind((Ipv4Addr::UNSPECIFIED, 0))?;
+        socket.set_nonblocking(true)?;
+        socket.set_broadcast(true)?;
+        Ok(socket)
+    }
+
+    // ask every host on the LAN and on this machine for its table
+    pub fn send_query(socket: &UdpSocket) {
+        let Ok(query) = serde_json::to_vec(&Discovery::Query) else { return; };
+        for port in DISCOVERY_PORT..DISCOVERY_PORT + DISCOVERY_PORTS {
+            for address in [Ipv4Addr::BROADCAST, Ipv4Addr::LOCALHOST] {
+                // broadcast can be refused (no network), loopback still works
+                let _ = socket.send_to(&query, (address, port));
+            }
+        }
+    }
+}
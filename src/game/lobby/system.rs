@@ -0,0 +1,148 @@
+use bevy::prelude::*;
+use std::net::SocketAddr;
+use crate::game::lobby::protocol::{Discovery, TableInfo, LobbyView, LobbyPlayer};
+use crate::game::lobby::resource::{LobbyDiscovery, LobbySettings, FoundTable, local_name};
+use crate::game::net::protocol::HostToClient;
+use crate::game::net::resource::NetSession;
+use crate::game::gamestate::AppState;
+use crate::game::rules::resource::Ruleset;
+
+// hosts answer discovery queries with their table, also while playing so spectators can find it
+pub fn answer_discovery(
+    mut discovery: ResMut<LobbyDiscovery>,
+    net_session: Res<NetSession>,
+    settings: Res<LobbySettings>,
+    ruleset: Res<Ruleset>,
+    state: Res<State<AppState>>,
+) {
+    let NetSession::Host(host) = &*net_session else {
+        discovery.responder = None;
+        return;
+    };
+
+    if discovery.responder.is_none() {
+        match LobbyDiscovery::bind_responder() {
+            Ok(socket) => discovery.responder = Some(socket),
+            Err(error) => {
+                warn!(target: "mygame", "LAN discovery unavailable: {}", error);
+                return;
+            }
+        }
+    }
+    let Some(socket) = discovery.responder.as_ref() else { return; };
+    let Ok(port) = host.listener.local_addr().map(|address| address.port()) else { return; };
+
+    let mut buffer = [0u8; 1024];
+    while let Ok((length, sender)) = socket.recv_from(&mut buffer) {
+        if !matches!(serde_json::from_slice(&buffer[..length]), Ok(Discovery::Query)) { continue; }
+
+        let table = Discovery::Table(TableInfo {
+            name: settings.name.clone(),
+            port,
+            seats: settings.seats,
+            joined: host.peers.len() + settings.host_plays as usize,
+            ai_fill: settings.fills_with_ai(),
+            rules: settings.rules(&ruleset),
+            playing: !matches!(state.get(), AppState::MainMenu | AppState::Lobby),
+        });
+        if let Ok(answer) = serde_json::to_vec(&table) {
+            let _ = socket.send_to(&answer, sender);
+        }
+    }
+}
+
+// browsers query the LAN and keep the list of tables that answered
+pub fn browse_tables(
+    mut discovery: ResMut<LobbyDiscovery>,
+    net_session: Res<NetSession>,
+    time: Res<Time>,
+) {
+    // only while looking for a table
+    if !matches!(*net_session, NetSession::Offline) {
+        discovery.browser = None;
+        return;
+    }
+
+    if discovery.browser.is_none() {
+        match LobbyDiscovery::bind_browser() {
+            Ok(socket) => discovery.browser = Some(socket),
+            Err(error) => {
+                warn!(target: "mygame", "LAN discovery unavailable: {}", error);
+                return;
+            }
+        }
+        discovery.next_query = 0.0;
+    }
+
+    let now = time.elapsed_secs();
+    let discovery = &mut *discovery;
+    let Some(socket) = discovery.browser.as_ref() else { return; };
+
+    if now >= discovery.next_query {
+        LobbyDiscovery::send_query(socket);
+        discovery.next_query = now + LobbyDiscovery::QUERY_INTERVAL;
+    }
+
+    let mut buffer = [0u8; 1024];
+    while let Ok((length, sender)) = socket.recv_from(&mut buffer) {
+        let Ok(Discovery::Table(info)) = serde_json::from_slice(&buffer[..length]) else { continue; };
+        let address = SocketAddr::new(sender.ip(), info.port);
+
+        match discovery.tables.iter_mut().find(|table| table.address == address) {
+            Some(table) => {
+                if table.info != info {
+                    table.info = info;
+                    discovery.generation += 1;
+                }
+                table.last_seen = now;
+            }
+            None => {
+                discovery.tables.push(FoundTable { address, info, last_seen: now });
+                discovery.generation += 1;
+            }
+        }
+    }
+
+    // forget the tables that stopped answering
+    let count = discovery.tables.len();
+    discovery.tables.retain(|table| now - table.last_seen < LobbyDiscovery::TABLE_TIMEOUT);
+    if discovery.tables.len() != count {
+        discovery.generation += 1;
+    }
+}
+
+// send the players waiting at the table to every client when it changes
+pub fn broadcast_lobby(
+    mut net_session: ResMut<NetSession>,
+    settings: Res<LobbySettings>,
+    mut last_view: Local<Option<LobbyView>>,
+) {
+    let NetSession::Host(host) = &mut *net_session else {
+        *last_view = None;
+        return;
+    };
+
+    let mut players = Vec::new();
+    if settings.host_plays {
+        players.push(LobbyPlayer { name: local_name(), ready: true, host: true });
+    }
+    players.extend(host.peers.iter().map(|peer| LobbyPlayer { name: peer.name.clone(), ready: peer.ready, host: false }));
+    let view = LobbyView { seats: settings.seats, ai_fill: settings.fills_with_ai(), players };
+
+    if last_view.as_ref() == Some(&view) { return; }
+
+    for peer in host.peers.iter_mut().filter(|peer| peer.connected) {
+        if let Err(error) = peer.connection.send(&HostToClient::Lobby(view.clone())) {
+            warn!(target: "mygame", "Could not update {}: {}", peer.name, error);
+            peer.connected = false;
+        }
+    }
+    *last_view = Some(view);
+}
+
+// the table list is rebuilt from scratch each time the lobby opens
+pub fn close_discovery(mut discovery: ResMut<LobbyDiscovery>) {
+    discovery.browser = None;
+    discovery.tables.clear();
+    discovery.generation += 1;
+}
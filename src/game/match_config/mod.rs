@@ -0,0 +1,27 @@
+use bevy::prelude::*;
+use crate::game::gamestate::AppState;
+
+pub mod resource;
+mod system;
+use resource::{MatchConfig, GameSpeed, Viewer, PassCurtain};
+use system::{update_fast_forward, stop_fast_forward, raise_pass_curtain, lower_pass_curtain};
+
+pub struct MatchConfigPlugin;
+
+impl Plugin for MatchConfigPlugin {
+    fn build(&self, app: &mut App) {
+        app
+        .init_resource::<MatchConfig>()
+        .init_resource::<GameSpeed>()
+        .init_resource::<Viewer>()
+        .init_resource::<PassCurtain>()
+        .add_systems(Update, update_fast_forward.run_if(in_state(AppState::PlayerTurn)))
+        .add_systems(OnExit(AppState::PlayerTurn), stop_fast_forward)
+        .add_systems(Update, (lower_pass_curtain, raise_pass_curtain).chain()
+            .run_if(in_state(AppState::PlayerTurn).and(is_hot_seat)));
+    }
+}
+
+pub fn is_hot_seat(match_config: Res<MatchConfig>) -> bool {
+    match_config.hot_seat
+}
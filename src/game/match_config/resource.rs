@@ -0,0 +1,181 @@
+use bevy::prelude::*;
+use crate::game::ai::component::{AIThinkTime, AIThinkSettings};
+use crate::game::card::component::Card;
+
+// who sits at each seat of the next match
+#[derive(Clone, PartialEq, Debug)]
+pub enum SeatKind {
+    Human,
+    AI(AIThinkTime),
+    Bot(String), // external bot process command
+    Remote(usize), // LAN player, index of the peer on the host (played elsewhere on a client)
+}
+
+// match configuration chosen in the main menu, read by spawn_player when a round starts
+#[derive(Resource)]
+pub struct MatchConfig {
+    pub seats: Vec<SeatKind>,
+    pub spectator: bool, // AI vs AI, nobody plays locally
+    pub show_hands: bool, // spectator can see every hand face-up
+    pub hot_seat: bool, // several humans share the screen, passing it between turns
+}
+
+// player whose point of view is rendered, None for spectators (only public cards)
+#[derive(Resource, Default)]
+pub struct Viewer(pub Option<Entity>);
+
+impl Viewer {
+    pub fn can_see(&self, card: &Card) -> bool {
+        match self.0 {
+            Some(player) => card.is_visible_to(player),
+            None => card.face_up,
+        }
+    }
+}
+
+// hot-seat: the screen is hidden until this player takes it
+#[derive(Resource, Default)]
+pub struct PassCurtain {
+    pub waiting_for: Option<Entity>,
+    pub click_started: bool, // the click that ended the last turn must not lift the curtain
+}
+
+// for default, one local player against one AI
+impl Default for MatchConfig {
+    fn default() -> Self {
+        Self {
+            seats: vec![SeatKind::Human, SeatKind::AI(AIThinkTime::default())],
+            spectator: false,
+            show_hands: false,
+            hot_seat: false,
+        }
+    }
+}
+
+impl MatchConfig {
+    // every seat played by the AI
+    pub fn spectator(ai_players: usize) -> Self {
+        Self {
+            seats: vec![SeatKind::AI(AIThinkTime::default()); ai_players],
+            spectator: true,
+            show_hands: false,
+            hot_seat: false,
+        }
+    }
+}
+
+impl MatchConfig {
+    // pass-and-play, every seat played by a human on this screen
+    pub fn hot_seat(humans: usize) -> Self {
+        Self {
+            seats: vec![SeatKind::Human; humans],
+            spectator: false,
+            show_hands: false,
+            hot_seat: true,
+        }
+    }
+}
+
+impl MatchConfig {
+    // LAN host: the host plays the first seat, each connected peer one more seat
+    pub fn lan_host(peers: usize) -> Self {
+        let mut seats = vec![SeatKind::Human];
+        seats.extend((0..peers).map(SeatKind::Remote));
+        Self { seats, spectator: false, show_hands: false, hot_seat: false }
+    }
+
+    // LAN spectator: every seat is played elsewhere, open hands when the host allows it
+    pub fn lan_watch(players: usize, open_hands: bool) -> Self {
+        Self {
+            seats: (0..players).map(SeatKind::Remote).collect(),
+            spectator: true,
+            show_hands: open_hands,
+            hot_seat: false,
+        }
+    }
+
+    // empty seats of a lobby table are played by the AI
+    pub fn with_ai_fill(mut self, seats: usize) -> Self {
+        while self.seats.len() < seats {
+            self.seats.push(SeatKind::AI(AIThinkTime::default()));
+        }
+        self
+    }
+
+    // LAN client: own seat first (bottom of the screen), the rest played on other machines
+    pub fn lan_client(players: usize) -> Self {
+        let mut seats = vec![SeatKind::Human];
+        seats.extend((1..players).map(SeatKind::Remote));
+        Self { seats, spectator: false, show_hands: false, hot_seat: false }
+    }
+
+    // dedicated server: every peer plays one seat, the AI fills the rest, nobody plays locally
+    pub fn server(peers: usize, ai_players: usize) -> Self {
+        let mut seats: Vec<SeatKind> = (0..peers).map(SeatKind::Remote).collect();
+        seats.extend(std::iter::repeat_n(SeatKind::AI(AIThinkTime::default()), ai_players));
+        Self { seats, spectator: true, show_hands: false, hot_seat: false }
+    }
+}
+
+impl MatchConfig {
+    // each AI seat waits as long as its think time setting
+    pub fn with_think_times(mut self, settings: &AIThinkSettings) -> Self {
+        for (index, seat) in self.seats.iter_mut().enumerate() {
+            if let SeatKind::AI(think_time) = seat {
+                *think_time = settings.for_seat(index);
+            }
+        }
+        self
+    }
+
+    // external bots take the AI seats, starting from the last one
+    pub fn with_bots(mut self, bot_commands: &[String]) -> Self {
+        let ai_seats = self.seats.iter_mut().rev().filter(|seat| matches!(seat, SeatKind::AI(_)));
+        for (seat, command) in ai_seats.zip(bot_commands) {
+            *seat = SeatKind::Bot(command.clone());
+        }
+        self
+    }
+}
+
+// pace of the game: scales AI think timers and card animations
+#[derive(Resource)]
+pub struct GameSpeed {
+    pub scale: f32,
+    pub paused: bool,
+    pub step_from: Option<Entity>, // while paused, play until this player's turn ends
+    pub fast_forward: bool, // fast-forward key held during an AI turn
+}
+
+// for default, normal speed
+impl Default for GameSpeed {
+    fn default() -> Self {
+        Self {
+            scale: 1.0,
+            paused: false,
+            step_from: None,
+            fast_forward: false,
+        }
+    }
+}
+
+impl GameSpeed {
+    pub const MIN_SCALE: f32 = 0.25;
+    pub const MAX_SCALE: f32 = 8.0;
+    pub const FAST_FORWARD_SCALE: f32 = 5.0;
+
+    // verify if the game can advance this frame
+    pub fn is_running(&self) -> bool {
+        !self.paused || self.step_from.is_some()
+    }
+
+    // frame delta scaled by the game speed, zero while paused
+    pub fn delta_secs(&self, time: &Time) -> f32 {
+        if self.is_running() {
+            let fast_forward = if self.fast_forward { Self::FAST_FORWARD_SCALE } else { 1.0 };
+            time.delta_secs() * self.scale * fast_forward
+        } else {
+            0.0
+        }
+    }
+}
@@ -0,0 +1,71 @@
+use bevy::prelude::*;
+use crate::game::match_config::resource::{GameSpeed, PassCurtain, Viewer};
+use crate::game::player::component::Player;
+use crate::game::ai::component::AIPlayer;
+use crate::game::turn_player::component::Turn;
+use crate::game::peek::resource::PeekPhase;
+
+// hold F to fast-forward AI turns (think time and card animations)
+pub fn update_fast_forward(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    turn: Res<Turn>,
+    ai_query: Query<(), With<AIPlayer>>,
+    mut game_speed: ResMut<GameSpeed>,
+) {
+    let fast_forward = keyboard.pressed(KeyCode::KeyF) && ai_query.contains(turn.current_player);
+    if game_speed.fast_forward != fast_forward {
+        game_speed.fast_forward = fast_forward;
+    }
+}
+
+// the round can end while F is held, the scores and the next deal run at normal speed
+pub fn stop_fast_forward(mut game_speed: ResMut<GameSpeed>) {
+    game_speed.fast_forward = false;
+}
+
+// hot-seat: hide the table when the turn (or the peek) passes to another player
+pub fn raise_pass_curtain(
+    turn: Res<Turn>,
+    peek_phase: Res<PeekPhase>,
+    player_query: Query<&Player>,
+    mut viewer: ResMut<Viewer>,
+    mut pass_curtain: ResMut<PassCurtain>,
+) {
+    let next_player = match peek_phase.active {
+        true => match peek_phase.local_peeker() {
+            Some(peeker) => peeker,
+            None => return,
+        },
+        false => turn.current_player,
+    };
+    if viewer.0 == Some(next_player) || pass_curtain.waiting_for == Some(next_player) {
+        return;
+    }
+
+    viewer.0 = None;
+    if player_query.get(next_player).is_ok_and(|player| player.is_local_player) {
+        pass_curtain.waiting_for = Some(next_player);
+        pass_curtain.click_started = false;
+    }
+}
+
+// hot-seat: the next player takes the screen (click or Space)
+pub fn lower_pass_curtain(
+    mouse_input: Res<ButtonInput<MouseButton>>,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut viewer: ResMut<Viewer>,
+    mut pass_curtain: ResMut<PassCurtain>,
+) {
+    if pass_curtain.waiting_for.is_none() { return; }
+
+    if mouse_input.just_pressed(MouseButton::Left) {
+        pass_curtain.click_started = true;
+    }
+
+    // lift on release, so the click never reaches the cards
+    let clicked = pass_curtain.click_started && mouse_input.just_released(MouseButton::Left);
+    if clicked || keyboard.just_pressed(KeyCode::Space) {
+        viewer.0 = pass_curtain.waiting_for.take();
+        info!(target: "mygame", "Screen passed to player {:?}", viewer.0);
+    }
+}
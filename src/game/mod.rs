@@ -12,6 +12,18 @@ pub mod turn_player;
 pub mod round_end;
 pub mod special_cards;
 pub mod ai;
+pub mod match_config;
+pub mod bot;
+pub mod net;
+pub mod layout;
+pub mod rng;
+pub mod lobby;
+pub mod snapshot;
+pub mod chat;
+pub mod turn_timer;
+pub mod rules;
+pub mod peek;
+pub mod snap;
 
 use gamestate::GameStatePlugin;
 use hand::HandPlugin;
@@ -22,19 +34,45 @@ use turn_player::TurnPlugin;
 use round_end::RoundEndPlugin;
 use special_cards::SpecialCardsPlugin;
 use ai::AIPlugin;
+use match_config::MatchConfigPlugin;
+use bot::BotPlugin;
+use net::NetPlugin;
+use lobby::LobbyPlugin;
+use chat::ChatPlugin;
+use turn_timer::TurnTimerPlugin;
+use rules::RulesPlugin;
+use peek::PeekPlugin;
+use snap::SnapPlugin;
+use layout::TableLayout;
+use rng::GameRng;
+use crate::ui::soundtrack::event::{PlayCardDraw, PlayCardPlace};
 
 pub struct GamePlugin;
 
 impl Plugin for GamePlugin {
     fn build(&self, app: &mut App) {
         app
+        .init_resource::<TableLayout>()
+        .init_resource::<GameRng>()
+        // game systems ask for sounds even when no audio plugin listens
+        .add_message::<PlayCardDraw>()
+        .add_message::<PlayCardPlace>()
         .add_plugins(GameStatePlugin)
+        .add_plugins(MatchConfigPlugin)
         .add_plugins(HandPlugin)
         .add_plugins(GraveyardPlugin)
         .add_plugins(DeckPlugin)
         .add_plugins(CardPlugin)
         .add_plugins(TurnPlugin)
         .add_plugins(AIPlugin)
+        .add_plugins(BotPlugin)
+        .add_plugins(NetPlugin)
+        .add_plugins(LobbyPlugin)
+        .add_plugins(ChatPlugin)
+        .add_plugins(TurnTimerPlugin)
+        .add_plugins(RulesPlugin)
+        .add_plugins(PeekPlugin)
+        .add_plugins(SnapPlugin)
         .add_plugins(RoundEndPlugin)
         .add_plugins(SpecialCardsPlugin)
 
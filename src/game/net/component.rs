@@ -0,0 +1,5 @@
+use bevy::prelude::*;
+
+// remote seat played by the AI while its player is away, handed back when they rejoin
+#[derive(Component)]
+pub struct StandIn;
@@ -0,0 +1,434 @@
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+use crate::game::net::resource::{NetSession, NetPeer, Lockstep, ClientTargeting};
+use crate::game::net::protocol::{ClientHello, HostToClient};
+use crate::game::net::system::{cursor_world_position, read_action};
+use crate::game::bot::protocol::{BotReply, DrawSource};
+use crate::game::bot::component::BotState;
+use crate::game::card::component::{Card, CardPosition};
+use crate::game::{deck::component::Deck, graveyard::component::Graveyard, hand::component::Hand, player::component::Player, turn_player::component::Turn};
+use crate::game::layout::TableLayout;
+use crate::game::match_config::resource::{MatchConfig, Viewer};
+use crate::game::rng::GameRng;
+use crate::game::snapshot::format::GameSnapshot;
+use crate::game::snapshot::system::{capture_snapshot, restore_snapshot};
+use crate::game::special_cards::resource::SpecialCardEffect;
+use crate::game::turn_timer::resource::TurnClock;
+use crate::game::rules::resource::Ruleset;
+use crate::game::special_cards::resource::SpecialEffectRegistry;
+use rand::seq::{IndexedRandom, SliceRandom};
+
+// every round of a lockstep table is dealt from a seed drawn by the host and sent in the welcome
+pub fn seed_lockstep_round(
+    mut net_session: ResMut<NetSession>,
+    match_config: Res<MatchConfig>,
+    mut game_rng: ResMut<GameRng>,
+) {
+    if let NetSession::Host(host) = &mut *net_session && host.lockstep.is_some() {
+        host.lockstep = Some(Lockstep::new(rand::random(), match_config.seats.len()));
+    }
+    let Some(lockstep) = net_session.lockstep() else { return; };
+    game_rng.seed(lockstep.seed);
+    info!(target: "mygame", "Lockstep round, seed {}", lockstep.seed);
+}
+
+// the local player plays through its own queue like every other seat, and the action goes to the other peers
+pub fn lockstep_input(
+    mouse_input: Res<ButtonInput<MouseButton>>,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    (windows, layout): (Query<&Window, With<PrimaryWindow>>, Res<TableLayout>),
+    camera_query: Query<(&Camera, &GlobalTransform)>,
+    card_query: Query<(Entity, &mut Transform, &mut Card), With<Card>>,
+    turn: Res<Turn>,
+    viewer: Res<Viewer>,
+    player_query: Query<&Player>,
+    hand_query: Query<&Hand>,
+    graveyard_query: Query<&Graveyard>,
+    mut net_session: ResMut<NetSession>,
+    (ruleset, registry): (Res<Ruleset>, Res<SpecialEffectRegistry>),
+) {
+    // only on our turn, once the last action was played
+    let Some(local_player) = viewer.0 else { return; };
+    if turn.current_player != local_player { return; }
+    let Ok(player) = player_query.get(local_player) else { return; };
+    let own_seat = net_session.host_seat(player.seat);
+    let rotation = match &*net_session {
+        NetSession::Client(client) => Some((client.seat, client.players.max(1))),
+        _ => None,
+    };
+    let Some(lockstep) = net_session.lockstep_mut() else { return; };
+    if lockstep.is_waiting(own_seat) { return; }
+
+    let world_pos = cursor_world_position(&mouse_input, &windows, &camera_query);
+    let Some(action) = read_action(
+        (&keyboard, &mouse_input, world_pos),
+        &card_query,
+        (&player_query, &hand_query, &graveyard_query),
+        &layout,
+        &turn,
+        (local_player, &ruleset, &registry),
+        &mut lockstep.targeting,
+        |seat| rotation.map_or(seat, |(seat_offset, players)| (seat + seat_offset) % players),
+    ) else { return; };
+
+    play_lockstep_action(&mut net_session, own_seat, action);
+}
+
+// out of time: the local player gives random targets to its special, else draws and discards the drawn card
+pub fn lockstep_timeout(
+    clock: Res<TurnClock>,
+    card_query: Query<&Card>,
+    turn: Res<Turn>,
+    viewer: Res<Viewer>,
+    player_query: Query<&Player>,
+    hand_query: Query<&Hand>,
+    deck_query: Query<&Deck>,
+    (special_effect, registry): (Option<Res<SpecialCardEffect>>, Res<SpecialEffectRegistry>),
+    mut net_session: ResMut<NetSession>,
+) {
+    if !clock.is_expired() { return; }
+    let Some(local_player) = viewer.0 else { return; };
+    if turn.current_player != local_player { return; }
+    let Ok(player) = player_query.get(local_player) else { return; };
+    let own_seat = net_session.host_seat(player.seat);
+    let Some(lockstep) = net_session.lockstep_mut() else { return; };
+    if lockstep.is_waiting(own_seat) { return; }
+    let targeting = lockstep.targeting.take();
+
+    let has_drawn_card = card_query.iter().any(|card| card.position == CardPosition::DrawnCard(local_player));
+    let mut rng = rand::rng();
+    // slots that can be picked, the frozen cards stay out of an exchange
+    let exchanges_cards = special_effect.as_ref()
+        .and_then(|effect| effect.effect_type)
+        .is_some_and(|effect_type| registry.exchanges_cards(effect_type));
+    let slots = |player: &Player| -> Vec<usize> {
+        hand_query.get(player.hand).map(|hand| hand.cards.iter().enumerate()
+            .filter(|&(_, &card)| !exchanges_cards || card_query.get(card).is_ok_and(|card| !card.frozen))
+            .map(|(slot, _)| slot)
+            .collect()
+        ).unwrap_or_default()
+    };
+    let opponents: Vec<_> = player_query.iter()
+        .filter(|opponent| opponent.seat != player.seat)
+        .map(|opponent| (net_session.host_seat(opponent.seat), slots(opponent)))
+        .filter(|(_, slots)| !slots.is_empty())
+        .collect();
+    let own_slot = slots(player).choose(&mut rng).copied();
+
+    let action = match targeting {
+        _ if !has_drawn_card => {
+            let deck_empty = deck_query.single().is_ok_and(|deck| deck.cards_values.is_empty());
+            BotReply::Draw { from: if deck_empty { DrawSource::Graveyard } else { DrawSource::Deck } }
+        }
+        Some(ClientTargeting::OpponentPlayer) => match opponents.choose(&mut rng) {
+            Some(&(seat, _)) => BotReply::Special { target_player: Some(seat), target_slot: None, own_slot: None, target_cards: Vec::new() },
+            None => BotReply::Discard,
+        },
+        Some(ClientTargeting::OpponentCard) => match opponents.choose(&mut rng) {
+            Some((seat, slots)) => BotReply::Special { target_player: Some(*seat), target_slot: slots.choose(&mut rng).copied(), own_slot: None, target_cards: Vec::new() },
+            None => BotReply::Discard,
+        },
+        Some(ClientTargeting::OwnCard) => BotReply::Special { target_player: None, target_slot: None, own_slot, target_cards: Vec::new() },
+        Some(ClientTargeting::OpponentAndOwnCard { target }) => {
+            let random_target = opponents.choose(&mut rng)
+                .and_then(|(seat, slots)| slots.choose(&mut rng).map(|&slot| (*seat, slot)));
+            let target = target.or(random_target);
+            match (target, own_slot) {
+                (Some((seat, slot)), Some(own_slot)) => BotReply::Special { target_player: Some(seat), target_slot: Some(slot), own_slot: Some(own_slot), target_cards: Vec::new() },
+                _ => BotReply::Discard,
+            }
+        }
+        // the cards still missing are drawn from the free opponent cards
+        Some(ClientTargeting::Several(chosen)) => {
+            let mut target_cards: Vec<(usize, usize)> = chosen.iter().flatten().copied().collect();
+            let mut free_cards: Vec<(usize, usize)> = opponents.iter()
+                .flat_map(|(seat, slots)| slots.iter().map(|&slot| (*seat, slot)))
+                .filter(|card| !target_cards.contains(card))
+                .collect();
+            free_cards.shuffle(&mut rng);
+            let missing = chosen.len() - target_cards.len();
+            if free_cards.len() < missing {
+                BotReply::Discard
+            } else {
+                target_cards.extend(free_cards.into_iter().take(missing));
+                BotReply::Special { target_player: None, target_slot: None, own_slot: None, target_cards }
+            }
+        }
+        // out of time before deciding, the cards stay where they are
+        Some(ClientTargeting::Decide) => BotReply::Decide { swap: false },
+        None => BotReply::Discard,
+    };
+    info!(target: "mygame", "Out of time, playing {:?}", action);
+    play_lockstep_action(&mut net_session, own_seat, action);
+}
+
+// host: queue the actions of the clients and pass them on to the other clients, in the order they came
+pub fn relay_lockstep_actions(mut net_session: ResMut<NetSession>) {
+    let NetSession::Host(host) = &mut *net_session else { return; };
+    let Some(lockstep) = &mut host.lockstep else { return; };
+
+    let mut relayed = Vec::new();
+    for peer in host.peers.iter().filter(|peer| peer.connected) {
+        let Some(seat) = peer.seat else { continue; };
+        while let Ok(line) = peer.connection.try_recv() {
+            match serde_json::from_str(&line) {
+                Ok(ClientHello::Action { action }) => {
+                    lockstep.push(seat, &action);
+                    relayed.push((seat, action));
+                }
+                Ok(ClientHello::Hash { turn, hash }) => lockstep.peer_hashes.push((seat, turn, hash)),
+                _ => {}
+            }
+        }
+    }
+
+    // a broken connection is noticed by hold_dropped_seats
+    for (seat, action) in relayed {
+        for peer in host.peers.iter().filter(|peer| peer.connected && peer.seat != Some(seat)) {
+            let _ = peer.connection.send(&HostToClient::Action { seat, action: action.clone() });
+        }
+    }
+}
+
+// at the start of every turn each peer hashes its table, the host compares and resyncs the clients that differ
+pub fn check_lockstep_hashes(
+    mut net_session: ResMut<NetSession>,
+    turn: Res<Turn>,
+    card_query: Query<(Entity, &Card)>,
+    player_query: Query<(Entity, &Player)>,
+    hand_query: Query<&Hand>,
+    graveyard_query: Query<&Graveyard>,
+    deck_query: Query<&Deck>,
+    ruleset: Res<Ruleset>,
+) {
+    if net_session.lockstep().is_none_or(|lockstep| lockstep.last_player == Some(turn.current_player)) { return; }
+    let hash = table_hash(&net_session, &turn, &card_query, &player_query, &hand_query, graveyard_query.single().ok(), deck_query.single().ok());
+
+    match &mut *net_session {
+        NetSession::Host(host) => {
+            let Some(lockstep) = &mut host.lockstep else { return; };
+            lockstep.last_player = Some(turn.current_player);
+            lockstep.turn += 1;
+            lockstep.record_hash(lockstep.turn, hash);
+
+            // hashes of turns the host did not reach yet wait, the too old ones are dropped
+            for (seat, peer_turn, peer_hash) in std::mem::take(&mut lockstep.peer_hashes) {
+                match lockstep.hash_of(peer_turn) {
+                    Some(own_hash) if own_hash != peer_hash && !lockstep.resync.contains(&seat) => {
+                        warn!(target: "mygame", "Seat {} is out of sync at turn {}, sending the table", seat, peer_turn);
+                        lockstep.resync.push(seat);
+                    }
+                    None if peer_turn > lockstep.turn => lockstep.peer_hashes.push((seat, peer_turn, peer_hash)),
+                    _ => {}
+                }
+            }
+            if lockstep.resync.is_empty() { return; }
+
+            if host.cards.is_empty() {
+                host.cards = card_query.iter().map(|(entity, _)| entity).collect();
+                host.cards.sort();
+            }
+            let snapshot = capture_snapshot(None, &host.cards, &card_query, &player_query, &hand_query, graveyard_query.single().ok(), deck_query.single().ok(), &turn, &ruleset);
+            for seat in std::mem::take(&mut lockstep.resync) {
+                if let Some(peer) = host.peers.iter_mut().find(|peer| peer.connected && peer.seat == Some(seat)) {
+                    send_lockstep_table(peer, lockstep, snapshot.clone());
+                }
+            }
+        }
+        NetSession::Client(client) => {
+            let Some(lockstep) = &mut client.lockstep else { return; };
+            lockstep.last_player = Some(turn.current_player);
+            lockstep.turn += 1;
+            lockstep.record_hash(lockstep.turn, hash);
+            if client.reconnect_until.is_none() {
+                let _ = client.connection.send(&ClientHello::Hash { turn: lockstep.turn, hash });
+            }
+        }
+        NetSession::Offline => {}
+    }
+}
+
+// client: put the table sent by the host, every seat starts its turn again from it
+pub fn restore_lockstep_table(
+    mut commands: Commands,
+    mut net_session: ResMut<NetSession>,
+    mut turn: ResMut<Turn>,
+    mut card_query: Query<(Entity, &mut Transform, &mut Card)>,
+    player_query: Query<(Entity, &Player)>,
+    mut hand_query: Query<&mut Hand>,
+    mut graveyard_query: Query<&mut Graveyard>,
+    mut deck_query: Query<&mut Deck>,
+    mut bot_query: Query<(Entity, &mut BotState)>,
+    layout: Res<TableLayout>,
+) {
+    let NetSession::Client(client) = &mut *net_session else { return; };
+    let (own_seat, players) = (client.seat, client.players.max(1));
+    let Some(lockstep) = &mut client.lockstep else { return; };
+    let Some(snapshot) = lockstep.restore.take() else { return; };
+
+    let mut graveyard = graveyard_query.single_mut().ok();
+    let mut deck = deck_query.single_mut().ok();
+    let restored = restore_snapshot(
+        &snapshot,
+        |seat| (seat + players - own_seat) % players,
+        &mut card_query,
+        &player_query,
+        &mut hand_query,
+        (graveyard.as_deref_mut(), deck.as_deref_mut()),
+        &mut turn,
+        &layout,
+    );
+    if !restored {
+        warn!(target: "mygame", "The table sent by the host does not match this deck");
+    }
+
+    // the host sends the actions it did not play yet right after the table
+    lockstep.clear_queues();
+    lockstep.targeting = None;
+    lockstep.turn = snapshot.turn;
+    lockstep.last_player = Some(turn.current_player);
+    lockstep.hashes.clear();
+    commands.insert_resource(SpecialCardEffect::default());
+
+    // a drawn card is picked up again by its bot controller
+    for (bot_entity, mut state) in bot_query.iter_mut() {
+        let drawing = bot_entity == turn.current_player
+            && card_query.iter().any(|(_, _, card)| card.position == CardPosition::DrawnCard(bot_entity));
+        *state = if drawing { BotState::ExecutingDraw } else { BotState::Idle };
+    }
+    info!(target: "mygame", "Table resynced at turn {}, {} cards in the deck", snapshot.turn, snapshot.deck_count());
+}
+
+// send a peer the whole table and the actions the host did not play yet, the peer drops its own queues
+pub fn send_lockstep_table(peer: &mut NetPeer, lockstep: &Lockstep, mut snapshot: GameSnapshot) {
+    snapshot.turn = lockstep.turn;
+    let mut messages = vec![HostToClient::Snapshot(snapshot)];
+    for seat in 0..lockstep.queues.len() {
+        messages.extend(lockstep.pending(seat).into_iter().map(|action| HostToClient::Action { seat, action }));
+    }
+
+    for message in &messages {
+        if let Err(error) = peer.connection.send(message) {
+            warn!(target: "mygame", "Could not resync {}: {}", peer.name, error);
+            peer.connected = false;
+            return;
+        }
+    }
+}
+
+// seats targeted by a special, turned into another seat numbering
+pub fn map_target_seat(action: BotReply, seat: impl Fn(usize) -> usize) -> BotReply {
+    match action {
+        BotReply::Special { target_player, target_slot, own_slot, target_cards } => {
+            let target_cards = target_cards.into_iter().map(|(target, slot)| (seat(target), slot)).collect();
+            BotReply::Special { target_player: target_player.map(&seat), target_slot, own_slot, target_cards }
+        }
+        other => other,
+    }
+}
+
+// auxiliar function - queue an action of the local seat (targets as host seats) and send it to the other peers
+fn play_lockstep_action(net_session: &mut NetSession, own_seat: usize, action: BotReply) {
+    let rotation = match &*net_session {
+        NetSession::Client(client) => Some((client.seat, client.players.max(1))),
+        _ => None,
+    };
+    let Some(lockstep) = net_session.lockstep_mut() else { return; };
+    lockstep.push(own_seat, &map_target_seat(action.clone(), |seat| rotation.map_or(seat, |(seat_offset, players)| (seat + players - seat_offset) % players)));
+
+    match net_session {
+        NetSession::Host(host) => {
+            for peer in host.peers.iter().filter(|peer| peer.connected) {
+                let _ = peer.connection.send(&HostToClient::Action { seat: own_seat, action: action.clone() });
+            }
+        }
+        NetSession::Client(client) => {
+            if let Err(error) = client.connection.send(&ClientHello::Action { action }) {
+                warn!(target: "mygame", "Could not send action to the host: {}", error);
+            }
+        }
+        NetSession::Offline => {}
+    }
+}
+
+// auxiliar function - hash of the faces in the deck, the graveyard and every hand (by host seat), and the current seat
+fn table_hash(
+    net_session: &NetSession,
+    turn: &Turn,
+    card_query: &Query<(Entity, &Card)>,
+    player_query: &Query<(Entity, &Player)>,
+    hand_query: &Query<&Hand>,
+    graveyard: Option<&Graveyard>,
+    deck: Option<&Deck>,
+) -> u64 {
+    let faces = |cards: &[Entity]| {
+        cards.iter()
+            .map(|&entity| card_query.get(entity).ok().map(|(_, card)| [card.value, card.suit.index() as u8, card.face_up as u8, card.frozen as u8]))
+            .collect::<Vec<_>>()
+    };
+
+    let mut hands: Vec<_> = player_query.iter()
+        .map(|(_, player)| {
+            let cards = hand_query.get(player.hand).map(|hand| faces(&hand.cards)).unwrap_or_default();
+            (net_session.host_seat(player.seat), cards)
+        })
+        .collect();
+    hands.sort();
+    let current_seat = player_query.get(turn.current_player).map(|(_, player)| net_session.host_seat(player.seat)).ok();
+
+    // every peer writes the same bytes for the same table, whatever toolchain built it
+    let mut hasher = TableHasher::default();
+    for cards in [deck.map(|deck| faces(&deck.cards_values)), graveyard.map(|graveyard| faces(&graveyard.cards))] {
+        hasher.write_faces(cards.as_deref());
+    }
+    hasher.write_u64(hands.len() as u64);
+    for (seat, cards) in &hands {
+        hasher.write_u64(*seat as u64);
+        hasher.write_faces(Some(cards));
+    }
+    match current_seat {
+        Some(seat) => { hasher.write(&[1]); hasher.write_u64(seat as u64); }
+        None => hasher.write(&[0]),
+    }
+    hasher.finish()
+}
+
+// FNV-1a 64 over an explicit byte encoding, its output is fixed by its specification
+// (the std hashers may change between Rust releases)
+struct TableHasher(u64);
+
+impl Default for TableHasher {
+    fn default() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl TableHasher {
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 ^= byte as u64;
+            self.0 = self.0.wrapping_mul(0x0000_0100_0000_01b3);
+        }
+    }
+
+    fn write_u64(&mut self, value: u64) {
+        self.write(&value.to_le_bytes());
+    }
+
+    // a missing pile, then the count and the bytes of each card (an unknown card is 0xff)
+    fn write_faces(&mut self, cards: Option<&[Option<[u8; 4]>]>) {
+        let Some(cards) = cards else {
+            self.write(&[0]);
+            return;
+        };
+        self.write(&[1]);
+        self.write_u64(cards.len() as u64);
+        for card in cards {
+            self.write(&card.unwrap_or([0xff; 4]));
+        }
+    }
+
+    fn finish(&self) -> u64 {
+        self.0
+    }
+}
@@ -0,0 +1,62 @@
+use bevy::prelude::*;
+use crate::game::gamestate::AppState;
+use crate::game::lobby::in_lobby;
+use crate::game::deck::DeckSet;
+use crate::game::player::system::spawn_player;
+use crate::game::peek::is_peeking;
+
+pub mod component;
+pub mod protocol;
+pub mod resource;
+mod system;
+mod lockstep;
+
+use resource::NetSession;
+use system::{leave_session, accept_peers, accept_late_connections, hold_dropped_seats, hand_back_seats, watch_spectators, welcome_peers, broadcast_table, relay_chat, receive_host_messages, apply_host_messages, client_input, receive_client_chat, send_client_chat};
+pub use system::cursor_world_position;
+use lockstep::{seed_lockstep_round, lockstep_input, lockstep_timeout, relay_lockstep_actions, check_lockstep_hashes, restore_lockstep_table};
+
+// LAN games: the host runs the rules, clients mirror the table and send their actions
+pub struct NetPlugin;
+
+impl Plugin for NetPlugin {
+    fn build(&self, app: &mut App) {
+        app
+        .init_resource::<NetSession>()
+        .add_systems(OnEnter(AppState::MainMenu), leave_session)
+        .add_systems(Update, accept_peers.run_if(in_lobby))
+        .add_systems(Update, (accept_late_connections, hold_dropped_seats, hand_back_seats).chain().run_if(not(in_lobby)))
+        .add_systems(Update, watch_spectators)
+        .add_systems(OnEnter(AppState::Setup), (seed_lockstep_round, welcome_peers).chain().before(DeckSet).before(spawn_player))
+        .add_systems(
+            Update,
+            broadcast_table.run_if(in_state(AppState::PlayerTurn).or(in_state(AppState::RoundEnd)))
+        )
+        .add_systems(Update, relay_chat.run_if(not(in_lobby)))
+        .add_systems(Update, (receive_host_messages, apply_host_messages).chain().run_if(is_net_client))
+        .add_systems(Update, (receive_client_chat, send_client_chat).chain().run_if(is_net_client.and(not(in_lobby))))
+        .add_systems(Update, client_input.after(apply_host_messages).run_if(in_state(AppState::PlayerTurn).and(is_net_client).and(not(is_net_spectator)).and(not(is_lockstep)).and(not(is_peeking))))
+        // lockstep: every peer runs the rules, the host only relays the actions
+        .add_systems(Update, relay_lockstep_actions.run_if(is_lockstep.and(not(in_lobby))))
+        .add_systems(
+            Update,
+            (restore_lockstep_table.after(apply_host_messages), (lockstep_input, lockstep_timeout).chain().run_if(not(is_peeking)), check_lockstep_hashes)
+            .chain()
+            .run_if(in_state(AppState::PlayerTurn).and(is_lockstep).and(not(is_net_spectator)))
+        );
+    }
+}
+
+pub fn is_net_client(net_session: Res<NetSession>) -> bool {
+    net_session.is_client()
+}
+
+// every peer of the table runs the rules with the same seed and the same actions
+pub fn is_lockstep(net_session: Res<NetSession>) -> bool {
+    net_session.lockstep().is_some()
+}
+
+// watching a LAN game, every input system stays off
+pub fn is_net_spectator(net_session: Res<NetSession>) -> bool {
+    matches!(&*net_session, NetSession::Client(client) if client.spectator)
+}
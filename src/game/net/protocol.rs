@@ -0,0 +1,119 @@
+use serde::{Deserialize, Serialize};
+use crate::game::lobby::protocol::LobbyView;
+use crate::game::snapshot::format::GameSnapshot;
+use crate::game::chat::event::ChatMessage;
+use crate::game::bot::protocol::BotReply;
+use crate::game::rules::resource::Ruleset;
+
+/*
+LAN PROTOCOL - one JSON object per line over TCP, the host runs the rules
+client -> host:
+    {"type":"hello","name":"Player"}
+    {"type":"ready","ready":true}                 while waiting in the lobby
+    {"type":"watch","name":"Player"}              instead of hello, read-only spectator
+    {"type":"rejoin","token":123}                 instead of hello, take the seat back after a drop
+    then the same actions as the bot replies (see bot/protocol.rs), e.g. {"action":"draw","from":"deck"}
+    {"type":"chat","seat":0,"content":{"emote":"nice"}}    at any time, the host uses the seat of the client
+    lockstep tables send instead:
+    {"type":"action","action":{"action":"swap","slot":2}}       every action of the seat, targets are host seats
+    {"type":"hash","turn":12,"hash":123}           table hash at the start of each turn
+host -> client:
+    {"type":"lobby",...}                          players waiting in the lobby (see lobby/protocol.rs)
+    {"type":"welcome","seat":1,"players":2,"token":123}    every round starts with a welcome
+    {"type":"welcome","seat":0,"players":2,"open_hands":true}    to spectators, they may see every hand
+    the welcome also has the turn time limit ("turn_time":30.0) and the rules of the host ("rules":{"peek_count":2})
+    {"type":"table","current_seat":0,"has_drawn_card":false,"cards":[
+        {"id":3,"position":{"hand":{"seat":1,"slot":0}},"face":{"value":5,"suit":2},"face_up":false}]}
+    {"type":"round_over"}
+    {"type":"snapshot",...}                       the whole table after a rejoin (see snapshot/format.rs)
+    {"type":"chat","seat":1,"content":{"text":"hi"}}     chat of the other seats
+    the host also sends the bot messages of the remote seat, clients ignore them
+    lockstep tables send no table updates, the welcome has the seed of the round ("lockstep_seed":42) and then:
+    {"type":"action","seat":0,"action":{"action":"draw","from":"deck"}}    actions of the other seats, in the order the host got them
+    {"type":"snapshot",...}                       whole table with every face, when the client hash differs or after a rejoin
+only the cards that changed are sent, and a card only has a face when that client is allowed to see it
+seats are the host seat numbers
+*/
+
+pub const DEFAULT_PORT: u16 = 7777;
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ClientHello {
+    Hello { name: String },
+    Ready { ready: bool },
+    Watch { name: String },
+    Rejoin { token: u64 },
+    Action { action: BotReply },
+    Hash { turn: u32, hash: u64 },
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum HostToClient {
+    Lobby(LobbyView),
+    Welcome {
+        seat: usize,
+        players: usize,
+        #[serde(default)]
+        open_hands: bool,
+        #[serde(default)]
+        token: Option<u64>,
+        #[serde(default)]
+        lockstep_seed: Option<u64>,
+        #[serde(default)]
+        turn_time: Option<f32>,
+        #[serde(default)]
+        rules: Ruleset,
+    },
+    Table(TableDelta),
+    RoundOver {
+        #[serde(default)]
+        caller_seat: Option<usize>, // host seat of the player who ended the round
+    },
+    Snapshot(GameSnapshot),
+    Action { seat: usize, action: BotReply },
+}
+
+// chat lines are read apart from the rest, so they never wait behind a turn
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ChatLine {
+    Chat(ChatMessage),
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct TableDelta {
+    pub current_seat: usize,
+    pub has_drawn_card: bool,
+    #[serde(default)]
+    pub turn_number: u32,
+    pub cards: Vec<CardView>,
+}
+
+// what one client can see of one card
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct CardView {
+    pub id: usize,
+    pub position: NetPosition,
+    pub face: Option<CardFace>,
+    pub face_up: bool,
+    #[serde(default)]
+    pub frozen: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum NetPosition {
+    Deck,
+    Hand { seat: usize, slot: usize },
+    Graveyard { index: usize },
+    Drawn { seat: usize },
+}
+
+// suit index follows the texture order: coarse, cup, gold, sword, joker
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct CardFace {
+    pub value: u8,
+    pub suit: u8,
+}
@@ -0,0 +1,419 @@
+use bevy::prelude::*;
+use std::collections::{HashMap, VecDeque};
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use serde::Serialize;
+use crate::game::bot::component::BotLink;
+use crate::game::net::protocol::{CardView, ChatLine, HostToClient, NetPosition, DEFAULT_PORT};
+use crate::game::bot::protocol::BotReply;
+use crate::game::snapshot::format::GameSnapshot;
+use crate::game::chat::event::ChatMessage;
+use crate::game::lobby::protocol::LobbyView;
+use crate::game::special_cards::handler::Targeting;
+
+// TCP connection speaking JSON lines, read in a thread so the game never blocks
+pub struct NetConnection {
+    stream: TcpStream,
+    lines: Mutex<Receiver<String>>,
+    chat: Mutex<Receiver<ChatMessage>>,
+    closed: Arc<AtomicBool>, // the other side closed the connection
+}
+
+impl NetConnection {
+    pub fn new(stream: TcpStream) -> io::Result<Self> {
+        stream.set_nodelay(true)?;
+        let reader = stream.try_clone()?;
+
+        let (sender, receiver) = mpsc::channel();
+        let (chat_sender, chat_receiver) = mpsc::channel();
+        let closed = Arc::new(AtomicBool::new(false));
+        let reader_closed = closed.clone();
+        std::thread::spawn(move || {
+            for line in BufReader::new(reader).lines() {
+                let Ok(line) = line else { break; };
+                let sent = match serde_json::from_str(&line) {
+                    Ok(ChatLine::Chat(message)) => chat_sender.send(message).is_ok(),
+                    Err(_) => sender.send(line).is_ok(),
+                };
+                if !sent { break; }
+            }
+            reader_closed.store(true, Ordering::Relaxed);
+        });
+
+        Ok(Self { stream, lines: Mutex::new(receiver), chat: Mutex::new(chat_receiver), closed })
+    }
+
+    // reconnecting must not freeze the game when the host is gone
+    pub fn connect_timeout(address: SocketAddr, timeout: Duration) -> io::Result<Self> {
+        Self::new(TcpStream::connect_timeout(&address, timeout)?)
+    }
+
+    pub fn peer_address(&self) -> io::Result<SocketAddr> {
+        self.stream.peer_addr()
+    }
+
+    // verify if the other side is gone, without reading its lines
+    pub fn is_closed(&self) -> bool {
+        self.closed.load(Ordering::Relaxed)
+    }
+
+    // send one message as a JSON line
+    pub fn send<T: Serialize>(&self, message: &T) -> io::Result<()> {
+        let line = serde_json::to_string(message).map_err(io::Error::other)?;
+        self.send_line(&line)
+    }
+
+    pub fn send_line(&self, line: &str) -> io::Result<()> {
+        let mut stream = &self.stream;
+        writeln!(stream, "{}", line)?;
+        stream.flush()
+    }
+
+    // obtain the next line without blocking
+    pub fn try_recv(&self) -> Result<String, TryRecvError> {
+        match self.lines.lock() {
+            Ok(lines) => lines.try_recv(),
+            Err(_) => Err(TryRecvError::Disconnected),
+        }
+    }
+
+    // obtain the next chat message without blocking
+    pub fn try_recv_chat(&self) -> Result<ChatMessage, TryRecvError> {
+        match self.chat.lock() {
+            Ok(chat) => chat.try_recv(),
+            Err(_) => Err(TryRecvError::Disconnected),
+        }
+    }
+}
+
+// remote seat on the host: the bot controller drives it with the client actions
+pub struct RemoteLink(pub Arc<NetConnection>);
+
+impl BotLink for RemoteLink {
+    fn send_line(&mut self, line: &str) -> io::Result<()> {
+        self.0.send_line(line)
+    }
+
+    fn try_recv(&self) -> Result<String, TryRecvError> {
+        self.0.try_recv()
+    }
+}
+
+// client connected to the host
+pub struct NetPeer {
+    pub connection: Arc<NetConnection>,
+    pub name: String,
+    pub seat: Option<usize>,
+    pub connected: bool,
+    pub ready: bool, // ready-up toggle in the lobby
+    pub spectator: bool, // read-only, only sees the public cards (or every hand with open hands)
+    pub delay: f32, // seconds every message waits before being sent, against cheating spectators
+    pub delayed: VecDeque<(f32, String)>, // lines waiting for their send time
+    pub token: Option<u64>, // secret to take the seat back after a drop, None once the seat is lost
+    pub grace_until: Option<f32>, // dropped: the AI stands in until this time (elapsed seconds)
+    pub sent_cards: HashMap<usize, CardView>, // last view of each card sent to this client
+    pub sent_turn: Option<(usize, bool, u32)>,
+    pub round_over_sent: bool,
+}
+
+impl NetPeer {
+    // seconds a dropped player has to come back before the AI keeps the seat
+    pub const RECONNECT_GRACE: f32 = 60.0;
+
+    pub fn new(connection: NetConnection) -> Self {
+        Self {
+            connection: Arc::new(connection),
+            name: String::from("Guest"),
+            seat: None,
+            connected: true,
+            ready: false,
+            spectator: false,
+            delay: 0.0,
+            delayed: VecDeque::new(),
+            token: Some(rand::random()),
+            grace_until: None,
+            sent_cards: HashMap::new(),
+            sent_turn: None,
+            round_over_sent: false,
+        }
+    }
+
+    // send one message after the peer delay, now is the elapsed time in seconds
+    pub fn send_delayed<T: Serialize>(&mut self, now: f32, message: &T) -> io::Result<()> {
+        if self.delay <= 0.0 && self.delayed.is_empty() {
+            return self.connection.send(message);
+        }
+        let line = serde_json::to_string(message).map_err(io::Error::other)?;
+        self.delayed.push_back((now + self.delay, line));
+        Ok(())
+    }
+
+    // send the delayed lines whose time has come
+    pub fn flush_delayed(&mut self, now: f32) -> io::Result<()> {
+        while let Some((send_at, _)) = self.delayed.front() && *send_at <= now {
+            let Some((_, line)) = self.delayed.pop_front() else { break; };
+            self.connection.send_line(&line)?;
+        }
+        Ok(())
+    }
+
+    // forget what was sent, the next update contains the whole table
+    pub fn reset_view(&mut self) {
+        self.sent_cards.clear();
+        self.sent_turn = None;
+        self.round_over_sent = false;
+    }
+}
+
+// host of a LAN game, runs the rules for every seat
+pub struct NetHost {
+    pub listener: TcpListener,
+    pub peers: Vec<NetPeer>,
+    pub spectators: Vec<NetPeer>,
+    pub pending: Vec<NetPeer>, // connected during a game, waiting for their hello
+    pub cards: Vec<Entity>, // card ids sent to the clients are indices in this list
+    pub lockstep: Option<Lockstep>, // every peer runs the rules, the host relays the actions
+}
+
+impl NetHost {
+    pub fn bind(port: u16) -> io::Result<Self> {
+        let listener = TcpListener::bind(("0.0.0.0", port))?;
+        listener.set_nonblocking(true)?;
+        Ok(Self { listener, peers: Vec::new(), spectators: Vec::new(), pending: Vec::new(), cards: Vec::new(), lockstep: None })
+    }
+}
+
+// client of a LAN game, mirrors the table sent by the host
+pub struct NetClient {
+    pub connection: NetConnection,
+    pub seat: usize,
+    pub players: usize,
+    pub inbox: Vec<HostToClient>, // messages waiting until the table is ready
+    pub cards: Vec<Entity>, // local card entity of each card id
+    pub positions: HashMap<usize, NetPosition>, // last known position of each card id
+    pub targeting: Option<ClientTargeting>,
+    pub awaiting_host: bool, // an action was sent, wait for the table to change
+    pub lobby: Option<LobbyView>, // players waiting at the table, before the first welcome
+    pub ready: bool,
+    pub spectator: bool, // watching, no input is sent to the host
+    pub token: Option<u64>, // given in the welcome, sent back to rejoin after a drop
+    pub host_address: Option<SocketAddr>,
+    pub reconnect_until: Option<f32>, // connection lost, trying to rejoin until this time
+    pub next_reconnect: f32,
+    pub lockstep: Option<Lockstep>, // given in the welcome, the table runs here too
+}
+
+impl NetClient {
+    pub fn new(connection: NetConnection) -> Self {
+        Self {
+            host_address: connection.peer_address().ok(),
+            connection,
+            seat: 0,
+            players: 0,
+            inbox: Vec::new(),
+            cards: Vec::new(),
+            positions: HashMap::new(),
+            targeting: None,
+            awaiting_host: false,
+            lobby: None,
+            ready: false,
+            spectator: false,
+            token: None,
+            reconnect_until: None,
+            next_reconnect: 0.0,
+            lockstep: None,
+        }
+    }
+
+    // host seat of a local seat (own seat is local seat 0, at the bottom)
+    pub fn host_seat(&self, local_seat: usize) -> usize {
+        (local_seat + self.seat) % self.players.max(1)
+    }
+}
+
+// special card targeting on the client, before the action is sent
+#[derive(Clone, Debug)]
+pub enum ClientTargeting {
+    OpponentPlayer,
+    OpponentCard,
+    OwnCard,
+    OpponentAndOwnCard { target: Option<(usize, usize)> }, // host seat and slot of the opponent card
+    Decide, // both cards are shown, Y swaps and N keeps them
+    Several(Box<[Option<(usize, usize)>]>), // one entry per card, host seat and slot of the opponent cards chosen so far
+}
+
+impl ClientTargeting {
+    // first step of the targeting of an effect, None when it needs no target
+    pub fn start(targeting: Targeting) -> Option<Self> {
+        match targeting {
+            Targeting::Nothing => None,
+            Targeting::OwnCard => Some(ClientTargeting::OwnCard),
+            Targeting::OpponentCard => Some(ClientTargeting::OpponentCard),
+            Targeting::OpponentPlayer => Some(ClientTargeting::OpponentPlayer),
+            Targeting::OpponentAndOwnCard => Some(ClientTargeting::OpponentAndOwnCard { target: None }),
+            Targeting::Several(count) => Some(ClientTargeting::Several(vec![None; count as usize].into_boxed_slice())),
+        }
+    }
+}
+
+#[derive(Resource, Default)]
+pub enum NetSession {
+    #[default]
+    Offline,
+    Host(NetHost),
+    Client(NetClient),
+}
+
+impl NetSession {
+    pub fn is_client(&self) -> bool {
+        matches!(self, NetSession::Client(_))
+    }
+
+    pub fn lockstep(&self) -> Option<&Lockstep> {
+        match self {
+            NetSession::Host(host) => host.lockstep.as_ref(),
+            NetSession::Client(client) => client.lockstep.as_ref(),
+            NetSession::Offline => None,
+        }
+    }
+
+    // special card targeting of the local player, on a client or a lockstep table
+    pub fn targeting(&self) -> Option<ClientTargeting> {
+        match self {
+            NetSession::Client(client) if client.lockstep.is_none() => client.targeting.clone(),
+            _ => self.lockstep().and_then(|lockstep| lockstep.targeting.clone()),
+        }
+    }
+
+    pub fn lockstep_mut(&mut self) -> Option<&mut Lockstep> {
+        match self {
+            NetSession::Host(host) => host.lockstep.as_mut(),
+            NetSession::Client(client) => client.lockstep.as_mut(),
+            NetSession::Offline => None,
+        }
+    }
+
+    // host seat of a local seat, the same seat everywhere but on a client
+    pub fn host_seat(&self, local_seat: usize) -> usize {
+        match self {
+            NetSession::Client(client) => client.host_seat(local_seat),
+            _ => local_seat,
+        }
+    }
+}
+
+// actions of one seat waiting for its bot controller, as bot reply lines
+pub type ActionQueue = Arc<Mutex<VecDeque<String>>>;
+
+// lockstep: every peer deals with the same seed and plays the same actions in the same order
+#[derive(Default)]
+pub struct Lockstep {
+    pub seed: u64, // seed of the current round
+    pub queues: Vec<ActionQueue>, // by host seat, targets in local seats
+    pub targeting: Option<ClientTargeting>,
+    pub turn: u32, // turns started this round
+    pub last_player: Option<Entity>,
+    pub hashes: VecDeque<(u32, u64)>, // table hash at the start of the last turns
+    pub peer_hashes: Vec<(usize, u32, u64)>, // host: hashes sent by the clients (seat, turn, hash), not compared yet
+    pub resync: Vec<usize>, // host: seats that get the whole table at the next turn
+    pub restore: Option<GameSnapshot>, // client: table sent by the host, applied before the next actions
+}
+
+impl Lockstep {
+    pub const KEPT_HASHES: usize = 32;
+
+    pub fn new(seed: u64, players: usize) -> Self {
+        Self { seed, queues: (0..players).map(|_| ActionQueue::default()).collect(), ..Default::default() }
+    }
+
+    pub fn queue(&self, host_seat: usize) -> ActionQueue {
+        self.queues.get(host_seat).cloned().unwrap_or_default()
+    }
+
+    // queue one action of a seat, the targets must already be local seats
+    pub fn push(&self, host_seat: usize, action: &BotReply) {
+        let (Some(queue), Ok(line)) = (self.queues.get(host_seat), serde_json::to_string(action)) else { return; };
+        if let Ok(mut queue) = queue.lock() {
+            queue.push_back(line);
+        }
+    }
+
+    // actions of a seat not played yet, as bot replies
+    pub fn pending(&self, host_seat: usize) -> Vec<BotReply> {
+        let Some(Ok(queue)) = self.queues.get(host_seat).map(|queue| queue.lock()) else { return Vec::new(); };
+        queue.iter().filter_map(|line| serde_json::from_str(line).ok()).collect()
+    }
+
+    pub fn is_waiting(&self, host_seat: usize) -> bool {
+        self.queues.get(host_seat).and_then(|queue| queue.lock().ok()).is_some_and(|queue| !queue.is_empty())
+    }
+
+    pub fn clear_queues(&self) {
+        for queue in &self.queues {
+            if let Ok(mut queue) = queue.lock() {
+                queue.clear();
+            }
+        }
+    }
+
+    // remember the hash of a turn, the oldest ones are dropped
+    pub fn record_hash(&mut self, turn: u32, hash: u64) {
+        self.hashes.push_back((turn, hash));
+        while self.hashes.len() > Self::KEPT_HASHES {
+            self.hashes.pop_front();
+        }
+    }
+
+    pub fn hash_of(&self, turn: u32) -> Option<u64> {
+        self.hashes.iter().find(|(hash_turn, _)| *hash_turn == turn).map(|(_, hash)| *hash)
+    }
+}
+
+// seat of a lockstep table: the bot controller plays the queued actions, nothing is sent
+pub struct LockstepLink(pub ActionQueue);
+
+impl BotLink for LockstepLink {
+    fn send_line(&mut self, _line: &str) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn try_recv(&self) -> Result<String, TryRecvError> {
+        match self.0.lock() {
+            Ok(mut queue) => queue.pop_front().ok_or(TryRecvError::Empty),
+            Err(_) => Err(TryRecvError::Disconnected),
+        }
+    }
+}
+
+// LAN options given on the command line (--port <port>, --join <address>)
+#[derive(Resource)]
+pub struct NetOptions {
+    pub port: u16,
+    pub join_address: String,
+}
+
+impl NetOptions {
+    pub fn from_args(mut args: impl Iterator<Item = String>) -> Self {
+        let mut options = Self { port: DEFAULT_PORT, join_address: format!("127.0.0.1:{}", DEFAULT_PORT) };
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--port" => {
+                    if let Some(port) = args.next().and_then(|port| port.parse().ok()) {
+                        options.port = port;
+                    }
+                }
+                "--join" => {
+                    if let Some(address) = args.next() {
+                        options.join_address = address;
+                    }
+                }
+                _ => {}
+            }
+        }
+        options
+    }
+}
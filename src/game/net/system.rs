@@ -0,0 +1,1011 @@
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+use std::io::ErrorKind;
+use std::time::Duration;
+use std::sync::mpsc::TryRecvError;
+use crate::game::net::resource::{NetSession, NetConnection, NetPeer, ClientTargeting, Lockstep};
+use crate::game::net::lockstep::{send_lockstep_table, map_target_seat};
+use crate::game::net::protocol::{ClientHello, HostToClient, ChatLine, TableDelta, CardView, CardFace, NetPosition};
+use crate::game::chat::event::{ChatMessage, SayChat};
+use crate::game::bot::protocol::{BotReply, DrawSource};
+use crate::game::card::component::{Card, CardPosition, CardHandles, Suit};
+use crate::game::card::system::{detect_card_click, detect_deck_click, detect_graveyard_click};
+use crate::game::{deck::component::Deck, graveyard::component::Graveyard, hand::component::Hand, player::component::Player, turn_player::component::Turn};
+use crate::game::hand::system::get_player_positions;
+use crate::game::layout::TableLayout;
+use crate::game::match_config::resource::{MatchConfig, SeatKind, GameSpeed, Viewer};
+use crate::game::round_end::{event::StartNewRound, resource::RoundCaller};
+use crate::game::gamestate::AppState;
+use crate::game::lobby::resource::LobbySettings;
+use crate::game::turn_timer::resource::{TurnTimeLimit, TurnClock};
+use crate::game::rules::resource::Ruleset;
+use crate::game::net::component::StandIn;
+use crate::game::net::resource::RemoteLink;
+use crate::game::ai::component::{AIPlayer, AIMemory, AIState, AIDifficulty, AIThinkTime, AIThinkSettings};
+use crate::game::bot::component::{BotPlayer, BotState};
+use crate::game::snapshot::system::{capture_snapshot, net_position};
+use crate::game::special_cards::resource::SpecialEffectRegistry;
+use crate::game::special_cards::system::cancel_pressed;
+use crate::ui::card_animation::component::CardAnimation;
+use std::collections::HashMap;
+
+// leaving a game closes the LAN session
+pub fn leave_session(mut net_session: ResMut<NetSession>) {
+    if !matches!(*net_session, NetSession::Offline) {
+        *net_session = NetSession::Offline;
+        info!(target: "mygame", "LAN session closed");
+    }
+}
+
+// HOST
+
+// accept clients and read their hello while the host waits in the menu or the lobby
+pub fn accept_peers(mut net_session: ResMut<NetSession>) {
+    let NetSession::Host(host) = &mut *net_session else { return; };
+
+    loop {
+        match host.listener.accept() {
+            Ok((stream, address)) => {
+                // the connection is read in its own thread, it can block
+                let connection = stream.set_nonblocking(false).and_then(|_| NetConnection::new(stream));
+                match connection {
+                    Ok(connection) => {
+                        info!(target: "mygame", "Client connected from {}", address);
+                        host.peers.push(NetPeer::new(connection));
+                    }
+                    Err(error) => warn!(target: "mygame", "Could not accept client {}: {}", address, error),
+                }
+            }
+            Err(error) if error.kind() == ErrorKind::WouldBlock => break,
+            Err(error) => {
+                warn!(target: "mygame", "Could not accept clients: {}", error);
+                break;
+            }
+        }
+    }
+
+    for peer in host.peers.iter_mut() {
+        loop {
+            match peer.connection.try_recv() {
+                Ok(line) => {
+                    match serde_json::from_str(&line) {
+                        Ok(ClientHello::Hello { name }) => peer.name = name,
+                        Ok(ClientHello::Ready { ready }) => peer.ready = ready,
+                        Ok(ClientHello::Watch { name }) => {
+                            peer.name = name;
+                            peer.spectator = true;
+                        }
+                        // nothing to rejoin or play before the game starts
+                        Ok(ClientHello::Rejoin { .. } | ClientHello::Action { .. } | ClientHello::Hash { .. }) | Err(_) => {}
+                    }
+                }
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => {
+                    info!(target: "mygame", "{} left the lobby", peer.name);
+                    peer.connected = false;
+                    break;
+                }
+            }
+        }
+    }
+    host.peers.retain(|peer| peer.connected);
+
+    // spectators don't take a seat
+    let (spectators, players) = std::mem::take(&mut host.peers).into_iter().partition(|peer| peer.spectator);
+    host.peers = players;
+    for spectator in spectators {
+        info!(target: "mygame", "{} is watching", spectator.name);
+        host.spectators.push(spectator);
+    }
+}
+
+// a running game accepts spectators, and the players that dropped and come back
+pub fn accept_late_connections(
+    mut commands: Commands,
+    mut net_session: ResMut<NetSession>,
+    settings: Res<LobbySettings>,
+    match_config: Res<MatchConfig>,
+    (turn_time, ruleset, ai_think): (Res<TurnTimeLimit>, Res<Ruleset>, Res<AIThinkSettings>),
+    time: Res<Time>,
+    state: Res<State<AppState>>,
+    turn: Option<Res<Turn>>,
+    card_query: Query<(Entity, &Card)>,
+    player_query: Query<(Entity, &Player)>,
+    hand_query: Query<&Hand>,
+    graveyard_query: Query<&Graveyard>,
+    deck_query: Query<&Deck>,
+) {
+    let NetSession::Host(host) = &mut *net_session else { return; };
+    let now = time.elapsed_secs();
+    let players = match_config.seats.len();
+    let lockstep_seed = host.lockstep.as_ref().map(|lockstep| lockstep.seed);
+
+    loop {
+        match host.listener.accept() {
+            Ok((stream, address)) => {
+                let connection = stream.set_nonblocking(false).and_then(|_| NetConnection::new(stream));
+                match connection {
+                    Ok(connection) => host.pending.push(NetPeer::new(connection)),
+                    Err(error) => warn!(target: "mygame", "Could not accept client {}: {}", address, error),
+                }
+            }
+            Err(error) if error.kind() == ErrorKind::WouldBlock => break,
+            Err(error) => {
+                warn!(target: "mygame", "Could not accept clients: {}", error);
+                break;
+            }
+        }
+    }
+
+    // the first line tells what the connection is for
+    for mut pending in std::mem::take(&mut host.pending) {
+        let hello = match pending.connection.try_recv() {
+            Ok(line) => serde_json::from_str::<ClientHello>(&line).ok(),
+            Err(TryRecvError::Empty) => {
+                host.pending.push(pending);
+                continue;
+            }
+            Err(TryRecvError::Disconnected) => continue,
+        };
+
+        match hello {
+            Some(ClientHello::Rejoin { token }) => {
+                // the drop may not be noticed yet
+                let Some(peer) = host.peers.iter_mut()
+                    .find(|peer| (!peer.connected || peer.connection.is_closed()) && peer.token == Some(token)) else {
+                    info!(target: "mygame", "Unknown or expired rejoin, the seat was lost");
+                    continue;
+                };
+                peer.connection = pending.connection;
+                peer.connected = true;
+                peer.grace_until = None;
+                peer.reset_view();
+                info!(target: "mygame", "{} is back", peer.name);
+
+                // the AI plays until the seat is handed back with the new connection, a lockstep seat waits
+                let Some(seat) = peer.seat else { continue; };
+                if host.lockstep.is_none() && let Some((seat_player, _)) = player_query.iter().find(|(_, player)| player.seat == seat) {
+                    stand_in(&mut commands, seat_player, &ruleset, ai_think.for_seat(seat));
+                }
+
+                // a new round sends its own welcome, during a round the client gets the whole table
+                let (Some(turn), AppState::PlayerTurn | AppState::RoundEnd) = (turn.as_ref(), state.get()) else {
+                    // the round is being set up, its welcome was missed
+                    let _ = peer.connection.send(&HostToClient::Welcome { seat, players, open_hands: false, token: peer.token, lockstep_seed, turn_time: turn_time.0, rules: ruleset.clone() });
+                    continue;
+                };
+
+                // lockstep: every face and the actions still to be played, the end of a round needs nothing
+                if let Some(lockstep) = &host.lockstep {
+                    if *state.get() == AppState::PlayerTurn {
+                        let snapshot = capture_snapshot(None, &host.cards, &card_query, &player_query, &hand_query, graveyard_query.single().ok(), deck_query.single().ok(), turn, &ruleset);
+                        send_lockstep_table(peer, lockstep, snapshot);
+                    }
+                    continue;
+                }
+                let Some((seat_player, _)) = player_query.iter().find(|(_, player)| player.seat == seat) else { continue; };
+                let snapshot = capture_snapshot(Some((seat, seat_player)), &host.cards, &card_query, &player_query, &hand_query, graveyard_query.single().ok(), None, turn, &ruleset);
+
+                for card in &snapshot.cards {
+                    peer.sent_cards.insert(card.id, CardView { id: card.id, position: card.position, face: card.face, face_up: card.face_up, frozen: card.frozen });
+                }
+                peer.sent_turn = Some((snapshot.current_seat, snapshot.has_drawn_card, turn.number));
+                if let Err(error) = peer.connection.send(&HostToClient::Snapshot(snapshot)) {
+                    warn!(target: "mygame", "Could not resync {}: {}", peer.name, error);
+                    peer.connected = false;
+                }
+            }
+            // a game in progress can only be watched
+            Some(ClientHello::Hello { name } | ClientHello::Watch { name }) => {
+                info!(target: "mygame", "{} is watching", name);
+                pending.name = name;
+                pending.spectator = true;
+                pending.token = None;
+                welcome_spectator(&mut pending, &settings, (turn_time.0, &ruleset), players, now);
+                host.spectators.push(pending);
+            }
+            Some(ClientHello::Ready { .. } | ClientHello::Action { .. } | ClientHello::Hash { .. }) | None => {}
+        }
+    }
+}
+
+// a player that drops keeps the seat for a grace period, the AI plays it meanwhile
+// a lockstep table has no AI to stand in, it waits and ends when the player is not back in time
+pub fn hold_dropped_seats(
+    mut commands: Commands,
+    mut net_session: ResMut<NetSession>,
+    mut next_state: ResMut<NextState<AppState>>,
+    time: Res<Time>,
+    player_query: Query<(Entity, &Player)>,
+    stand_in_query: Query<Entity, With<StandIn>>,
+    ruleset: Res<Ruleset>,
+    ai_think: Res<AIThinkSettings>,
+) {
+    let NetSession::Host(host) = &mut *net_session else { return; };
+    let now = time.elapsed_secs();
+
+    for peer in host.peers.iter_mut() {
+        let seat_player = peer.seat.and_then(|seat| player_query.iter().find(|(_, player)| player.seat == seat)).map(|(entity, _)| entity);
+
+        if peer.connected && peer.connection.is_closed() {
+            peer.connected = false;
+        }
+
+        // just dropped
+        if !peer.connected && peer.token.is_some() && peer.grace_until.is_none() {
+            peer.grace_until = Some(now + NetPeer::RECONNECT_GRACE);
+            warn!(target: "mygame", "{} dropped, waiting {}s for them", peer.name, NetPeer::RECONNECT_GRACE);
+            if host.lockstep.is_none() && let (Some(seat), Some(seat_player)) = (peer.seat, seat_player) {
+                stand_in(&mut commands, seat_player, &ruleset, ai_think.for_seat(seat));
+            }
+        }
+
+        // too late, the AI keeps the seat
+        if let Some(grace_until) = peer.grace_until && now >= grace_until {
+            peer.grace_until = None;
+            peer.token = None;
+            if host.lockstep.is_some() {
+                warn!(target: "mygame", "{} did not come back, the lockstep table can't go on", peer.name);
+                next_state.set(AppState::MainMenu);
+                continue;
+            }
+            warn!(target: "mygame", "{} did not come back, the AI keeps the seat", peer.name);
+            if let Some(seat_player) = seat_player && stand_in_query.contains(seat_player) {
+                commands.entity(seat_player).remove::<StandIn>();
+            }
+        }
+    }
+}
+
+// give the seat back to a player that rejoined, between two of its turns
+pub fn hand_back_seats(
+    mut commands: Commands,
+    net_session: Res<NetSession>,
+    turn: Option<Res<Turn>>,
+    stand_in_query: Query<(Entity, &Player), With<StandIn>>,
+) {
+    let NetSession::Host(host) = &*net_session else { return; };
+
+    for (player_entity, player) in stand_in_query.iter() {
+        if turn.as_ref().is_some_and(|turn| turn.current_player == player_entity) { continue; }
+        let Some(peer) = host.peers.iter().find(|peer| peer.connected && peer.seat == Some(player.seat)) else { continue; };
+
+        commands.entity(player_entity)
+            .remove::<(StandIn, AIPlayer, AIMemory, AIState)>()
+            .insert((
+                BotPlayer { link: Box::new(RemoteLink(peer.connection.clone())), known_cards: HashMap::new() },
+                BotState::Idle,
+            ));
+        info!(target: "mygame", "{} plays seat {} again", peer.name, player.seat);
+    }
+}
+
+// auxiliar function - the AI takes a remote seat until its player is back
+fn stand_in(commands: &mut Commands, player_entity: Entity, ruleset: &Ruleset, think_time: AIThinkTime) {
+    commands.entity(player_entity)
+        .remove::<(BotPlayer, BotState)>()
+        .insert((
+            AIPlayer { difficulty: AIDifficulty::Hard, think_time },
+            AIMemory::for_rules(ruleset),
+            AIState::Idle,
+            StandIn,
+        ));
+}
+
+// read what spectators send (only to notice they left) and send their delayed messages
+pub fn watch_spectators(
+    mut net_session: ResMut<NetSession>,
+    time: Res<Time>,
+) {
+    let NetSession::Host(host) = &mut *net_session else { return; };
+    let now = time.elapsed_secs();
+
+    for spectator in host.spectators.iter_mut() {
+        loop {
+            match spectator.connection.try_recv() {
+                Ok(_) => {}
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => {
+                    spectator.connected = false;
+                    break;
+                }
+            }
+        }
+        if spectator.connected && spectator.flush_delayed(now).is_err() {
+            spectator.connected = false;
+        }
+        if !spectator.connected {
+            info!(target: "mygame", "{} stopped watching", spectator.name);
+        }
+    }
+    host.spectators.retain(|spectator| spectator.connected);
+}
+
+// auxiliar function - spectators watch from the first seat, the next update contains the whole table
+fn welcome_spectator(spectator: &mut NetPeer, settings: &LobbySettings, (turn_time, rules): (Option<f32>, &Ruleset), players: usize, now: f32) {
+    spectator.reset_view();
+    spectator.seat = Some(0);
+    spectator.delay = settings.spectator_delay;
+    let welcome = HostToClient::Welcome { seat: 0, players, open_hands: settings.open_hands_for_spectators, token: None, lockstep_seed: None, turn_time, rules: rules.clone() };
+    if let Err(error) = spectator.send_delayed(now, &welcome) {
+        warn!(target: "mygame", "Could not welcome {}: {}", spectator.name, error);
+        spectator.connected = false;
+    }
+}
+
+// every round starts with a welcome, clients reset their table
+pub fn welcome_peers(
+    mut net_session: ResMut<NetSession>,
+    match_config: Res<MatchConfig>,
+    settings: Res<LobbySettings>,
+    turn_time: Res<TurnTimeLimit>,
+    ruleset: Res<Ruleset>,
+    time: Res<Time>,
+) {
+    let NetSession::Host(host) = &mut *net_session else { return; };
+    host.cards.clear();
+
+    let players = match_config.seats.len();
+    let lockstep_seed = host.lockstep.as_ref().map(|lockstep| lockstep.seed);
+    for (seat, seat_kind) in match_config.seats.iter().enumerate() {
+        let SeatKind::Remote(peer_index) = seat_kind else { continue; };
+        let Some(peer) = host.peers.get_mut(*peer_index) else { continue; };
+
+        peer.seat = Some(seat);
+        peer.reset_view();
+        if peer.connected && let Err(error) = peer.connection.send(&HostToClient::Welcome { seat, players, open_hands: false, token: peer.token, lockstep_seed, turn_time: turn_time.0, rules: ruleset.clone() }) {
+            warn!(target: "mygame", "Could not welcome {}: {}", peer.name, error);
+            peer.connected = false;
+        }
+    }
+
+    for spectator in host.spectators.iter_mut() {
+        welcome_spectator(spectator, &settings, (turn_time.0, &ruleset), players, time.elapsed_secs());
+    }
+}
+
+// send each client the cards that changed, with only the faces that client can see
+pub fn broadcast_table(
+    mut net_session: ResMut<NetSession>,
+    state: Res<State<AppState>>,
+    turn: Res<Turn>,
+    card_query: Query<(Entity, &Card)>,
+    player_query: Query<(Entity, &Player)>,
+    hand_query: Query<&Hand>,
+    graveyard_query: Query<&Graveyard>,
+    (settings, round_caller): (Res<LobbySettings>, Res<RoundCaller>),
+    time: Res<Time>,
+) {
+    let NetSession::Host(host) = &mut *net_session else { return; };
+
+    // card ids are stable for the whole round
+    if host.cards.is_empty() {
+        host.cards = card_query.iter().map(|(entity, _)| entity).collect();
+        host.cards.sort();
+    }
+
+    // every card with its face, each client only gets the faces it can see
+    let graveyard = graveyard_query.single().ok();
+    let table: Vec<(CardView, &Card)> = host.cards.iter().enumerate()
+        .filter_map(|(id, &card_entity)| {
+            let (_, card) = card_query.get(card_entity).ok()?;
+            let view = CardView {
+                id,
+                position: net_position(card_entity, card, &player_query, &hand_query, graveyard),
+                face: Some(CardFace { value: card.value, suit: card.suit.index() as u8 }),
+                face_up: card.face_up,
+                frozen: card.frozen,
+            };
+            Some((view, card))
+        })
+        .collect();
+
+    let current_seat = player_query.get(turn.current_player).map(|(_, player)| player.seat).unwrap_or(0);
+    let turn_view = (current_seat, turn.has_drawn_card, turn.number);
+    // once the round is over, the host seat of its caller
+    let caller_seat = round_caller.0.and_then(|caller| player_query.get(caller).ok()).map(|(_, player)| player.seat);
+    let round_over = (*state.get() == AppState::RoundEnd).then_some(caller_seat);
+    let now = time.elapsed_secs();
+
+    // lockstep peers run the table themselves
+    for peer in host.peers.iter_mut().filter(|_| host.lockstep.is_none()) {
+        let Some(seat) = peer.seat else { continue; };
+        if !peer.connected { continue; }
+        let Some((seat_player, _)) = player_query.iter().find(|(_, player)| player.seat == seat) else { continue; };
+        send_table(peer, &table, |card| card.is_visible_to(seat_player), turn_view, round_over, now);
+    }
+
+    // spectators see the public cards, or every hand when the host opens them
+    let open_hands = settings.open_hands_for_spectators;
+    for spectator in host.spectators.iter_mut().filter(|spectator| spectator.connected) {
+        send_table(spectator, &table, |card| {
+            card.face_up || (open_hands && matches!(card.position, CardPosition::Hand(_) | CardPosition::DrawnCard(_)))
+        }, turn_view, round_over, now);
+    }
+}
+
+// auxiliar function - send one client the cards whose view changed since the last update
+fn send_table(
+    peer: &mut NetPeer,
+    table: &[(CardView, &Card)],
+    can_see: impl Fn(&Card) -> bool,
+    (current_seat, has_drawn_card, turn_number): (usize, bool, u32),
+    round_over: Option<Option<usize>>,
+    now: f32,
+) {
+    let mut cards = Vec::new();
+    for (view, card) in table {
+        let view = CardView { face: view.face.filter(|_| can_see(card)), ..view.clone() };
+        if peer.sent_cards.get(&view.id) != Some(&view) {
+            peer.sent_cards.insert(view.id, view.clone());
+            cards.push(view);
+        }
+    }
+
+    if !cards.is_empty() || peer.sent_turn != Some((current_seat, has_drawn_card, turn_number)) {
+        peer.sent_turn = Some((current_seat, has_drawn_card, turn_number));
+        let delta = HostToClient::Table(TableDelta { current_seat, has_drawn_card, turn_number, cards });
+        if let Err(error) = peer.send_delayed(now, &delta) {
+            warn!(target: "mygame", "Lost connection with {}: {}", peer.name, error);
+            peer.connected = false;
+            return;
+        }
+    }
+
+    // the revealed table goes first, so the client scores the right values
+    if let Some(caller_seat) = round_over && !peer.round_over_sent {
+        peer.round_over_sent = true;
+        let _ = peer.send_delayed(now, &HostToClient::RoundOver { caller_seat });
+    }
+}
+
+// chat of the clients goes through the rate limit of the host, every accepted line goes to the other clients
+pub fn relay_chat(
+    mut net_session: ResMut<NetSession>,
+    time: Res<Time>,
+    mut chat_messages: MessageReader<ChatMessage>,
+    mut say_messages: MessageWriter<SayChat>,
+) {
+    let NetSession::Host(host) = &mut *net_session else { return; };
+    let now = time.elapsed_secs();
+
+    // the seat is the one of the connection, not the one written by the client
+    for peer in host.peers.iter() {
+        let Some(seat) = peer.seat else { continue; };
+        while let Ok(message) = peer.connection.try_recv_chat() {
+            say_messages.write(SayChat { seat, content: message.content });
+        }
+    }
+
+    // a lost line is not worth dropping the peer, the table updates notice the broken connection
+    for message in chat_messages.read() {
+        let line = ChatLine::Chat(message.clone());
+        for peer in host.peers.iter().filter(|peer| peer.connected && peer.seat != Some(message.seat)) {
+            let _ = peer.connection.send(&line);
+        }
+        for spectator in host.spectators.iter_mut().filter(|spectator| spectator.connected) {
+            let _ = spectator.send_delayed(now, &line);
+        }
+    }
+}
+
+// CLIENT
+
+// read the host messages, they are applied in order once the table is ready
+pub fn receive_host_messages(
+    mut net_session: ResMut<NetSession>,
+    state: Res<State<AppState>>,
+    mut next_state: ResMut<NextState<AppState>>,
+    time: Res<Time>,
+) {
+    let NetSession::Client(client) = &mut *net_session else { return; };
+    let now = time.elapsed_secs();
+
+    // after a drop, try to take the seat back until the grace period ends
+    if let Some(reconnect_until) = client.reconnect_until {
+        if now >= reconnect_until {
+            warn!(target: "mygame", "Could not reconnect to the host");
+            *net_session = NetSession::Offline;
+            next_state.set(AppState::MainMenu);
+            return;
+        }
+        if now < client.next_reconnect { return; }
+        client.next_reconnect = now + 1.0;
+
+        let (Some(address), Some(token)) = (client.host_address, client.token) else { return; };
+        let connection = NetConnection::connect_timeout(address, Duration::from_millis(300))
+            .and_then(|connection| connection.send(&ClientHello::Rejoin { token }).map(|_| connection));
+        if let Ok(connection) = connection {
+            info!(target: "mygame", "Reconnected to the host, waiting for the table");
+            client.connection = connection;
+            client.reconnect_until = None;
+            client.awaiting_host = false;
+            client.targeting = None;
+        }
+        return;
+    }
+
+    let mut disconnected = false;
+    loop {
+        match client.connection.try_recv() {
+            Ok(line) => {
+                // lines that are not for the client (bot messages of the seat) are ignored
+                if let Ok(message) = serde_json::from_str::<HostToClient>(&line) {
+                    client.inbox.push(message);
+                }
+            }
+            Err(TryRecvError::Empty) => break,
+            Err(TryRecvError::Disconnected) => {
+                disconnected = true;
+                break;
+            }
+        }
+    }
+
+    if disconnected {
+        // players in a game keep their seat for a while, spectators and the lobby just leave
+        let in_game = !matches!(state.get(), AppState::MainMenu | AppState::Lobby);
+        if in_game && client.token.is_some() && !client.spectator {
+            warn!(target: "mygame", "Lost connection with the host, reconnecting...");
+            client.reconnect_until = Some(now + NetPeer::RECONNECT_GRACE);
+            client.next_reconnect = now;
+        } else {
+            warn!(target: "mygame", "Lost connection with the host");
+            *net_session = NetSession::Offline;
+            next_state.set(AppState::MainMenu);
+        }
+    }
+}
+
+// mirror the host table: rounds, turn, card positions and visible faces
+pub fn apply_host_messages(
+    mut commands: Commands,
+    mut net_session: ResMut<NetSession>,
+    state: Res<State<AppState>>,
+    mut next_state: ResMut<NextState<AppState>>,
+    mut start_messages: MessageWriter<StartNewRound>,
+    mut clock: ResMut<TurnClock>,
+    mut turn: Option<ResMut<Turn>>,
+    mut card_query: Query<(Entity, &mut Transform, &mut Card)>,
+    player_query: Query<(Entity, &Player)>,
+    mut hand_query: Query<&mut Hand>,
+    mut graveyard_query: Query<&mut Graveyard>,
+    mut deck_query: Query<&mut Deck>,
+    card_handles: Option<Res<CardHandles>>,
+    layout: Res<TableLayout>,
+) {
+    let NetSession::Client(client) = &mut *net_session else { return; };
+
+    while !client.inbox.is_empty() {
+        // a lockstep table sent by the host goes first, the next actions follow from it
+        if client.lockstep.as_ref().is_some_and(|lockstep| lockstep.restore.is_some()) { return; }
+
+        match &client.inbox[0] {
+            HostToClient::Lobby(view) => {
+                client.lobby = Some(view.clone());
+                client.inbox.remove(0);
+            }
+            HostToClient::Welcome { seat, players, open_hands, token, lockstep_seed, turn_time, rules } => {
+                match state.get() {
+                    AppState::MainMenu | AppState::Lobby => next_state.set(AppState::Setup),
+                    AppState::RoundEnd => { start_messages.write(StartNewRound); }
+                    // finish the current round first
+                    AppState::PlayerTurn => {
+                        commands.insert_resource(RoundCaller(None));
+                        next_state.set(AppState::RoundEnd);
+                        return;
+                    }
+                    AppState::Setup => return,
+                }
+                client.seat = *seat;
+                client.players = *players;
+                client.token = *token;
+                client.cards.clear();
+                client.positions.clear();
+                client.targeting = None;
+                client.awaiting_host = false;
+                client.lockstep = lockstep_seed.map(|seed| Lockstep::new(seed, *players));
+                clock.limit = *turn_time;
+                // a LAN client plays with the rules of the host
+                commands.insert_resource(rules.clone());
+                if client.spectator {
+                    commands.insert_resource(MatchConfig::lan_watch(*players, *open_hands));
+                } else {
+                    commands.insert_resource(MatchConfig::lan_client(*players));
+                }
+                commands.insert_resource(GameSpeed::default());
+                info!(target: "mygame", "Joined LAN game at seat {} of {}", seat, players);
+                client.inbox.remove(0);
+                return; // wait for the new round
+            }
+            // whole table after a rejoin, applied as an update of every card
+            HostToClient::Snapshot(snapshot) => {
+                match state.get() {
+                    AppState::PlayerTurn => {}
+                    // the round changed while away
+                    AppState::RoundEnd => {
+                        start_messages.write(StartNewRound);
+                        return;
+                    }
+                    _ => return,
+                }
+                if let Some(lockstep) = &mut client.lockstep {
+                    let HostToClient::Snapshot(snapshot) = client.inbox.remove(0) else { continue; };
+                    lockstep.restore = Some(snapshot);
+                    continue;
+                }
+                client.positions.clear();
+                client.awaiting_host = false;
+                client.targeting = None;
+                let mut cards = Vec::new();
+                for card in &snapshot.cards {
+                    // the drawn card keeps its special when it came from the deck
+                    if card.from_deck && matches!(card.position, NetPosition::Drawn { .. }) {
+                        client.positions.insert(card.id, NetPosition::Deck);
+                    }
+                    cards.push(CardView { id: card.id, position: card.position, face: card.face, face_up: card.face_up, frozen: card.frozen });
+                }
+                info!(target: "mygame", "Table resynced, {} cards in the deck", snapshot.deck_count());
+                client.inbox[0] = HostToClient::Table(TableDelta {
+                    current_seat: snapshot.current_seat,
+                    has_drawn_card: snapshot.has_drawn_card,
+                    turn_number: turn.as_ref().map_or(0, |turn| turn.number),
+                    cards,
+                });
+            }
+            // lockstep: action of another seat, played by its bot controller
+            HostToClient::Action { seat, action } => {
+                if let Some(lockstep) = &client.lockstep {
+                    let (own_seat, players) = (client.seat, client.players);
+                    lockstep.push(*seat, &map_target_seat(action.clone(), |host_seat| (host_seat + players - own_seat) % players.max(1)));
+                }
+                client.inbox.remove(0);
+            }
+            HostToClient::RoundOver { caller_seat } => {
+                if *state.get() != AppState::PlayerTurn { return; }
+                let (own_seat, players) = (client.seat, client.players);
+                let caller = caller_seat
+                    .map(|host_seat| (host_seat + players - own_seat) % players.max(1))
+                    .and_then(|seat| player_query.iter().find(|(_, player)| player.seat == seat))
+                    .map(|(entity, _)| entity);
+                commands.insert_resource(RoundCaller(caller));
+                next_state.set(AppState::RoundEnd);
+                client.inbox.remove(0);
+                return;
+            }
+            HostToClient::Table(_) => {
+                // the table is ready once the round is being played
+                if *state.get() != AppState::PlayerTurn { return; }
+                let Some(turn) = turn.as_mut() else { return; };
+                let Some(card_handles) = card_handles.as_ref() else { return; };
+                if client.cards.is_empty() {
+                    client.cards = card_query.iter().map(|(entity, _, _)| entity).collect();
+                    client.cards.sort();
+                }
+
+                let HostToClient::Table(delta) = client.inbox.remove(0) else { continue; };
+
+                // players by local seat
+                let (own_seat, players) = (client.seat, client.players);
+                let local_seat = |host_seat: usize| (host_seat + players - own_seat) % players.max(1);
+                let mut seat_players: Vec<(usize, Entity, Entity)> = player_query.iter()
+                    .map(|(entity, player)| (player.seat, entity, player.hand))
+                    .collect();
+                seat_players.sort();
+                let player_at = |host_seat: usize| seat_players.get(local_seat(host_seat)).map(|&(_, entity, _)| entity);
+                let Some(local_player) = player_at(client.seat) else { continue; };
+
+                if let Some(current_player) = player_at(delta.current_seat) {
+                    turn.current_player = current_player;
+                }
+                turn.has_drawn_card = delta.has_drawn_card;
+                turn.number = delta.turn_number;
+
+                for view in delta.cards {
+                    let Some(&card_entity) = client.cards.get(view.id) else { continue; };
+                    let Ok((_, mut transform, mut card)) = card_query.get_mut(card_entity) else { continue; };
+                    let previous = client.positions.insert(view.id, view.position);
+
+                    // face and visibility, a card just frozen shakes
+                    card.hide();
+                    card.face_up = view.face_up;
+                    if view.frozen && !card.frozen {
+                        commands.entity(card_entity).insert(CardAnimation::shake(&transform));
+                    }
+                    card.frozen = view.frozen;
+                    if let Some(face) = view.face {
+                        card.value = face.value;
+                        card.suit = Suit::from_index(face.suit as usize);
+                        if let Some(front) = card_handles.0.get(card.suit.face_index(face.value)) {
+                            card.front_face = front.clone();
+                        }
+                        if !view.face_up {
+                            card.reveal_to(local_player);
+                        }
+                    }
+
+                    // position, movement animations start from the position change
+                    match view.position {
+                        NetPosition::Deck => {
+                            card.position = CardPosition::Deck;
+                            card.owner_id = None;
+                            transform.translation = Vec3::new(layout.width * 0.15, 0.0, 1.0);
+                        }
+                        NetPosition::Hand { seat, slot } => {
+                            let Some(owner) = player_at(seat) else { continue; };
+                            card.position = CardPosition::Hand(owner);
+                            card.owner_id = Some(owner);
+                            // LAN tables play without snaps, every hand keeps the dealt size
+                            let positions = get_player_positions(local_seat(seat), Hand::SIZE, layout.width, layout.height);
+                            if let Some(position) = positions.get(slot) {
+                                transform.translation = *position;
+                            }
+                        }
+                        NetPosition::Graveyard { .. } => {
+                            card.position = CardPosition::Graveyard;
+                        }
+                        NetPosition::Drawn { seat } => {
+                            let Some(owner) = player_at(seat) else { continue; };
+                            card.position = CardPosition::DrawnCard(owner);
+                            card.owner_id = Some(owner);
+                            card.from_deck = matches!(previous, Some(NetPosition::Deck));
+                        }
+                    }
+                }
+
+                // rebuild hands, graveyard and deck from the card positions
+                let mut hands: Vec<Vec<(usize, Entity)>> = vec![Vec::new(); seat_players.len()];
+                let mut graveyard_cards = Vec::new();
+                let mut deck_cards = Vec::new();
+                for (&id, position) in client.positions.iter() {
+                    let Some(&card_entity) = client.cards.get(id) else { continue; };
+                    match *position {
+                        NetPosition::Hand { seat, slot } => {
+                            if let Some(hand) = hands.get_mut(local_seat(seat)) {
+                                hand.push((slot, card_entity));
+                            }
+                        }
+                        NetPosition::Graveyard { index } => graveyard_cards.push((index, card_entity)),
+                        NetPosition::Deck => deck_cards.push(card_entity),
+                        NetPosition::Drawn { .. } => {}
+                    }
+                }
+                for (cards, &(_, _, hand_entity)) in hands.iter_mut().zip(seat_players.iter()) {
+                    cards.sort();
+                    if let Ok(mut hand) = hand_query.get_mut(hand_entity) {
+                        hand.cards = cards.iter().map(|&(_, card_entity)| card_entity).collect();
+                    }
+                }
+                graveyard_cards.sort();
+                if let Ok(mut graveyard) = graveyard_query.single_mut() {
+                    graveyard.cards = graveyard_cards.into_iter().map(|(_, card_entity)| card_entity).collect();
+                }
+                if let Ok(mut deck) = deck_query.single_mut() {
+                    deck.cards_values = deck_cards;
+                }
+
+                client.awaiting_host = false;
+            }
+        }
+    }
+}
+
+// turn clicks and keys into actions for the host
+pub fn client_input(
+    mouse_input: Res<ButtonInput<MouseButton>>,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    (windows, layout): (Query<&Window, With<PrimaryWindow>>, Res<TableLayout>),
+    camera_query: Query<(&Camera, &GlobalTransform)>,
+    mut card_query: Query<(Entity, &mut Transform, &mut Card), With<Card>>,
+    turn: Res<Turn>,
+    viewer: Res<Viewer>,
+    player_query: Query<&Player>,
+    hand_query: Query<&Hand>,
+    graveyard_query: Query<&Graveyard>,
+    mut net_session: ResMut<NetSession>,
+    (ruleset, registry): (Res<Ruleset>, Res<SpecialEffectRegistry>),
+) {
+    let NetSession::Client(client) = &mut *net_session else { return; };
+
+    // only on our turn, one action at a time
+    let Some(local_player) = viewer.0 else { return; };
+    if turn.current_player != local_player || client.awaiting_host { return; }
+
+    let world_pos = cursor_world_position(&mouse_input, &windows, &camera_query);
+    let (own_seat, players) = (client.seat, client.players);
+    let Some(action) = read_action(
+        (&keyboard, &mouse_input, world_pos),
+        &card_query,
+        (&player_query, &hand_query, &graveyard_query),
+        &layout,
+        &turn,
+        (local_player, &ruleset, &registry),
+        &mut client.targeting,
+        |seat| (seat + own_seat) % players.max(1),
+    ) else { return; };
+
+    // the special is spent once it is sent
+    if matches!(action, BotReply::Special { .. }) {
+        let drawn_card = card_query.iter()
+            .find(|(_, _, card)| card.position == CardPosition::DrawnCard(local_player))
+            .map(|(entity, _, _)| entity);
+        if let Some(drawn_entity) = drawn_card && let Ok((_, _, mut card)) = card_query.get_mut(drawn_entity) {
+            card.from_deck = false;
+        }
+    }
+
+    match client.connection.send(&action) {
+        Ok(()) => client.awaiting_host = true,
+        Err(error) => warn!(target: "mygame", "Could not send action to the host: {}", error),
+    }
+}
+
+// auxiliar function - world position of a left click of this frame
+pub fn cursor_world_position(
+    mouse_input: &ButtonInput<MouseButton>,
+    windows: &Query<&Window, With<PrimaryWindow>>,
+    camera_query: &Query<(&Camera, &GlobalTransform)>,
+) -> Option<Vec2> {
+    if !mouse_input.just_pressed(MouseButton::Left) { return None; }
+    let window = windows.single().ok()?;
+    let (camera, camera_transform) = camera_query.single().ok()?;
+    let cursor_pos = window.cursor_position()?;
+    camera.viewport_to_world_2d(camera_transform, cursor_pos).ok()
+}
+
+// auxiliar function - action of the local player from the keys and the click (world position) of this frame
+// special targets are given as host seats, targeting keeps the targets chosen so far
+pub fn read_action(
+    (keyboard, mouse_input, world_pos): (&ButtonInput<KeyCode>, &ButtonInput<MouseButton>, Option<Vec2>),
+    card_query: &Query<(Entity, &mut Transform, &mut Card), With<Card>>,
+    (player_query, hand_query, graveyard_query): (&Query<&Player>, &Query<&Hand>, &Query<&Graveyard>),
+    layout: &TableLayout,
+    turn: &Turn,
+    (local_player, ruleset, registry): (Entity, &Ruleset, &SpecialEffectRegistry),
+    targeting: &mut Option<ClientTargeting>,
+    host_seat: impl Fn(usize) -> usize,
+) -> Option<BotReply> {
+    let drawn_card = card_query.iter()
+        .find(|(_, _, card)| card.position == CardPosition::DrawnCard(local_player))
+        .map(|(entity, _, card)| (entity, card.value, card.from_deck));
+
+    // end the round
+    if keyboard.just_pressed(KeyCode::KeyR) && drawn_card.is_none() && !turn.has_drawn_card {
+        return Some(BotReply::Call);
+    }
+
+    // use the special card drawn from the deck, an effect without targets is sent at once
+    if keyboard.just_pressed(KeyCode::KeyE) && let Some((_, value, true)) = drawn_card {
+        if let Some(effect) = ruleset.special_for(value) {
+            *targeting = ClientTargeting::start(registry.targeting(effect));
+            if targeting.is_none() {
+                return Some(BotReply::Special { target_player: None, target_slot: None, own_slot: None, target_cards: Vec::new() });
+            }
+        }
+    }
+
+    // look and swap: both cards are seen, swap or keep them
+    if matches!(*targeting, Some(ClientTargeting::Decide)) {
+        for (key, swap) in [(KeyCode::KeyY, true), (KeyCode::KeyN, false)] {
+            if keyboard.just_pressed(key) {
+                *targeting = None;
+                return Some(BotReply::Decide { swap });
+            }
+        }
+        return None;
+    }
+
+    // back out of choosing the targets, the drawn card can be swapped or discarded again
+    if targeting.is_some() && cancel_pressed(keyboard, mouse_input) {
+        *targeting = None;
+        return None;
+    }
+
+    let world_pos = world_pos?;
+
+    // host seat and slot of a card in a hand
+    let clicked = detect_card_click(card_query, world_pos);
+    let hand_slot = clicked.and_then(|card_entity| {
+        let Ok((_, _, card)) = card_query.get(card_entity) else { return None; };
+        let CardPosition::Hand(owner) = card.position else { return None; };
+        let player = player_query.get(owner).ok()?;
+        let slot = hand_query.get(player.hand).ok()?.cards.iter().position(|&entity| entity == card_entity)?;
+        Some((owner, host_seat(player.seat), slot))
+    });
+
+    // frozen cards can't take part in a swap
+    let frozen = clicked.is_some_and(|card_entity| card_query.get(card_entity).is_ok_and(|(_, _, card)| card.frozen));
+    let exchanges_cards = drawn_card.and_then(|(_, value, _)| ruleset.special_for(value)).is_some_and(|effect| registry.exchanges_cards(effect));
+
+    let mut action = None;
+    match (targeting.clone(), hand_slot) {
+        // special targets
+        (Some(ClientTargeting::OpponentPlayer), Some((owner, seat, _))) if owner != local_player => {
+            action = Some(BotReply::Special { target_player: Some(seat), target_slot: None, own_slot: None, target_cards: Vec::new() });
+        }
+        (Some(ClientTargeting::OpponentAndOwnCard { target: None }), Some((owner, seat, slot))) if owner != local_player && !frozen => {
+            *targeting = Some(ClientTargeting::OpponentAndOwnCard { target: Some((seat, slot)) });
+        }
+        (Some(ClientTargeting::OpponentAndOwnCard { target: Some((seat, slot)) }), Some((owner, _, own_slot))) if owner == local_player && !frozen => {
+            action = Some(BotReply::Special { target_player: Some(seat), target_slot: Some(slot), own_slot: Some(own_slot), target_cards: Vec::new() });
+        }
+        (Some(ClientTargeting::OwnCard), Some((owner, _, own_slot))) if owner == local_player => {
+            action = Some(BotReply::Special { target_player: None, target_slot: None, own_slot: Some(own_slot), target_cards: Vec::new() });
+        }
+        (Some(ClientTargeting::OpponentCard), Some((owner, seat, slot))) if owner != local_player => {
+            action = Some(BotReply::Special { target_player: Some(seat), target_slot: Some(slot), own_slot: None, target_cards: Vec::new() });
+        }
+        // several cards, one click at a time
+        (Some(ClientTargeting::Several(mut chosen)), Some((owner, seat, slot)))
+            if owner != local_player && !(frozen && exchanges_cards) && !chosen.contains(&Some((seat, slot))) => {
+            if let Some(free) = chosen.iter_mut().find(|card| card.is_none()) {
+                *free = Some((seat, slot));
+            }
+            match chosen.iter().copied().collect::<Option<Vec<_>>>() {
+                Some(target_cards) => action = Some(BotReply::Special { target_player: None, target_slot: None, own_slot: None, target_cards }),
+                None => *targeting = Some(ClientTargeting::Several(chosen)),
+            }
+        }
+        // the drawn card is discarded without using the special
+        (Some(_), _) if clicked.is_some() && clicked == drawn_card.map(|(entity, _, _)| entity) => {
+            *targeting = None;
+            action = Some(BotReply::Discard);
+        }
+        (Some(_), _) => {}
+
+        // draw
+        (None, _) if drawn_card.is_none() => {
+            if detect_deck_click(world_pos, layout) {
+                action = Some(BotReply::Draw { from: DrawSource::Deck });
+            } else if detect_graveyard_click(world_pos, layout) && graveyard_query.single().is_ok_and(|graveyard| !graveyard.cards.is_empty()) {
+                action = Some(BotReply::Draw { from: DrawSource::Graveyard });
+            }
+        }
+
+        // discard the drawn card or swap it with a hand card
+        (None, Some((owner, _, slot))) if owner == local_player => {
+            action = Some(BotReply::Swap { slot });
+        }
+        (None, _) => {
+            if clicked.is_some() && clicked == drawn_card.map(|(entity, _, _)| entity) {
+                action = Some(BotReply::Discard);
+            }
+        }
+    }
+
+    // the targets are chosen, an effect asking for a decision goes on with it
+    if matches!(action, Some(BotReply::Special { .. })) {
+        let decide = drawn_card.and_then(|(_, value, _)| ruleset.special_for(value)).is_some_and(|effect| registry.asks_decision(effect));
+        *targeting = decide.then_some(ClientTargeting::Decide);
+    }
+    action
+}
+
+// chat of the other seats, the host seats are turned into local seats
+pub fn receive_client_chat(
+    net_session: Res<NetSession>,
+    mut chat_messages: MessageWriter<ChatMessage>,
+) {
+    let NetSession::Client(client) = &*net_session else { return; };
+    let players = client.players.max(1);
+
+    while let Ok(mut message) = client.connection.try_recv_chat() {
+        message.seat = (message.seat + players - client.seat % players) % players;
+        chat_messages.write(message);
+    }
+}
+
+// chat of this client goes to the host, it is not sent back
+pub fn send_client_chat(
+    net_session: Res<NetSession>,
+    mut chat_messages: MessageReader<ChatMessage>,
+) {
+    let NetSession::Client(client) = &*net_session else { return; };
+
+    for message in chat_messages.read() {
+        if message.seat != 0 || client.spectator || client.reconnect_until.is_some() { continue; }
+        if let Err(error) = client.connection.send(&ChatLine::Chat(message.clone())) {
+            warn!(target: "mygame", "Could not send chat to the host: {}", error);
+        }
+    }
+}
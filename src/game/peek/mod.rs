@@ -0,0 +1,30 @@
+use bevy::prelude::*;
+use crate::game::gamestate::AppState;
+
+pub mod resource;
+mod system;
+
+use resource::PeekPhase;
+use system::{start_peek_phase, local_peek_input, finish_peeks};
+
+// before the first turn every player looks at some of their cards, and has to remember them
+pub struct PeekPlugin;
+
+impl Plugin for PeekPlugin {
+    fn build(&self, app: &mut App) {
+        app
+        .init_resource::<PeekPhase>()
+        .add_systems(OnEnter(AppState::PlayerTurn), start_peek_phase)
+        .add_systems(
+            Update,
+            (local_peek_input, finish_peeks)
+            .chain()
+            .run_if(in_state(AppState::PlayerTurn).and(is_peeking))
+        );
+    }
+}
+
+// turns wait until every player peeked
+pub fn is_peeking(peek_phase: Res<PeekPhase>) -> bool {
+    peek_phase.active
+}
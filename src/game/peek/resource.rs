@@ -0,0 +1,60 @@
+use bevy::prelude::*;
+
+// start of the round: each player chooses some of their cards, they are shown for a few seconds and then hidden again
+#[derive(Resource, Default)]
+pub struct PeekPhase {
+    pub active: bool,
+    pub count: usize, // cards each player peeks at
+    pub players: Vec<PeekingPlayer>, // players whose peek is played on this machine, in seat order
+}
+
+pub struct PeekingPlayer {
+    pub player: Entity,
+    pub local: bool, // chooses with clicks on this screen
+    pub asked: bool, // bots and LAN seats were sent the peek message
+    pub picked: Vec<Entity>,
+    pub deadline: Option<f32>, // cards still to choose are chosen at random after this time
+    pub shown_until: Option<f32>, // every card is chosen, they are up until this time
+    pub done: bool,
+}
+
+impl PeekPhase {
+    pub const CHOOSE_SECONDS: f32 = 20.0;
+    pub const SHOW_SECONDS: f32 = 3.0;
+
+    // local players peek one at a time, so hot-seat players can pass the screen
+    pub fn local_peeker(&self) -> Option<Entity> {
+        self.players.iter()
+            .find(|peeking| peeking.local && !peeking.done)
+            .map(|peeking| peeking.player)
+    }
+
+    pub fn get_mut(&mut self, player: Entity) -> Option<&mut PeekingPlayer> {
+        self.players.iter_mut().find(|peeking| peeking.player == player)
+    }
+}
+
+impl PeekingPlayer {
+    pub fn new(player: Entity, local: bool) -> Self {
+        Self { player, local, asked: false, picked: Vec::new(), deadline: None, shown_until: None, done: false }
+    }
+
+    // still choosing cards
+    pub fn is_choosing(&self) -> bool {
+        !self.done && self.shown_until.is_none()
+    }
+
+    // choose one more card, false when it was already chosen
+    pub fn pick(&mut self, card: Entity) -> bool {
+        if self.picked.contains(&card) { return false; }
+        self.picked.push(card);
+        true
+    }
+
+    // the cards are shown once every card is chosen (or the hand has no more cards)
+    pub fn start_showing(&mut self, count: usize, hand_size: usize, now: f32) {
+        if self.picked.len() >= count.min(hand_size) {
+            self.shown_until = Some(now + PeekPhase::SHOW_SECONDS);
+        }
+    }
+}
@@ -0,0 +1,144 @@
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+use rand::seq::IteratorRandom;
+use crate::game::peek::resource::{PeekPhase, PeekingPlayer};
+use crate::game::rules::resource::Ruleset;
+use crate::game::card::component::{Card, CardPosition};
+use crate::game::card::system::detect_card_click;
+use crate::game::{hand::component::Hand, player::component::Player};
+use crate::game::match_config::resource::{MatchConfig, Viewer};
+use crate::game::net::resource::NetSession;
+use crate::game::net::cursor_world_position;
+use crate::game::bot::protocol::BotReply;
+
+// every round starts with the peek, a LAN client or a lockstep peer only plays the one of its own seat
+pub fn start_peek_phase(
+    mut peek_phase: ResMut<PeekPhase>,
+    ruleset: Res<Ruleset>,
+    match_config: Res<MatchConfig>,
+    net_session: Res<NetSession>,
+    player_query: Query<(Entity, &Player)>,
+) {
+    let only_local = net_session.is_client() || net_session.lockstep().is_some();
+    let mut players: Vec<(Entity, &Player)> = player_query.iter()
+        .filter(|(_, player)| !only_local || player.is_local_player)
+        .collect();
+    players.sort_by_key(|(_, player)| net_session.host_seat(player.seat));
+
+    peek_phase.count = ruleset.peek_count;
+    peek_phase.players = players.into_iter()
+        .map(|(entity, player)| PeekingPlayer::new(entity, player.is_local_player && !match_config.spectator))
+        .collect();
+    peek_phase.active = peek_phase.count > 0 && !peek_phase.players.is_empty();
+    if peek_phase.active {
+        info!(target: "mygame", "Peek phase: each player chooses {} cards", peek_phase.count);
+    }
+}
+
+// the local player whose peek it is clicks the cards of their hand
+pub fn local_peek_input(
+    mouse_input: Res<ButtonInput<MouseButton>>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    camera_query: Query<(&Camera, &GlobalTransform)>,
+    mut card_query: Query<(Entity, &mut Transform, &mut Card), With<Card>>,
+    player_query: Query<&Player>,
+    hand_query: Query<&Hand>,
+    viewer: Res<Viewer>,
+    mut peek_phase: ResMut<PeekPhase>,
+    net_session: Res<NetSession>,
+    time: Res<Time>,
+) {
+    let now = time.elapsed_secs();
+    let count = peek_phase.count;
+    let Some(peeker) = peek_phase.local_peeker() else { return; };
+    if viewer.0 != Some(peeker) { return; }
+    let Some(peeking) = peek_phase.get_mut(peeker) else { return; };
+
+    // the time to choose starts once the player has the screen
+    let deadline = *peeking.deadline.get_or_insert(now + PeekPhase::CHOOSE_SECONDS);
+    if !peeking.is_choosing() || now >= deadline { return; }
+
+    let Some(world_pos) = cursor_world_position(&mouse_input, &windows, &camera_query) else { return; };
+    let Some(card_entity) = detect_card_click(&card_query, world_pos) else { return; };
+    let Ok((_, _, card)) = card_query.get(card_entity) else { return; };
+    if card.position != CardPosition::Hand(peeker) { return; }
+
+    let Ok(player) = player_query.get(peeker) else { return; };
+    let Ok(hand) = hand_query.get(player.hand) else { return; };
+    let Some(slot) = hand.cards.iter().position(|&entity| entity == card_entity) else { return; };
+    if !peeking.pick(card_entity) { return; }
+
+    // a LAN client only learns the face from the host
+    match &*net_session {
+        NetSession::Client(client) => {
+            if let Err(error) = client.connection.send(&BotReply::Peek { slot }) {
+                warn!(target: "mygame", "Could not send peek to the host: {}", error);
+            }
+        }
+        _ => {
+            if let Ok((_, _, mut card)) = card_query.get_mut(card_entity) {
+                card.reveal_to(peeker);
+            }
+        }
+    }
+    peeking.start_showing(count, hand.cards.len(), now);
+}
+
+// random cards for the players out of time, hide the cards once they were shown, end the phase when everyone peeked
+pub fn finish_peeks(
+    mut peek_phase: ResMut<PeekPhase>,
+    mut card_query: Query<&mut Card>,
+    player_query: Query<&Player>,
+    hand_query: Query<&Hand>,
+    net_session: Res<NetSession>,
+    ruleset: Res<Ruleset>,
+    time: Res<Time>,
+) {
+    let now = time.elapsed_secs();
+    let count = peek_phase.count;
+    let mirror = net_session.is_client();
+
+    for peeking in peek_phase.players.iter_mut().filter(|peeking| !peeking.done) {
+        // players that are not on this screen start choosing with the phase
+        if !peeking.local {
+            peeking.deadline.get_or_insert(now + PeekPhase::CHOOSE_SECONDS);
+        }
+
+        if peeking.shown_until.is_none() && peeking.deadline.is_some_and(|deadline| now >= deadline) {
+            // the host chooses for a LAN client that ran out of time
+            if mirror {
+                peeking.done = true;
+                continue;
+            }
+            let Ok(player) = player_query.get(peeking.player) else { continue; };
+            let Ok(hand) = hand_query.get(player.hand) else { continue; };
+            let missing = count.saturating_sub(peeking.picked.len());
+            let unpicked = hand.cards.iter().filter(|card| !peeking.picked.contains(card)).copied();
+            for card_entity in unpicked.choose_multiple(&mut rand::rng(), missing) {
+                if let Ok(mut card) = card_query.get_mut(card_entity) {
+                    card.reveal_to(peeking.player);
+                }
+                peeking.picked.push(card_entity);
+            }
+            info!(target: "mygame", "{} ran out of time to peek, random cards were chosen", player.name);
+            peeking.shown_until = Some(now + PeekPhase::SHOW_SECONDS);
+        }
+
+        // the host hides the cards of a LAN client, with the assist they stay visible
+        if peeking.shown_until.is_some_and(|shown_until| now >= shown_until) {
+            if !mirror && !ruleset.assist {
+                for &card_entity in &peeking.picked {
+                    if let Ok(mut card) = card_query.get_mut(card_entity) {
+                        card.forget(peeking.player);
+                    }
+                }
+            }
+            peeking.done = true;
+        }
+    }
+
+    if peek_phase.players.iter().all(|peeking| peeking.done) {
+        peek_phase.active = false;
+        info!(target: "mygame", "Peek phase over");
+    }
+}
@@ -4,5 +4,6 @@ use bevy::prelude::{Component, Entity};
 pub struct Player {
     pub name: String,
     pub hand: Entity,
-    pub is_local_player: bool
-}
\ No newline at end of file
+    pub is_local_player: bool,
+    pub seat: usize, // position around the table
+}
@@ -2,36 +2,111 @@ use bevy::prelude::*;
 use crate::game::player::component::Player;
 use crate::game::hand::component::Hand;
 use crate::game::gamestate::GameEntity;
-use crate::game::ai::component::{AIDifficulty, AIMemory, AIPlayer, AIState};
+use crate::game::ai::component::{AIDifficulty, AIMemory, AIPlayer, AIState, AIThinkSettings};
+use std::collections::HashMap;
+use crate::game::match_config::resource::{MatchConfig, SeatKind, Viewer};
+use crate::game::bot::component::{BotLink, BotPlayer, BotProcess, BotState};
+use crate::game::bot::protocol::{GameToBot, PROTOCOL_VERSION};
+use crate::game::net::resource::{NetSession, RemoteLink, LockstepLink};
+use crate::game::net::component::StandIn;
 
-pub fn spawn_player(mut commands: Commands) {
-    let player_names = ["Player 1", "Player 2"];
+pub fn spawn_player(mut commands: Commands, match_config: Res<MatchConfig>, mut viewer: ResMut<Viewer>, net_session: Res<NetSession>, ai_think: Res<AIThinkSettings>) {
+    // cards are rendered from the local player's point of view
+    viewer.0 = None;
 
-    for (i, name) in player_names.iter().enumerate() {
+    for (i, seat) in match_config.seats.iter().enumerate() {
         // create hand
         let hand = commands.spawn((
             Hand { cards: Vec::new() },
             GameEntity,
         )).id();
 
+        // on a LAN client the seats are rotated, names follow the host seats
+        let name = match &*net_session {
+            NetSession::Client(client) => format!("Player {}", client.host_seat(i) + 1),
+            _ => format!("Player {}", i + 1),
+        };
+
         let player_entity = commands.spawn((
             Player {
-                name: name.to_string(),
+                name,
                 hand: hand,
-                is_local_player: i == 0
+                is_local_player: *seat == SeatKind::Human,
+                seat: i,
             },
             GameEntity,
         )).id();
+
+        // in hot-seat the view is handed over behind the pass curtain
+        if *seat == SeatKind::Human && viewer.0.is_none() && !match_config.hot_seat {
+            viewer.0 = Some(player_entity);
+        }
         
-        // add AIPlayer to second player
-        if i == 1 {
+        // lockstep: every seat, the local one too, plays the actions queued for it
+        if let Some(lockstep) = net_session.lockstep() {
+            commands.entity(player_entity).insert((
+                BotPlayer { link: Box::new(LockstepLink(lockstep.queue(net_session.host_seat(i)))), known_cards: HashMap::new() },
+                BotState::Idle,
+            ));
+            continue;
+        }
+
+        // start the external bot, if it fails the built-in AI plays the seat
+        let think_time = match seat {
+            SeatKind::Human => None,
+            SeatKind::AI(think_time) => Some(*think_time),
+            SeatKind::Bot(command) => match BotProcess::spawn(command) {
+                Ok(mut process) => {
+                    let hello = GameToBot::Hello { protocol: PROTOCOL_VERSION, seat: i, players: match_config.seats.len() };
+                    if let Err(error) = process.send(&hello) {
+                        warn!(target: "mygame", "Could not greet bot {:?}: {}", command, error);
+                    }
+                    commands.entity(player_entity).insert((
+                        BotPlayer { link: Box::new(process), known_cards: HashMap::new() },
+                        BotState::Idle,
+                    ));
+                    info!(target: "mygame", "Bot {:?} plays seat {}", command, i);
+                    None
+                }
+                Err(error) => {
+                    warn!(target: "mygame", "Could not start bot {:?}: {}, using AI", command, error);
+                    Some(ai_think.for_seat(i))
+                }
+            },
+            // the host plays remote seats with the actions of the client, like a bot
+            SeatKind::Remote(peer_index) => match &*net_session {
+                NetSession::Host(host) => match host.peers.get(*peer_index).filter(|peer| peer.connected) {
+                    Some(peer) => {
+                        commands.entity(player_entity).insert((
+                            BotPlayer { link: Box::new(RemoteLink(peer.connection.clone())), known_cards: HashMap::new() },
+                            BotState::Idle,
+                        ));
+                        info!(target: "mygame", "{} plays seat {}", peer.name, i);
+                        None
+                    }
+                    None => {
+                        // a dropped player can still come back to the seat
+                        if host.peers.get(*peer_index).is_some_and(|peer| peer.grace_until.is_some()) {
+                            commands.entity(player_entity).insert(StandIn);
+                        }
+                        warn!(target: "mygame", "Seat {} disconnected, using AI", i);
+                        Some(ai_think.for_seat(i))
+                    }
+                },
+                _ => None,
+            },
+        };
+
+        // add AIPlayer to AI seats
+        if let Some(think_time) = think_time {
             commands.entity(player_entity).insert((
                 AIPlayer {
                     difficulty: AIDifficulty::Hard,
+                    think_time,
                 },
                 AIMemory::default(),
                 AIState::Idle,
             ));
         }
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,21 @@
+use bevy::prelude::*;
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+
+// random source of the rules (deck order, shuffle and reveal specials)
+// lockstep peers seed it with the same value every round, so they deal and shuffle alike
+#[derive(Resource)]
+pub struct GameRng(pub StdRng);
+
+impl GameRng {
+    pub fn seed(&mut self, seed: u64) {
+        self.0 = StdRng::seed_from_u64(seed);
+    }
+}
+
+// for default, a new random seed every game
+impl Default for GameRng {
+    fn default() -> Self {
+        Self(StdRng::seed_from_u64(rand::random()))
+    }
+}
@@ -0,0 +1,5 @@
+use bevy::prelude::*;
+
+// start the next round without the N key (LAN clients follow the host)
+#[derive(Event, Message)]
+pub struct StartNewRound;
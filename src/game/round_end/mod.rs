@@ -2,13 +2,20 @@ use bevy::prelude::*;
 use crate::game::gamestate::AppState;
 
 pub mod system;
+pub mod event;
+pub mod resource;
 use system::{reveal_all_cards, calculate_scores, prepare_new_round};
+use event::StartNewRound;
+use resource::{RoundScores, RoundCaller};
 
 pub struct RoundEndPlugin;
 
 impl Plugin for RoundEndPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(OnEnter(AppState::RoundEnd), (reveal_all_cards, calculate_scores).chain())
+        app.add_message::<StartNewRound>()
+        .init_resource::<RoundScores>()
+        .init_resource::<RoundCaller>()
+        .add_systems(OnEnter(AppState::RoundEnd), (reveal_all_cards, calculate_scores).chain())
         .add_systems(Update, prepare_new_round.run_if(in_state(AppState::RoundEnd)));
     }
 }
\ No newline at end of file
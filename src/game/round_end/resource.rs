@@ -0,0 +1,124 @@
+use bevy::prelude::*;
+use std::collections::BTreeMap;
+use crate::game::card::component::Card;
+use crate::game::rules::resource::{Ruleset, TieBreak};
+
+// points of one player at the end of the round, with the rules that changed them
+#[derive(Clone, Debug)]
+pub struct PlayerScore {
+    pub player: Entity,
+    pub seat: usize,
+    pub name: String,
+    pub card_count: usize,
+    pub cards: i32, // sum of the card points
+    pub adjustments: Vec<(&'static str, i32)>, // pairs, caller penalty or bonus
+    pub total: i32,
+    pub caller: bool,
+    pub winner: bool,
+}
+
+// scores of the last round, read by the results panel, the AI chat and the server log
+#[derive(Resource, Default)]
+pub struct RoundScores {
+    pub players: Vec<PlayerScore>,
+}
+
+// player who ended the round, none when nobody at the table called it
+#[derive(Resource, Default)]
+pub struct RoundCaller(pub Option<Entity>);
+
+impl RoundScores {
+    // score every hand with the rules of the table, the caller is the player who ended the round
+    pub fn compute(ruleset: &Ruleset, caller: Option<Entity>, hands: Vec<(Entity, usize, String, Vec<&Card>)>) -> Self {
+        let mut players: Vec<PlayerScore> = hands.into_iter().map(|(player, seat, name, cards)| {
+            let mut adjustments = Vec::new();
+            if ruleset.pairs_cancel {
+                let cancelled = cancelled_pairs(ruleset, &cards);
+                if cancelled != 0 {
+                    adjustments.push(("pairs", -cancelled));
+                }
+            }
+            PlayerScore {
+                player,
+                seat,
+                name,
+                card_count: cards.len(),
+                cards: cards.iter().map(|card| ruleset.card_points(card)).sum(),
+                adjustments,
+                total: 0,
+                caller: caller == Some(player),
+                winner: false,
+            }
+        }).collect();
+        players.sort_by_key(|score| score.seat);
+        for score in players.iter_mut() {
+            score.total = score.cards + score.adjustments.iter().map(|(_, points)| points).sum::<i32>();
+        }
+
+        // the caller is strictly lowest or not, before the caller rules change the totals
+        let strictly_lowest = players.iter().find(|score| score.caller).is_some_and(|caller| {
+            players.iter().all(|score| score.caller || score.total > caller.total)
+        });
+        if let Some(caller) = players.iter_mut().find(|score| score.caller) {
+            if strictly_lowest && ruleset.caller_bonus > 0 {
+                caller.adjustments.push(("caller bonus", -(ruleset.caller_bonus as i32)));
+                caller.total -= ruleset.caller_bonus as i32;
+            } else if !strictly_lowest && ruleset.caller_penalty > 0 {
+                caller.adjustments.push(("caller penalty", ruleset.caller_penalty as i32));
+                caller.total += ruleset.caller_penalty as i32;
+            }
+        }
+
+        // lowest total wins, ties are settled by the tie-break rule
+        let Some(best) = players.iter().map(|score| score.total).min() else { return Self { players }; };
+        let mut tied: Vec<usize> = (0..players.len()).filter(|&index| players[index].total == best).collect();
+        if tied.len() > 1 {
+            match ruleset.tie_break {
+                TieBreak::Shared => {}
+                TieBreak::FewerCards => {
+                    let fewest = tied.iter().map(|&index| players[index].card_count).min().unwrap_or_default();
+                    tied.retain(|&index| players[index].card_count == fewest);
+                }
+                TieBreak::CallerLoses => tied.retain(|&index| !players[index].caller),
+            }
+        }
+        for index in tied {
+            players[index].winner = true;
+        }
+
+        Self { players }
+    }
+
+    pub fn winners(&self) -> impl Iterator<Item = &PlayerScore> {
+        self.players.iter().filter(|score| score.winner)
+    }
+
+    pub fn is_winner(&self, player: Entity) -> bool {
+        self.winners().any(|score| score.player == player)
+    }
+}
+
+impl PlayerScore {
+    // one line of the results, e.g. "Ana: 14 cards, -6 pairs, +5 caller penalty = 13"
+    pub fn breakdown(&self) -> String {
+        let mut line = format!("{}: {} cards", self.name, self.cards);
+        for (rule, points) in &self.adjustments {
+            line.push_str(&format!(", {:+} {}", points, rule));
+        }
+        line.push_str(&format!(" = {}", self.total));
+        line
+    }
+}
+
+// auxiliar function - points taken away by the pairs of equal values in a hand, the highest cards of a value pair first
+// cards worth less than zero (jokers) are kept, cancelling them would raise the score
+fn cancelled_pairs(ruleset: &Ruleset, cards: &[&Card]) -> i32 {
+    let mut by_value: BTreeMap<u8, Vec<i32>> = BTreeMap::new();
+    for card in cards {
+        by_value.entry(card.value).or_default().push(ruleset.card_points(card).max(0));
+    }
+    by_value.into_values().map(|mut points| {
+        points.sort_unstable_by(|a, b| b.cmp(a));
+        points.iter().take(points.len() / 2 * 2).sum::<i32>()
+    }).sum()
+}
@@ -1,6 +1,10 @@
 use bevy::prelude::*;
 use crate::game::{card::component::{Card, CardPosition}, player::component::Player, hand::component::Hand, gamestate::{AppState, GameEntity}, turn_player::component::Turn, graveyard::component::Graveyard, deck::component::Deck};
 use crate::game::card::component::{CardBack, CardHandles};
+use crate::game::round_end::event::StartNewRound;
+use crate::game::net::resource::NetSession;
+use crate::game::round_end::resource::{RoundScores, RoundCaller};
+use crate::game::rules::resource::Ruleset;
 
 // Reveal all cards
 pub fn reveal_all_cards(
@@ -20,36 +24,33 @@ pub fn reveal_all_cards(
     }
 }
 
-// Calculate scores
+// Calculate scores, with the scoring rules of the table
 pub fn calculate_scores(
     card_query: Query<&Card>,
     player_query: Query<(Entity, &Player)>,
     hand_query: Query<&Hand>,
+    round_caller: Res<RoundCaller>,
+    ruleset: Res<Ruleset>,
+    mut round_scores: ResMut<RoundScores>,
 ) {
     info!(target: "mygame", "=== ROUND END SCORES ===");
-    
-    let mut scores = Vec::new();
-    
-    for (player_entity, player) in player_query.iter() {
-        if let Ok(hand) = hand_query.get(player.hand) {
-            let mut total_score = 0; // score default
-            
-            for &card_entity in &hand.cards {
-                if let Ok(card) = card_query.get(card_entity) {
-                    total_score += card.value as u32; // sum card value
-                }
-            }
-            
-            scores.push((player_entity, player.name.clone(), total_score)); // push final score
-            info!(target: "mygame", "Player {}: {} points", player.name, total_score);
-        }
+
+    let hands = player_query.iter().filter_map(|(player_entity, player)| {
+        let hand = hand_query.get(player.hand).ok()?;
+        let cards = hand.cards.iter().filter_map(|&card_entity| card_query.get(card_entity).ok()).collect();
+        Some((player_entity, player.seat, player.name.clone(), cards))
+    }).collect();
+
+    *round_scores = RoundScores::compute(&ruleset, round_caller.0, hands);
+    for score in &round_scores.players {
+        info!(target: "mygame", "Player {}", score.breakdown());
     }
-    
+
     // search winner
-    if let Some((_, winner_name, winner_score)) = scores.iter().min_by_key(|(_, _, score)| *score) {
-        info!(target: "mygame", "WINNER: {} with {} points!", winner_name, winner_score);
+    for winner in round_scores.winners() {
+        info!(target: "mygame", "WINNER: {} with {} points!", winner.name, winner.total);
     }
-    
+
     info!(target: "mygame", "Press N for new round");
 }
 
@@ -64,8 +65,12 @@ pub fn prepare_new_round(
     graveyard_query: Query<Entity, With<Graveyard>>,
     mut hand_query: Query<&mut Hand>,
     player_query: Query<Entity, With<Player>>,
+    mut start_messages: MessageReader<StartNewRound>,
+    net_session: Res<NetSession>,
 ) {
-    if keyboard.just_pressed(KeyCode::KeyN) {
+    // LAN clients wait for the host to start the next round
+    let key_pressed = keyboard.just_pressed(KeyCode::KeyN) && !net_session.is_client();
+    if key_pressed || start_messages.read().count() > 0 {
 
         // reset player hands
         for mut hand in hand_query.iter_mut() {
@@ -92,6 +97,7 @@ pub fn prepare_new_round(
         commands.remove_resource::<CardBack>();
 
         turn.has_drawn_card = false;
+        commands.insert_resource(RoundCaller::default());
         next_state.set(AppState::Setup);
         info!(target: "mygame", "Starting new round...");
     }
@@ -0,0 +1,14 @@
+use bevy::prelude::*;
+
+pub mod resource;
+
+use resource::Ruleset;
+
+// rule variants chosen before the match, the host's ones on a LAN table
+pub struct RulesPlugin;
+
+impl Plugin for RulesPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Ruleset>();
+    }
+}
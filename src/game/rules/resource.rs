@@ -0,0 +1,313 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use crate::game::special_cards::resource::{SpecialEffect, SpecialEffectRegistry};
+use crate::game::card::component::{Card, Suit};
+use crate::game::graveyard::component::Graveyard;
+use crate::game::deck::resource::DeckKind;
+
+// rules of the match, chosen in the rules panel and sent to LAN clients in the welcome
+#[derive(Resource, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(default)]
+pub struct Ruleset {
+    pub peek_count: usize, // cards each player looks at before the first turn
+    pub assist: bool, // peeked and revealed cards stay visible, instead of the classic memory game
+    pub specials: BTreeMap<u8, SpecialEffect>, // effect of each special card value, when drawn from the deck
+    pub snap: bool, // any player can throw a hand card matching the graveyard top, offline games only
+    pub caller_penalty: u32, // points added to the player who ended the round when they are not strictly lowest
+    pub caller_bonus: u32, // points taken from the player who ended the round when they are strictly lowest
+    pub king_of_golds_zero: bool, // the 12 of golds counts as 0 points
+    pub pairs_cancel: bool, // two cards of the same value in a hand count 0 points
+    pub tie_break: TieBreak,
+    pub graveyard_must_swap: bool, // a card taken from the graveyard goes into the hand, it can't be discarded again
+    pub no_take_back: bool, // a player can't draw from the graveyard the card they just discarded
+    pub deck: DeckKind,
+    pub deck_copies: usize, // decks shuffled together, for larger tables
+    pub joker_points: i32, // points of a joker at the end of the round, 0 or -1
+}
+
+// who wins when several players have the lowest score
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Default)]
+pub enum TieBreak {
+    #[default]
+    Shared, // every tied player wins
+    FewerCards, // the tied player with the fewest cards in hand wins
+    CallerLoses, // the player who ended the round never wins a tie
+}
+
+impl TieBreak {
+    pub const ALL: [TieBreak; 3] = [TieBreak::Shared, TieBreak::FewerCards, TieBreak::CallerLoses];
+
+    // name on the server command line
+    pub fn name(&self) -> &'static str {
+        match self {
+            TieBreak::Shared => "shared",
+            TieBreak::FewerCards => "fewer_cards",
+            TieBreak::CallerLoses => "caller_loses",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|tie_break| tie_break.name() == name)
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            TieBreak::Shared => "SHARED",
+            TieBreak::FewerCards => "FEWER CARDS",
+            TieBreak::CallerLoses => "CALLER LOSES",
+        }
+    }
+}
+
+impl Default for Ruleset {
+    fn default() -> Self {
+        Self {
+            peek_count: 2,
+            assist: false,
+            specials: BTreeMap::from([(7, SpecialEffect::SWAP), (9, SpecialEffect::REVEAL), (11, SpecialEffect::SHUFFLE)]),
+            snap: false,
+            caller_penalty: 0,
+            caller_bonus: 0,
+            king_of_golds_zero: false,
+            pairs_cancel: false,
+            tie_break: TieBreak::Shared,
+            graveyard_must_swap: false,
+            no_take_back: false,
+            deck: DeckKind::Spanish48,
+            deck_copies: 1,
+            joker_points: 0,
+        }
+    }
+}
+
+// options shown in the rules panel, each button cycles one of them
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum RuleOption {
+    PeekCount,
+    Assist,
+    Special(u8), // effect of a card value
+    Snap,
+    CallerPenalty,
+    CallerBonus,
+    KingOfGolds,
+    PairsCancel,
+    TieBreak,
+    GraveyardSwap,
+    TakeBack,
+    Deck,
+    DeckCopies,
+    JokerPoints,
+}
+
+impl RuleOption {
+    pub const ALL: [RuleOption; 19] = [
+        RuleOption::PeekCount,
+        RuleOption::Assist,
+        RuleOption::Special(7),
+        RuleOption::Special(8),
+        RuleOption::Special(9),
+        RuleOption::Special(10),
+        RuleOption::Special(11),
+        RuleOption::Special(12),
+        RuleOption::Snap,
+        RuleOption::CallerPenalty,
+        RuleOption::CallerBonus,
+        RuleOption::KingOfGolds,
+        RuleOption::PairsCancel,
+        RuleOption::TieBreak,
+        RuleOption::GraveyardSwap,
+        RuleOption::TakeBack,
+        RuleOption::Deck,
+        RuleOption::DeckCopies,
+        RuleOption::JokerPoints,
+    ];
+
+    // snaps are played out of turn, LAN tables only send the actions of the current player
+    pub fn on_lan(&self) -> bool {
+        !matches!(self, RuleOption::Snap)
+    }
+}
+
+impl Ruleset {
+    pub const MAX_PEEK: usize = 4;
+    pub const CALLER_POINTS: [u32; 3] = [0, 5, 10]; // choices of the caller penalty and bonus
+    pub const DECK_COPIES: [usize; 2] = [1, 2];
+    pub const JOKER_POINTS: [i32; 2] = [0, -1];
+
+    // move an option to its next value
+    pub fn cycle(&mut self, option: RuleOption, registry: &SpecialEffectRegistry) {
+        match option {
+            RuleOption::PeekCount => self.peek_count = (self.peek_count + 1) % (Self::MAX_PEEK + 1),
+            RuleOption::Assist => self.assist = !self.assist,
+            RuleOption::Snap => self.snap = !self.snap,
+            RuleOption::CallerPenalty => self.caller_penalty = next_in(&Self::CALLER_POINTS, self.caller_penalty),
+            RuleOption::CallerBonus => self.caller_bonus = next_in(&Self::CALLER_POINTS, self.caller_bonus),
+            RuleOption::KingOfGolds => self.king_of_golds_zero = !self.king_of_golds_zero,
+            RuleOption::PairsCancel => self.pairs_cancel = !self.pairs_cancel,
+            RuleOption::TieBreak => self.tie_break = next_in(&TieBreak::ALL, self.tie_break),
+            RuleOption::GraveyardSwap => self.graveyard_must_swap = !self.graveyard_must_swap,
+            RuleOption::TakeBack => self.no_take_back = !self.no_take_back,
+            RuleOption::Deck => {
+                self.deck = next_in(&DeckKind::ALL, self.deck);
+                self.move_specials_into_deck();
+            }
+            RuleOption::DeckCopies => self.deck_copies = next_in(&Self::DECK_COPIES, self.deck_copies),
+            RuleOption::JokerPoints => self.joker_points = next_in(&Self::JOKER_POINTS, self.joker_points),
+            // no effect, then every effect in turn
+            RuleOption::Special(value) => {
+                let mut effects = registry.effects();
+                let next = match self.specials.get(&value) {
+                    None => effects.next(),
+                    Some(effect) => effects.skip_while(|other| other != effect).nth(1),
+                };
+                match next {
+                    Some(effect) => self.specials.insert(value, effect),
+                    None => self.specials.remove(&value),
+                };
+            }
+        }
+    }
+
+    pub fn label(&self, option: RuleOption, registry: &SpecialEffectRegistry) -> String {
+        match option {
+            RuleOption::PeekCount => format!("PEEK AT START: {} CARDS", self.peek_count),
+            RuleOption::Assist => format!("MEMORY: {}", if self.assist { "ASSIST" } else { "CLASSIC" }),
+            RuleOption::Special(value) => format!("{}: {}", value, self.special_for(value).map_or("NO EFFECT", |effect| registry.label(effect))),
+            RuleOption::Snap => format!("SNAP: {}", if self.snap { "ON" } else { "OFF" }),
+            RuleOption::CallerPenalty => match self.caller_penalty {
+                0 => String::from("CALLER PENALTY: OFF"),
+                points => format!("CALLER PENALTY: +{}", points),
+            },
+            RuleOption::CallerBonus => match self.caller_bonus {
+                0 => String::from("CALLER BONUS: OFF"),
+                points => format!("CALLER BONUS: -{}", points),
+            },
+            RuleOption::KingOfGolds => format!("12 OF GOLDS: {} POINTS", if self.king_of_golds_zero { 0 } else { 12 }),
+            RuleOption::PairsCancel => format!("PAIRS CANCEL: {}", if self.pairs_cancel { "ON" } else { "OFF" }),
+            RuleOption::TieBreak => format!("TIE: {}", self.tie_break.label()),
+            RuleOption::GraveyardSwap => format!("GRAVEYARD CARD: {}", if self.graveyard_must_swap { "MUST SWAP" } else { "SWAP OR DISCARD" }),
+            RuleOption::TakeBack => format!("TAKE BACK OWN DISCARD: {}", if self.no_take_back { "NO" } else { "YES" }),
+            RuleOption::Deck => format!("DECK: {}", self.deck.label()),
+            RuleOption::DeckCopies => format!("DECKS SHUFFLED: {}", self.deck_copies),
+            RuleOption::JokerPoints => format!("JOKER: {} POINTS", self.joker_points),
+        }
+    }
+
+    // options that apply to the deck in play, a special needs cards of its value
+    pub fn offers(&self, option: RuleOption) -> bool {
+        match option {
+            RuleOption::Special(value) => self.deck.definition().has_value(value),
+            _ => true,
+        }
+    }
+
+    // special values with no card in the deck in play, their effects never happen
+    pub fn specials_off_deck(&self) -> Vec<(u8, SpecialEffect)> {
+        self.specials.iter()
+            .filter(|(value, _)| !self.deck.definition().has_value(**value))
+            .map(|(&value, &effect)| (value, effect))
+            .collect()
+    }
+
+    // auxiliar function - a deck without some values keeps their effects on the free special values it has
+    fn move_specials_into_deck(&mut self) {
+        for (value, effect) in self.specials_off_deck() {
+            let free_value = RuleOption::ALL.iter()
+                .filter_map(|option| match option { RuleOption::Special(value) => Some(*value), _ => None })
+                .find(|&other| self.deck.definition().has_value(other) && !self.specials.contains_key(&other));
+            if let Some(free_value) = free_value {
+                self.specials.remove(&value);
+                self.specials.insert(free_value, effect);
+                info!(target: "mygame", "No {} in the {} deck, {} moves to the {}", value, self.deck.name(), effect.name(), free_value);
+            }
+        }
+    }
+
+    // effect of a card drawn from the deck
+    pub fn special_for(&self, value: u8) -> Option<SpecialEffect> {
+        self.specials.get(&value).copied()
+    }
+
+    // the drawn card came from the graveyard and has to go into the hand
+    pub fn must_swap(&self, drawn_card: &Card) -> bool {
+        self.graveyard_must_swap && !drawn_card.from_deck
+    }
+
+    // the player can draw the top of the graveyard, not the card they just put there
+    pub fn can_take_from_graveyard(&self, graveyard: &Graveyard, player: Entity) -> bool {
+        !(self.no_take_back && graveyard.discarded_by == Some(player))
+    }
+
+    // points of a card at the end of the round
+    pub fn card_points(&self, card: &Card) -> i32 {
+        self.face_points(&card.suit, card.value)
+    }
+
+    // points of a suit and value, also for the cards still in the deck
+    pub fn face_points(&self, suit: &Suit, value: u8) -> i32 {
+        match suit {
+            Suit::Joker => self.joker_points,
+            Suit::Gold if self.king_of_golds_zero && value == 12 => 0,
+            _ => value as i32,
+        }
+    }
+
+    // rules that differ from the classic game, for the table list
+    pub fn variants(&self) -> Vec<String> {
+        let classic = Self::default();
+        let mut variants = Vec::new();
+        if self.peek_count != classic.peek_count {
+            variants.push(format!("peek {}", self.peek_count));
+        }
+        if self.assist {
+            variants.push(String::from("assist"));
+        }
+        if self.specials != classic.specials {
+            let specials: Vec<String> = self.specials.iter()
+                .map(|(value, effect)| format!("{} {}", value, effect.name()))
+                .collect();
+            variants.push(format!("specials {}", specials.join(" ")));
+        }
+        for (value, effect) in self.specials_off_deck() {
+            variants.push(format!("{} {} not in deck", value, effect.name()));
+        }
+        if self.caller_penalty > 0 {
+            variants.push(format!("caller +{}", self.caller_penalty));
+        }
+        if self.caller_bonus > 0 {
+            variants.push(format!("caller -{}", self.caller_bonus));
+        }
+        if self.king_of_golds_zero {
+            variants.push(String::from("12 of golds 0"));
+        }
+        if self.pairs_cancel {
+            variants.push(String::from("pairs cancel"));
+        }
+        if self.graveyard_must_swap {
+            variants.push(String::from("graveyard must swap"));
+        }
+        if self.no_take_back {
+            variants.push(String::from("no take back"));
+        }
+        if self.deck != classic.deck {
+            variants.push(format!("deck {}", self.deck.name()));
+        }
+        if self.deck_copies > 1 {
+            variants.push(format!("{} decks", self.deck_copies));
+        }
+        if self.deck == DeckKind::WithJokers && self.joker_points != 0 {
+            variants.push(format!("joker {}", self.joker_points));
+        }
+        if self.tie_break != classic.tie_break {
+            variants.push(format!("tie {}", self.tie_break.label().to_lowercase()));
+        }
+        variants
+    }
+}
+
+// auxiliar function - value after the current one in a list of choices, back to the first after the last
+fn next_in<T: Copy + PartialEq>(choices: &[T], current: T) -> T {
+    let next = choices.iter().position(|&choice| choice == current).map_or(0, |index| index + 1);
+    choices[next % choices.len()]
+}
@@ -0,0 +1,9 @@
+use bevy::prelude::*;
+
+// a player throws a hand card on the graveyard, top is the graveyard card they reacted to
+#[derive(Event, Message, Clone, Debug)]
+pub struct SnapAttempt {
+    pub player: Entity,
+    pub card: Entity,
+    pub top: Entity,
+}
@@ -0,0 +1,38 @@
+use bevy::prelude::*;
+use crate::game::gamestate::AppState;
+use crate::game::peek::is_peeking;
+use crate::game::rules::resource::Ruleset;
+use crate::game::net::resource::NetSession;
+use crate::game::special_cards::system::cancel_special_targeting;
+
+pub mod event;
+pub mod resource;
+mod system;
+
+use event::SnapAttempt;
+use resource::SnapState;
+use system::{reset_snaps, snap_input, resolve_snaps};
+
+// snap rule: any player can throw a hand card matching the graveyard top, even out of turn
+// a wrong guess draws a penalty card
+pub struct SnapPlugin;
+
+impl Plugin for SnapPlugin {
+    fn build(&self, app: &mut App) {
+        app
+        .add_message::<SnapAttempt>()
+        .init_resource::<SnapState>()
+        .add_systems(OnEnter(AppState::PlayerTurn), reset_snaps)
+        .add_systems(
+            Update,
+            (snap_input.before(cancel_special_targeting), resolve_snaps)
+            .chain()
+            .run_if(in_state(AppState::PlayerTurn).and(is_snapping).and(not(is_peeking)))
+        );
+    }
+}
+
+// snaps are only played offline, LAN tables send the actions of the current player alone
+pub fn is_snapping(ruleset: Res<Ruleset>, net_session: Res<NetSession>) -> bool {
+    ruleset.snap && matches!(*net_session, NetSession::Offline)
+}
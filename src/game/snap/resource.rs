@@ -0,0 +1,7 @@
+use bevy::prelude::*;
+
+// graveyard top already matched by a snap, the players snapping after it are too late
+#[derive(Resource, Default)]
+pub struct SnapState {
+    pub claimed: Option<Entity>,
+}
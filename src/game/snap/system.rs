@@ -0,0 +1,140 @@
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+use crate::game::snap::event::SnapAttempt;
+use crate::game::snap::resource::SnapState;
+use crate::game::card::component::{Card, CardPosition, Selected};
+use crate::game::card::system::detect_card_click;
+use crate::game::{deck::component::Deck, graveyard::component::Graveyard, hand::component::Hand, player::component::Player, turn_player::component::Turn};
+use crate::game::hand::system::arrange_hand;
+use crate::game::special_cards::resource::SpecialCardEffect;
+use crate::game::match_config::resource::Viewer;
+use crate::game::layout::TableLayout;
+use crate::ui::card_animation::component::CardAnimation;
+use crate::ui::soundtrack::event::{PlayCardDraw, PlayCardPlace};
+
+// a new round, nothing snapped yet
+pub fn reset_snaps(mut snap_state: ResMut<SnapState>) {
+    *snap_state = SnapState::default();
+}
+
+// right click on one of your cards throws it on the graveyard, at any moment of the round
+pub fn snap_input(
+    mouse_input: Res<ButtonInput<MouseButton>>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    camera_query: Query<(&Camera, &GlobalTransform)>,
+    card_query: Query<(Entity, &mut Transform, &mut Card), With<Card>>,
+    graveyard_query: Query<&Graveyard>,
+    viewer: Res<Viewer>,
+    special_effect: Option<Res<SpecialCardEffect>>,
+    mut snap_messages: MessageWriter<SnapAttempt>,
+) {
+    if !mouse_input.just_pressed(MouseButton::Right) { return; }
+    let Some(player) = viewer.0 else { return; };
+
+    // a right click while choosing the targets of a special cancels them
+    if special_effect.is_some_and(|effect| effect.effect_type.is_some()) { return; }
+
+    // obtain window, camera, cursor/world position
+    let Ok(window) = windows.single() else { return; };
+    let Ok((camera, camera_transform)) = camera_query.single() else { return; };
+    let Some(cursor_pos) = window.cursor_position() else { return; };
+    let Ok(world_pos) = camera.viewport_to_world_2d(camera_transform, cursor_pos) else { return; };
+
+    let Some(card_entity) = detect_card_click(&card_query, world_pos) else { return; };
+    if !card_query.get(card_entity).is_ok_and(|(_, _, card)| card.position == CardPosition::Hand(player)) { return; }
+    let Some(&top) = graveyard_query.single().ok().and_then(|graveyard| graveyard.cards.last()) else { return; };
+
+    snap_messages.write(SnapAttempt { player, card: card_entity, top });
+}
+
+// settle the snaps of this frame, in seat order from the player whose turn it is
+// the first card matching a graveyard top wins, a wrong card stays in the hand and draws a penalty card
+pub fn resolve_snaps(
+    mut commands: Commands,
+    mut snap_messages: MessageReader<SnapAttempt>,
+    mut snap_state: ResMut<SnapState>,
+    mut card_query: Query<(Entity, &mut Transform, &mut Card), With<Card>>,
+    mut hand_query: Query<&mut Hand>,
+    player_query: Query<(Entity, &Player)>,
+    (mut graveyard_query, mut deck_query): (Query<&mut Graveyard>, Query<&mut Deck>),
+    turn: Res<Turn>,
+    special_effect: Option<Res<SpecialCardEffect>>,
+    layout: Res<TableLayout>,
+    (mut draw_message, mut place_message): (MessageWriter<PlayCardDraw>, MessageWriter<PlayCardPlace>),
+) {
+    let mut attempts: Vec<SnapAttempt> = snap_messages.read().cloned().collect();
+    if attempts.is_empty() { return; }
+
+    let players = player_query.iter().count().max(1);
+    let current_seat = player_query.get(turn.current_player).map_or(0, |(_, player)| player.seat);
+    attempts.sort_by_key(|attempt| player_query.get(attempt.player).map_or(players, |(_, player)| (player.seat + players - current_seat) % players));
+
+    for attempt in attempts {
+        let Ok((_, player)) = player_query.get(attempt.player) else { continue; };
+
+        // cards are locked while a special is played, and the current player finishes their turn first
+        if special_effect.as_ref().is_some_and(|effect| effect.effect_type.is_some()) {
+            info!(target: "mygame", "No snaps while a special card is being played");
+            continue;
+        }
+        if attempt.player == turn.current_player && turn.has_drawn_card {
+            info!(target: "mygame", "Finish your turn before snapping");
+            continue;
+        }
+
+        // the graveyard top moved on or was already matched
+        let Ok(mut graveyard) = graveyard_query.single_mut() else { return; };
+        let top = graveyard.cards.last().copied();
+        if top != Some(attempt.top) || snap_state.claimed == top {
+            info!(target: "mygame", "Too late to snap on this card");
+            continue;
+        }
+        let Ok((_, _, top_card)) = card_query.get(attempt.top) else { continue; };
+        let top_value = top_card.value;
+
+        let Ok(mut hand) = hand_query.get_mut(player.hand) else { continue; };
+        let Some(slot) = hand.cards.iter().position(|&card_entity| card_entity == attempt.card) else { continue; };
+        let Ok((_, transform, card)) = card_query.get(attempt.card) else { continue; };
+        if card.frozen {
+            info!(target: "mygame", "Card is frozen, it can't be snapped this round");
+            continue;
+        }
+        let matched = card.value == top_value;
+        let shake = CardAnimation::shake(transform);
+
+        if matched {
+            if let Ok((_, _, mut card)) = card_query.get_mut(attempt.card) {
+                card.position = CardPosition::Graveyard;
+                card.face_up = true;
+                card.is_being_dealt = true;
+            }
+            graveyard.cards.push(attempt.card);
+            graveyard.discarded_by = Some(attempt.player);
+            hand.cards.remove(slot);
+            snap_state.claimed = Some(attempt.card);
+            commands.entity(attempt.card).remove::<Selected>();
+            place_message.write(PlayCardPlace);
+            info!(target: "mygame", "Player {} snapped a {} on the graveyard", player.name, top_value);
+        } else {
+            // wrong guess: everyone sees the card shake, and a penalty card comes from the deck
+            commands.entity(attempt.card).insert(shake);
+            let penalty_card = deck_query.single_mut().ok()
+                .filter(|deck| !deck.cards_values.is_empty())
+                .map(|mut deck| deck.cards_values.remove(0));
+
+            match penalty_card.and_then(|card_entity| card_query.get_mut(card_entity).ok()) {
+                Some((penalty_entity, _, mut penalty)) => {
+                    penalty.position = CardPosition::Hand(attempt.player);
+                    penalty.owner_id = Some(attempt.player);
+                    penalty.hide();
+                    hand.cards.push(penalty_entity);
+                    draw_message.write(PlayCardDraw);
+                    info!(target: "mygame", "Player {} snapped a wrong card and draws a penalty card", player.name);
+                }
+                None => info!(target: "mygame", "Player {} snapped a wrong card, the deck is empty", player.name),
+            }
+        }
+
+        arrange_hand(&mut commands, &hand, player.seat, &layout, &card_query);
+    }
+}
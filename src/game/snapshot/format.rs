@@ -0,0 +1,54 @@
+use serde::{Deserialize, Serialize};
+use crate::game::net::protocol::{CardFace, NetPosition};
+
+/*
+GAME SNAPSHOT - the whole table at one moment, as JSON
+    {"version":1,"seat":1,"players":[{"name":"Player 1"},{"name":"Player 2"}],"current_seat":0,"has_drawn_card":true,
+     "cards":[{"id":3,"position":{"drawn":{"seat":0}},"face":null,"face_up":false,"known_by":[],"from_deck":true}],
+     "pending_special":"swap"}
+seat is the point of view: only the faces that seat can see and its own known cards are included
+without a seat (a save game) every face, who knows each card and the deck order ("deck_index":0 is drawn first) are included
+cards locked by a freeze have "frozen":true
+lockstep tables also send the turn counter ("turn":12), the hashes of both sides are compared by turn
+deck size, graveyard and hands follow from the card positions, card ids are the LAN card ids
+*/
+
+pub const SNAPSHOT_VERSION: u32 = 1;
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct GameSnapshot {
+    pub version: u32,
+    pub seat: Option<usize>,
+    pub players: Vec<SeatSnapshot>,
+    pub current_seat: usize,
+    pub has_drawn_card: bool,
+    pub cards: Vec<CardSnapshot>,
+    pub pending_special: Option<String>, // special of the card drawn from the deck, still to be used
+    #[serde(default)]
+    pub turn: u32,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SeatSnapshot {
+    pub name: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CardSnapshot {
+    pub id: usize,
+    pub position: NetPosition,
+    pub face: Option<CardFace>,
+    pub face_up: bool,
+    pub known_by: Vec<usize>, // seats that saw this face-down card
+    pub from_deck: bool,
+    #[serde(default)]
+    pub frozen: bool, // locked by a freeze until the end of the round
+    #[serde(default)]
+    pub deck_index: Option<usize>,
+}
+
+impl GameSnapshot {
+    pub fn deck_count(&self) -> usize {
+        self.cards.iter().filter(|card| card.position == NetPosition::Deck).count()
+    }
+}
@@ -0,0 +1,3 @@
+// snapshot of a round: resyncs a LAN client that reconnects, and is the save-game format
+pub mod format;
+pub mod system;
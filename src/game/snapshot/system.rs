@@ -0,0 +1,201 @@
+use bevy::prelude::*;
+use crate::game::snapshot::format::{GameSnapshot, SeatSnapshot, CardSnapshot, SNAPSHOT_VERSION};
+use crate::game::net::protocol::{CardFace, NetPosition};
+use crate::game::card::component::{Card, CardPosition};
+use crate::game::{deck::component::Deck, graveyard::component::Graveyard, hand::component::Hand, player::component::Player, turn_player::component::Turn};
+use crate::game::hand::system::get_player_positions;
+use crate::game::layout::TableLayout;
+use crate::game::rules::resource::Ruleset;
+use std::collections::HashMap;
+
+// capture the table, from the point of view of one player (seat and entity) or complete
+pub fn capture_snapshot(
+    view: Option<(usize, Entity)>,
+    cards: &[Entity],
+    card_query: &Query<(Entity, &Card)>,
+    player_query: &Query<(Entity, &Player)>,
+    hand_query: &Query<&Hand>,
+    graveyard: Option<&Graveyard>,
+    deck: Option<&Deck>,
+    turn: &Turn,
+    ruleset: &Ruleset,
+) -> GameSnapshot {
+    let mut players: Vec<(usize, Entity, String)> = player_query.iter()
+        .map(|(entity, player)| (player.seat, entity, player.name.clone()))
+        .collect();
+    players.sort();
+    let seat_of = |player: Entity| players.iter().find(|(_, entity, _)| *entity == player).map(|(seat, _, _)| *seat);
+
+    let mut snapshot_cards = Vec::new();
+    let mut pending_special = None;
+    for (id, &card_entity) in cards.iter().enumerate() {
+        let Ok((_, card)) = card_query.get(card_entity) else { continue; };
+
+        let visible = match view {
+            Some((_, viewer)) => card.is_visible_to(viewer),
+            None => true,
+        };
+        let known_by = players.iter()
+            .filter(|(seat, entity, _)| card.known_by.0.contains(entity) && view.is_none_or(|(view_seat, _)| view_seat == *seat))
+            .map(|(seat, _, _)| *seat)
+            .collect();
+
+        if let CardPosition::DrawnCard(owner) = card.position && card.from_deck && owner == turn.current_player
+            && view.is_none_or(|(_, viewer)| viewer == owner) {
+            pending_special = ruleset.special_for(card.value).map(|effect| effect.name().to_string());
+        }
+
+        snapshot_cards.push(CardSnapshot {
+            id,
+            position: net_position(card_entity, card, player_query, hand_query, graveyard),
+            face: visible.then(|| CardFace { value: card.value, suit: card.suit.index() as u8 }),
+            face_up: card.face_up,
+            known_by,
+            from_deck: card.from_deck,
+            frozen: card.frozen,
+            deck_index: deck.filter(|_| view.is_none())
+                .and_then(|deck| deck.cards_values.iter().position(|&entity| entity == card_entity)),
+        });
+    }
+
+    GameSnapshot {
+        version: SNAPSHOT_VERSION,
+        seat: view.map(|(seat, _)| seat),
+        current_seat: seat_of(turn.current_player).unwrap_or(0),
+        has_drawn_card: turn.has_drawn_card,
+        players: players.into_iter().map(|(_, _, name)| SeatSnapshot { name }).collect(),
+        cards: snapshot_cards,
+        pending_special,
+        turn: 0,
+    }
+}
+
+// put a complete snapshot on the local table: cards are found by their face, seats turned into local seats
+// false when a card of the snapshot is not on this table
+pub fn restore_snapshot(
+    snapshot: &GameSnapshot,
+    local_seat: impl Fn(usize) -> usize,
+    card_query: &mut Query<(Entity, &mut Transform, &mut Card)>,
+    player_query: &Query<(Entity, &Player)>,
+    hand_query: &mut Query<&mut Hand>,
+    (graveyard, deck): (Option<&mut Graveyard>, Option<&mut Deck>),
+    turn: &mut Turn,
+    layout: &TableLayout,
+) -> bool {
+    let mut players: Vec<(usize, Entity, Entity)> = player_query.iter()
+        .map(|(entity, player)| (player.seat, entity, player.hand))
+        .collect();
+    players.sort();
+    let player_at = |seat: usize| players.get(local_seat(seat)).map(|&(_, entity, _)| entity);
+
+    // several decks or jokers give cards with the same face, each snapshot card takes one of them
+    let mut by_face: HashMap<(u8, usize), Vec<Entity>> = HashMap::new();
+    for (entity, _, card) in card_query.iter() {
+        by_face.entry((card.value, card.suit.index())).or_default().push(entity);
+    }
+
+    // hands can be smaller or bigger than the dealt size after snaps
+    let hand_size = |seat: usize| snapshot.cards.iter()
+        .filter(|card_snapshot| matches!(card_snapshot.position, NetPosition::Hand { seat: other, .. } if other == seat))
+        .count();
+
+    let mut hands: Vec<Vec<(usize, Entity)>> = vec![Vec::new(); players.len()];
+    let mut graveyard_cards = Vec::new();
+    let mut deck_cards = Vec::new();
+    for card_snapshot in &snapshot.cards {
+        let Some(face) = card_snapshot.face else { return false; };
+        let Some(card_entity) = by_face.get_mut(&(face.value, face.suit as usize)).and_then(|cards| cards.pop()) else { return false; };
+        let Ok((_, mut transform, mut card)) = card_query.get_mut(card_entity) else { return false; };
+
+        card.face_up = card_snapshot.face_up;
+        card.from_deck = card_snapshot.from_deck;
+        card.frozen = card_snapshot.frozen;
+        card.hide();
+        for &seat in &card_snapshot.known_by {
+            if let Some(player) = player_at(seat) {
+                card.reveal_to(player);
+            }
+        }
+
+        match card_snapshot.position {
+            NetPosition::Deck => {
+                card.position = CardPosition::Deck;
+                card.owner_id = None;
+                transform.translation = Vec3::new(layout.width * 0.15, 0.0, 1.0);
+                deck_cards.push((card_snapshot.deck_index.unwrap_or(usize::MAX), card_entity));
+            }
+            NetPosition::Hand { seat, slot } => {
+                let Some(owner) = player_at(seat) else { return false; };
+                card.position = CardPosition::Hand(owner);
+                card.owner_id = Some(owner);
+                if let Some(position) = get_player_positions(local_seat(seat), hand_size(seat), layout.width, layout.height).get(slot) {
+                    transform.translation = *position;
+                }
+                if let Some(hand) = hands.get_mut(local_seat(seat)) {
+                    hand.push((slot, card_entity));
+                }
+            }
+            NetPosition::Graveyard { index } => {
+                card.position = CardPosition::Graveyard;
+                graveyard_cards.push((index, card_entity));
+            }
+            NetPosition::Drawn { seat } => {
+                let Some(owner) = player_at(seat) else { return false; };
+                card.position = CardPosition::DrawnCard(owner);
+                card.owner_id = Some(owner);
+            }
+        }
+    }
+
+    for (cards, &(_, _, hand_entity)) in hands.iter_mut().zip(players.iter()) {
+        cards.sort();
+        if let Ok(mut hand) = hand_query.get_mut(hand_entity) {
+            hand.cards = cards.iter().map(|&(_, card_entity)| card_entity).collect();
+        }
+    }
+    graveyard_cards.sort();
+    if let Some(graveyard) = graveyard {
+        graveyard.cards = graveyard_cards.into_iter().map(|(_, card_entity)| card_entity).collect();
+    }
+    deck_cards.sort();
+    if let Some(deck) = deck {
+        deck.cards_values = deck_cards.into_iter().map(|(_, card_entity)| card_entity).collect();
+    }
+
+    if let Some(current_player) = player_at(snapshot.current_seat) && turn.current_player != current_player {
+        turn.current_player = current_player;
+        turn.number += 1;
+    }
+    turn.has_drawn_card = snapshot.has_drawn_card;
+    true
+}
+
+// position of a card as seen on the network
+pub fn net_position(
+    card_entity: Entity,
+    card: &Card,
+    player_query: &Query<(Entity, &Player)>,
+    hand_query: &Query<&Hand>,
+    graveyard: Option<&Graveyard>,
+) -> NetPosition {
+    match card.position {
+        CardPosition::Deck => NetPosition::Deck,
+        CardPosition::Hand(owner) => {
+            let Ok((_, player)) = player_query.get(owner) else { return NetPosition::Deck; };
+            let slot = hand_query.get(player.hand).ok()
+                .and_then(|hand| hand.cards.iter().position(|&entity| entity == card_entity))
+                .unwrap_or(0);
+            NetPosition::Hand { seat: player.seat, slot }
+        }
+        CardPosition::Graveyard => {
+            let index = graveyard
+                .and_then(|graveyard| graveyard.cards.iter().position(|&entity| entity == card_entity))
+                .unwrap_or(0);
+            NetPosition::Graveyard { index }
+        }
+        CardPosition::DrawnCard(owner) => {
+            let seat = player_query.get(owner).map(|(_, player)| player.seat).unwrap_or(0);
+            NetPosition::Drawn { seat }
+        }
+    }
+}
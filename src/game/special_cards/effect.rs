@@ -1,259 +1,343 @@
 use bevy::prelude::*;
-use rand::{Rng, seq::SliceRandom};
-use crate::game::{special_cards::resource::{SpecialCardEffect, SpecialEffect}, card::component::{Card, CardPosition}, hand::component::Hand, player::component::Player, turn_player::component::Turn};
-use crate::ui::turn_indicator::component::CardOpacity;
+use rand::seq::SliceRandom;
+use crate::game::special_cards::resource::SpecialCardEffect;
+use crate::game::special_cards::handler::{SpecialEffectHandler, Targeting, SpecialTargets, EffectProgress, EffectContext, AiSpecialView};
+use crate::game::card::component::{CardPosition, TemporaryReveal};
+use crate::game::ai::component::AIMemory;
+use crate::game::ai::decision::{choose_swap_special, choose_look_and_swap, should_swap_looked, choose_shuffle_target, choose_reveal_card, choose_spy_card, choose_freeze_target};
 use crate::ui::card_animation::component::{CardAnimation, AnimationState, AnimationType};
 
 // special effects:
-//      - reveal --> reveal random card from your hand
+//      - reveal --> look at a chosen card of your hand
+//      - spy --> look at a chosen card of an opponent
 //      - shuffle --> randomize positions of hand cards
-//      - swap --> swap one card from your hand for another card from your opponent's hand
-
-pub fn reveal_effect(
-    special_effect: Option<ResMut<SpecialCardEffect>>,
-    mut card_query: Query<&mut Card>,
-    hand_query: Query<&Hand>,
-    player_query: Query<(Entity, &Player)>,
-    turn_query: Res<Turn>,
-) {
-    // run if effect type is reveal
-    let Some(mut special_effect) = special_effect else { return; };
-    if !matches!(special_effect.effect_type, Some(SpecialEffect::Reveal)) {
-        return;
+//      - swap --> swap one card from your hand for another card from your opponent's hand, without looking
+//      - look and swap --> look at both cards, then decide whether to swap them
+//      - skip --> the next player loses their turn
+//      - freeze --> an opponent card can't be swapped until the end of the round
+
+// look at one card: an own card, or an opponent card for the spy
+pub struct RevealHandler {
+    pub spy: bool,
+}
+
+impl SpecialEffectHandler for RevealHandler {
+    fn targeting(&self) -> Targeting {
+        if self.spy { Targeting::OpponentCard } else { Targeting::OwnCard }
     }
-    
-    if let Some((_, player)) = player_query.iter().find(|(entity, _)| *entity == turn_query.current_player) {
-        if let Ok(hand) = hand_query.get(player.hand) {
-            
-            // find one random card with face_up = false to return true
-            let face_down_cards: Vec<Entity> = hand.cards.iter()
-                .filter_map(|&card_entity| {
-                    card_query.get(card_entity).ok()
-                        .filter(|card| !card.face_up)
-                        .map(|_| card_entity)
-                })
-                .collect();
-
-            if !face_down_cards.is_empty() {
-                let mut rng = rand::rng();
-                let random_index = rng.random_range(0..face_down_cards.len());
-                let selected_card = face_down_cards[random_index];
-                
-                if let Ok(mut card) = card_query.get_mut(selected_card) {
-                    card.face_up = true;
-                    info!(target: "mygame", "Revealed card: {} of {:?}", card.value, card.suit);
-                }
+
+    fn apply(&self, effect: &mut SpecialCardEffect, ctx: &mut EffectContext) -> EffectProgress {
+        let player = ctx.turn.current_player;
+        let chosen_card = if self.spy { effect.target_card } else { effect.own_card };
+        let Some(card_entity) = chosen_card else { return EffectProgress::Done; };
+
+        if let Ok((_, _, mut card, _)) = ctx.cards.get_mut(card_entity) {
+            card.reveal_to(player);
+            info!(target: "mygame", "Revealed card: {} of {:?}", card.value, card.suit);
+        }
+        // classic memory: the card is only shown for a while
+        if !ctx.ruleset.assist {
+            ctx.commands.entity(card_entity).insert(TemporaryReveal::new(player));
+        }
+        EffectProgress::Done
+    }
+
+    fn ai_targets(&self, view: &mut AiSpecialView) -> Option<SpecialTargets> {
+        if self.spy {
+            // look at the opponent card the AI is least sure about
+            let Some(card_entity) = choose_spy_card(view.memory, view.opponents) else {
+                info!(target: "mygame", "AI skips the spy, it knows every opponent card");
+                return None;
+            };
+            info!(target: "mygame", "AI will spy an opponent card");
+            Some(SpecialTargets { target_card: Some(card_entity), ..default() })
+        } else {
+            // only reveal when there are unknown cards left
+            let Some(card_entity) = choose_reveal_card(view.memory, view.hand) else {
+                info!(target: "mygame", "AI skips the reveal, there is nothing left to discover");
+                return None;
+            };
+            info!(target: "mygame", "AI will look at a card");
+            Some(SpecialTargets { own_card: Some(card_entity), ..default() })
+        }
+    }
+}
+
+// randomize the order of an opponent's hand, each slot keeps its position on the table
+pub struct ShuffleHandler;
+
+impl SpecialEffectHandler for ShuffleHandler {
+    fn targeting(&self) -> Targeting {
+        Targeting::OpponentPlayer
+    }
+
+    fn apply(&self, effect: &mut SpecialCardEffect, ctx: &mut EffectContext) -> EffectProgress {
+        // find target player hand
+        let Some((_, target_player)) = effect.target_player.and_then(|entity| ctx.players.get(entity).ok()) else {
+            info!(target: "mygame", "Target player not found");
+            return EffectProgress::Done;
+        };
+        let Ok(mut hand) = ctx.hands.get_mut(target_player.hand) else {
+            info!(target: "mygame", "Target player hand not found");
+            return EffectProgress::Done;
+        };
+
+        // verify if have cards to shuffle, snaps can shrink the hand
+        if hand.cards.len() < 2 {
+            info!(target: "mygame", "Target player doesn't have cards to shuffle");
+            return EffectProgress::Done;
+        }
+
+        // verify card positions
+        let mut positions: Vec<Vec3> = Vec::new();
+        for &card_entity in &hand.cards {
+            if let Ok((_, transform, mut card, _)) = ctx.cards.get_mut(card_entity) {
+                positions.push(transform.translation); // push to Vec (positions)
+                card.hide();
             }
+        }
 
-            // reset from_deck so that the effect is used only once
-            if let Some(special_card_entity) = special_effect.card_entity {
-                if let Ok(mut special_card) = card_query.get_mut(special_card_entity) {
-                    special_card.from_deck = false;
-                }
+        hand.cards.shuffle(&mut ctx.rng.0);
+
+        // update card positions
+        for (i, &card_entity) in hand.cards.iter().enumerate() {
+            if let Ok((_, mut transform, _, _)) = ctx.cards.get_mut(card_entity) {
+                transform.translation = positions[i];
             }
         }
+
+        info!(target: "mygame", "Cards shuffled for target player!");
+        EffectProgress::Done
+    }
+
+    fn ai_targets(&self, view: &mut AiSpecialView) -> Option<SpecialTargets> {
+        // only shuffle an opponent who has memorised their hand
+        let Some(opponent_entity) = choose_shuffle_target(view.memory, view.opponents) else {
+            info!(target: "mygame", "AI saves the shuffle, opponent has not memorised their hand");
+            return None;
+        };
+
+        // the opponent lost track of their cards
+        if let Some((_, opponent_hand)) = view.opponents.iter().find(|(entity, _)| *entity == opponent_entity) {
+            view.memory.opponent_model.forget_memorised(&opponent_hand.cards);
+        }
+        info!(target: "mygame", "AI shuffled opponent's hand");
+        Some(SpecialTargets { target_player: Some(opponent_entity), ..default() })
     }
-    // clean effect
-    *special_effect = SpecialCardEffect::default();
-    info!(target: "mygame", "Effect completed");
 }
 
-pub fn shuffle_effect(
-    special_effect: Option<ResMut<SpecialCardEffect>>,
-    mut card_query: Query<(Entity, &mut Transform, &mut Card), With<Card>>,
-    hand_query: Query<&Hand>,
-    player_query: Query<(Entity, &Player)>,
-) {
-    // run if effect type is shuffle
-    if let Some(mut effect) = special_effect {
-        if matches!(effect.effect_type, Some(SpecialEffect::Shuffle)) {
-            
-            // verify do not expecting target
-            if effect.awaiting_target { return; }
-
-            // verify have target_player
-            let Some(target_player_id) = effect.target_player else {
-                *effect = SpecialCardEffect::default();
-                return;
-            };
+// exchange an own card with an opponent card, blind or after looking at both
+pub struct SwapHandler {
+    pub look: bool,
+}
 
-            // find target player
-            let target_player = player_query.iter()
-                .find(|(entity, _)| *entity == target_player_id);
-    
-            let Some((_, player)) = target_player else {
-                info!(target: "mygame", "Target player not found");
-                *effect = SpecialCardEffect::default();
-                return;
-            };
+impl SpecialEffectHandler for SwapHandler {
+    fn targeting(&self) -> Targeting {
+        Targeting::OpponentAndOwnCard
+    }
 
-            // find target player hand
-            let Ok(hand) = hand_query.get(player.hand) else {
-                info!(target: "mygame", "Target player hand not found");
-                *effect = SpecialCardEffect::default();
-                return;
-            };
+    fn exchanges_cards(&self) -> bool {
+        true
+    }
 
-            // verify if have 4 cards in hand
-            if hand.cards.len() != 4 {
-                info!(target: "mygame", "Target player doesn't have 4 cards");
-                *effect = SpecialCardEffect::default();
-                return;
-            };
+    fn asks_decision(&self) -> bool {
+        self.look
+    }
 
-            // verify card positions
-            let mut positions: Vec<Vec3> = Vec::new();
-            for &card_entity in &hand.cards {
-                if let Ok((_, transform, mut card)) = card_query.get_mut(card_entity) {
-                    positions.push(transform.translation); // push to Vec (positions)
-                    card.face_up = false;
+    fn apply(&self, effect: &mut SpecialCardEffect, ctx: &mut EffectContext) -> EffectProgress {
+        let (Some(target_card_entity), Some(own_card_entity)) = (effect.target_card, effect.own_card) else {
+            return EffectProgress::Done;
+        };
+        let player = ctx.turn.current_player;
+
+        // look and swap: show both cards to the player, then wait for the decision
+        if self.look && effect.swap_decided.is_none() {
+            if !effect.awaiting_decision {
+                for card_entity in [target_card_entity, own_card_entity] {
+                    if let Ok((_, _, mut card, _)) = ctx.cards.get_mut(card_entity) {
+                        card.reveal_to(player);
+                    }
+                    if !ctx.ruleset.assist {
+                        ctx.commands.entity(card_entity).insert(TemporaryReveal::new(player));
+                    }
                 }
+                effect.awaiting_decision = true;
             }
-            
-            // randomize positions
-            let mut rng = rand::rng();
-            positions.shuffle(&mut rng);
-
-            // update card positions
-            for (i, &card_entity) in hand.cards.iter().enumerate() {
-               if let Ok((_, mut transform, _)) = card_query.get_mut(card_entity) {
-                   transform.translation = positions[i];
-               }
+            return EffectProgress::Waiting;
+        }
+
+        if effect.swap_decided == Some(false) {
+            info!(target: "mygame", "Cards kept in place");
+            return EffectProgress::Done;
+        }
+
+        let Ok([(_, mut target_transform, mut target_card, target_opacity), (_, mut own_transform, mut own_card, own_opacity)]) =
+            ctx.cards.get_many_mut([target_card_entity, own_card_entity]) else {
+            return EffectProgress::Done;
+        };
+
+        // obtain positions and owner id
+        let target_pos = target_transform.translation;
+        let own_pos = own_transform.translation;
+        let target_id = target_card.owner_id;
+        let own_id = own_card.owner_id;
+
+        // swap and update positions, owner ids and hide both cards
+        target_transform.translation = own_pos;
+        target_card.owner_id = own_id;
+        target_card.position = CardPosition::Hand(own_id.unwrap());
+        target_card.hide();
+        own_transform.translation = target_pos;
+        own_card.owner_id = target_id;
+        own_card.position = CardPosition::Hand(target_id.unwrap());
+        own_card.hide();
+
+        // insert movement card animation
+        ctx.commands.entity(target_card_entity).insert(CardAnimation {
+            animation_type: AnimationType::Movement,
+            progress: 0.0,
+            duration: 0.25,
+            state: AnimationState::Animating,
+            original_position: target_pos,  // previous position
+            original_scale: target_transform.scale,
+            original_rotation: target_transform.rotation,
+            target_position: Some(own_pos),  // new position
+            delay: 0.0,
+            delay_elapsed: 0.0,
+        });
+
+        // reset position for start from origin
+        target_transform.translation = target_pos;
+
+        ctx.commands.entity(own_card_entity).insert(CardAnimation {
+            animation_type: AnimationType::Movement,
+            progress: 0.0,
+            duration: 0.25,
+            state: AnimationState::Animating,
+            original_position: own_pos,
+            original_scale: own_transform.scale,
+            original_rotation: own_transform.rotation,
+            target_position: Some(target_pos),
+            delay: 0.0,
+            delay_elapsed: 0.0,
+        });
+
+        // reset position for start from origin
+        own_transform.translation = own_pos;
+
+        // update opacity of both cards
+        for (opacity, owner) in [(target_opacity, own_id), (own_opacity, target_id)] {
+            if let Some(mut opacity) = opacity && let Some(owner) = owner {
+                let new_value = if owner == player { 1.0 } else { 0.2 };
+                opacity.current = new_value;
+                opacity.target = new_value;
             }
+        }
 
-            // reset from_deck so that the effect is used only once
-            if let Some(special_card_entity) = effect.card_entity {
-                if let Ok(mut special_card) = card_query.get_mut(special_card_entity) {
-                    special_card.2.from_deck = false;
+        // each card takes the slot of the other one in the hands
+        for (owner, from, to) in [(target_id, target_card_entity, own_card_entity), (own_id, own_card_entity, target_card_entity)] {
+            let Some((_, owner_player)) = owner.and_then(|owner| ctx.players.get(owner).ok()) else { continue; };
+            if let Ok(mut hand) = ctx.hands.get_mut(owner_player.hand) {
+                for card_entity in hand.cards.iter_mut().filter(|card_entity| **card_entity == from) {
+                    *card_entity = to;
                 }
             }
+        }
+
+        info!(target: "mygame", "Card swap completed: {:?} -> {:?}", target_card_entity, own_card_entity);
+        EffectProgress::Done
+    }
 
-            info!(target: "mygame", "Cards shuffled for target player!");
+    fn ai_targets(&self, view: &mut AiSpecialView) -> Option<SpecialTargets> {
+        if self.look {
+            // look at the pair that might help, the swap is decided once both cards are seen
+            let Some((own, target)) = choose_look_and_swap(view.memory, view.free_hand, view.free_opponent_hands) else {
+                info!(target: "mygame", "AI gives up the look and swap, no card is worth stealing");
+                return None;
+            };
+            info!(target: "mygame", "AI will look at two cards before swapping");
+            return Some(SpecialTargets { target_card: Some(target), own_card: Some(own), ..default() });
+        }
+
+        // only swap when stealing a low card clearly improves the hand
+        let Some((own, target)) = choose_swap_special(view.memory, view.free_hand, view.free_opponent_hands) else {
+            info!(target: "mygame", "AI gives up the swap, it would not improve its hand");
+            return None;
+        };
+        view.memory.swap_knowledge(own, target);
+        info!(target: "mygame", "AI will swap cards");
+        Some(SpecialTargets { target_card: Some(target), own_card: Some(own), ..default() })
+    }
 
-            *effect = SpecialCardEffect::default();
-            info!(target: "mygame", "Effect completed");
+    fn ai_decide(&self, memory: &mut AIMemory, effect: &SpecialCardEffect) -> bool {
+        let (Some(own), Some(target)) = (effect.own_card, effect.target_card) else { return false; };
+        let swap = should_swap_looked(memory, own, target);
+        if swap {
+            memory.swap_knowledge(own, target);
         }
+        swap
     }
 }
 
-pub fn swap_effect(
-    mut commands: Commands,
-    special_effect: Option<ResMut<SpecialCardEffect>>,
-    mut card_query: Query<(Entity, &mut Transform, &mut Card, Option<&mut CardOpacity>), With<Card>>,
-    mut hand_query: Query<&mut Hand>,
-    player_query: Query<(Entity, &Player)>,
-    turn_query: Res<Turn>,
-) {
-    // run if effect type is swap
-    if let Some(mut effect) = special_effect {
-        if matches!(effect.effect_type, Some(SpecialEffect::Swap)) {
-            
-            // verify if have target_card (rival card) and own_card (own card)
-            if let (Some(target_card_entity), Some(own_card_entity)) = (effect.target_card, effect.own_card) {
-                if let Ok([(_, mut target_transform, mut target_card, target_opacity), (_, mut own_transform, mut own_card, own_opacity)]) =
-                    card_query.get_many_mut([target_card_entity, own_card_entity]) {
-                    
-                    // obtain positions and owner id
-                    let target_pos = target_transform.translation;
-                    let own_pos = own_transform.translation;
-                    let target_id = target_card.owner_id;
-                    let own_id = own_card.owner_id;
-
-                    // swap and update positions, owner ids and no face_up
-                    target_transform.translation = own_pos;
-                    target_card.owner_id = own_id;
-                    target_card.position = CardPosition::Hand(own_id.unwrap());
-                    target_card.face_up = false;
-                    own_transform.translation = target_pos;
-                    own_card.owner_id = target_id;
-                    own_card.position = CardPosition::Hand(target_id.unwrap());
-                    own_card.face_up = false;
-
-                    // insert movement card animation
-                    commands.entity(target_card_entity).insert(CardAnimation {
-                        animation_type: AnimationType::Movement,
-                        progress: 0.0,
-                        duration: 0.25,
-                        state: AnimationState::Animating,
-                        original_position: target_pos,  // previous position
-                        original_scale: target_transform.scale,
-                        original_rotation: target_transform.rotation,
-                        target_position: Some(own_pos),  // new position
-                        delay: 0.0,
-                        delay_elapsed: 0.0,
-                    });
-
-                    // reset position for start from origin
-                    target_transform.translation = target_pos;
-
-                    commands.entity(own_card_entity).insert(CardAnimation {
-                        animation_type: AnimationType::Movement,
-                        progress: 0.0,
-                        duration: 0.25,
-                        state: AnimationState::Animating,
-                        original_position: own_pos,
-                        original_scale: own_transform.scale,
-                        original_rotation: own_transform.rotation,
-                        target_position: Some(target_pos),
-                        delay: 0.0,
-                        delay_elapsed: 0.0,
-                    });
-
-                    // reset position for start from origin
-                    own_transform.translation = own_pos;
-
-                    // update and save changes of player hand with target_card
-                    if let Some((_, target_player)) = player_query.iter().find(|(entity, _)| Some(*entity) == target_id) {
-                        if let Ok(mut target_hand) = hand_query.get_mut(target_player.hand) {
-                            // remove target_card => add own_card
-                            target_hand.cards.retain(|&card_entity| card_entity != target_card_entity);
-                            target_hand.cards.push(own_card_entity);
-                        }
-                    }
+// the next player loses their turn, the cards of their hand shake
+pub struct SkipHandler;
 
-                    // update and save changes of player hand with own_card
-                    if let Some((_, own_player)) = player_query.iter().find(|(entity, _)| Some(*entity) == own_id) {
-                        if let Ok(mut own_hand) = hand_query.get_mut(own_player.hand) {
-                            // remove own_card => add target_card
-                            own_hand.cards.retain(|&card_entity| card_entity != own_card_entity);
-                            own_hand.cards.push(target_card_entity);
-                        }
-                    }
+impl SpecialEffectHandler for SkipHandler {
+    fn targeting(&self) -> Targeting {
+        Targeting::Nothing
+    }
 
-                    // update opacity of target_card
-                    if let Some(mut target_opacity) = target_opacity {
-                        if let CardPosition::Hand(owner) = target_card.position {
-                            let new_value = if owner == turn_query.current_player { 1.0 } else { 0.2 };
-                            target_opacity.current = new_value;
-                            target_opacity.target = new_value;
-                        }
-                    }
+    fn apply(&self, _effect: &mut SpecialCardEffect, ctx: &mut EffectContext) -> EffectProgress {
+        ctx.turn.skip_next = true;
 
-                    // update opacity of own_card 
-                    if let Some(mut own_opacity) = own_opacity {
-                        if let CardPosition::Hand(owner) = own_card.position {
-                            let new_value = if owner == turn_query.current_player { 1.0 } else { 0.2 };
-                            own_opacity.current = new_value;
-                            own_opacity.target = new_value;
-                        }
-                    }
-                    
-                    info!(target: "mygame", "Card swap completed: {:?} -> {:?}", target_card_entity, own_card_entity);
-                }
-            } else {
-                info!(target: "mygame", "One more card needs to be selected");
-                return;
-            }
+        // next player in seat order
+        let mut players: Vec<(Entity, usize)> = ctx.players.iter().map(|(entity, player)| (entity, player.seat)).collect();
+        players.sort_by_key(|(_, seat)| *seat);
+        let next_player = players.iter()
+            .position(|(entity, _)| *entity == ctx.turn.current_player)
+            .map(|pos| players[(pos + 1) % players.len()].0);
+        info!(target: "mygame", "Player {:?} will lose their turn", next_player);
 
-            // reset from_deck so that the effect is used only once
-            if let Some(special_card_entity) = effect.card_entity {
-                if let Ok((_, _, mut special_card, _)) = card_query.get_mut(special_card_entity) {
-                    special_card.from_deck = false;
-                }
+        for (card_entity, transform, card, _) in ctx.cards.iter() {
+            if matches!(card.position, CardPosition::Hand(owner) if Some(owner) == next_player) {
+                ctx.commands.entity(card_entity).insert(CardAnimation::shake(transform));
             }
+        }
+        EffectProgress::Done
+    }
 
-            *effect = SpecialCardEffect::default();
-            info!(target: "mygame", "Effect completed");
+    fn ai_targets(&self, _view: &mut AiSpecialView) -> Option<SpecialTargets> {
+        // a lost turn for the next player is always worth it
+        info!(target: "mygame", "AI makes the next player lose their turn");
+        Some(SpecialTargets::default())
+    }
+}
+
+// lock an opponent card until the end of the round
+pub struct FreezeHandler;
+
+impl SpecialEffectHandler for FreezeHandler {
+    fn targeting(&self) -> Targeting {
+        Targeting::OpponentCard
+    }
+
+    fn apply(&self, effect: &mut SpecialCardEffect, ctx: &mut EffectContext) -> EffectProgress {
+        let Some(card_entity) = effect.target_card else { return EffectProgress::Done; };
+        if let Ok((_, transform, mut card, _)) = ctx.cards.get_mut(card_entity) {
+            card.frozen = true;
+            ctx.commands.entity(card_entity).insert(CardAnimation::shake(&transform));
+            info!(target: "mygame", "Card frozen: {:?}", card_entity);
         }
+        EffectProgress::Done
+    }
+
+    fn ai_targets(&self, view: &mut AiSpecialView) -> Option<SpecialTargets> {
+        // leave an opponent stuck with a high card
+        let Some(card_entity) = choose_freeze_target(view.memory, view.free_opponent_hands) else {
+            info!(target: "mygame", "AI saves the freeze, no opponent card is clearly high");
+            return None;
+        };
+        info!(target: "mygame", "AI will freeze an opponent card");
+        Some(SpecialTargets { target_card: Some(card_entity), ..default() })
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,128 @@
+use bevy::prelude::*;
+use bevy::ecs::system::SystemParam;
+use crate::game::{card::component::{Card, CardPosition}, hand::component::Hand, player::component::Player, turn_player::component::Turn};
+use crate::game::ai::component::AIMemory;
+use crate::game::rng::GameRng;
+use crate::game::rules::resource::Ruleset;
+use crate::game::special_cards::resource::SpecialCardEffect;
+use crate::ui::turn_indicator::component::CardOpacity;
+
+// what the player clicks before a special is applied
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Targeting {
+    Nothing,
+    OwnCard,
+    OpponentCard,
+    OpponentPlayer,     // any card of the hand to target
+    OpponentAndOwnCard, // an opponent card, then one of your cards
+    Several(u8),        // that many different opponent cards, from one or more opponents
+}
+
+impl Targeting {
+    pub fn needs_opponent_card(&self) -> bool {
+        matches!(self, Targeting::OpponentCard | Targeting::OpponentPlayer | Targeting::OpponentAndOwnCard | Targeting::Several(_))
+    }
+
+    pub fn needs_own_card(&self) -> bool {
+        matches!(self, Targeting::OwnCard | Targeting::OpponentAndOwnCard)
+    }
+}
+
+// targets chosen by the AI for a special
+#[derive(Default, Debug)]
+pub struct SpecialTargets {
+    pub target_player: Option<Entity>,
+    pub target_card: Option<Entity>,
+    pub own_card: Option<Entity>,
+    pub target_cards: Vec<Entity>, // several
+}
+
+// an effect is applied at once, or waits for a decision of the player
+#[derive(PartialEq, Debug)]
+pub enum EffectProgress {
+    Done,
+    Waiting,
+}
+
+// the table as effect handlers see it
+#[derive(SystemParam)]
+pub struct EffectContext<'w, 's> {
+    pub commands: Commands<'w, 's>,
+    pub cards: Query<'w, 's, (Entity, &'static mut Transform, &'static mut Card, Option<&'static mut CardOpacity>)>,
+    pub hands: Query<'w, 's, &'static mut Hand>,
+    pub players: Query<'w, 's, (Entity, &'static Player)>,
+    pub turn: ResMut<'w, Turn>,
+    pub rng: ResMut<'w, GameRng>,
+    pub ruleset: Res<'w, Ruleset>,
+}
+
+impl EffectContext<'_, '_> {
+    // owner of a card in a hand
+    pub fn hand_owner(&self, card_entity: Entity) -> Option<Entity> {
+        match self.cards.get(card_entity).ok()?.2.position {
+            CardPosition::Hand(owner) => Some(owner),
+            _ => None,
+        }
+    }
+
+    pub fn is_frozen(&self, card_entity: Entity) -> bool {
+        self.cards.get(card_entity).is_ok_and(|(_, _, card, _)| card.frozen)
+    }
+}
+
+// what the AI knows when it weighs a special, frozen cards are left out of the free hands
+pub struct AiSpecialView<'a> {
+    pub memory: &'a mut AIMemory,
+    pub hand: &'a Hand,
+    pub free_hand: &'a Hand,
+    pub opponents: &'a [(Entity, &'a Hand)],
+    pub free_opponent_hands: &'a [&'a Hand],
+}
+
+// behaviour of one special effect, registered in the SpecialEffectRegistry
+// targeting, card selection, bots, LAN clients and the turn timer work from targeting() alone
+pub trait SpecialEffectHandler: Send + Sync {
+    fn targeting(&self) -> Targeting;
+
+    // the effect moves cards between hands, frozen cards can't be chosen
+    fn exchanges_cards(&self) -> bool {
+        false
+    }
+
+    // the effect waits for a yes/no of the player once the targets are chosen
+    fn asks_decision(&self) -> bool {
+        false
+    }
+
+    // the chosen targets are on the right hands
+    fn validate(&self, effect: &SpecialCardEffect, ctx: &EffectContext) -> bool {
+        let player = ctx.turn.current_player;
+        let targeting = self.targeting();
+        let own_ok = !targeting.needs_own_card()
+            || effect.own_card.is_some_and(|card_entity| ctx.hand_owner(card_entity) == Some(player));
+        let opponent_ok = match targeting {
+            Targeting::OpponentPlayer => effect.target_player.is_some_and(|target| target != player),
+            Targeting::OpponentCard | Targeting::OpponentAndOwnCard => effect.target_card
+                .and_then(|card_entity| ctx.hand_owner(card_entity))
+                .is_some_and(|owner| owner != player),
+            Targeting::Several(count) => effect.target_cards.len() == count as usize
+                && effect.target_cards.iter().enumerate().all(|(index, card_entity)| !effect.target_cards[..index].contains(card_entity)
+                    && ctx.hand_owner(*card_entity).is_some_and(|owner| owner != player)),
+            Targeting::Nothing | Targeting::OwnCard => true,
+        };
+        let frozen = self.exchanges_cards()
+            && [effect.own_card, effect.target_card].into_iter().flatten().chain(effect.target_cards.iter().copied()).any(|card_entity| ctx.is_frozen(card_entity));
+        own_ok && opponent_ok && !frozen
+    }
+
+    // change the table, Waiting keeps the effect until the player decides
+    fn apply(&self, effect: &mut SpecialCardEffect, ctx: &mut EffectContext) -> EffectProgress;
+
+    // targets chosen by the AI, None keeps the special unused
+    fn ai_targets(&self, view: &mut AiSpecialView) -> Option<SpecialTargets>;
+
+    // answer of the AI when the effect waits for a decision
+    fn ai_decide(&self, _memory: &mut AIMemory, _effect: &SpecialCardEffect) -> bool {
+        false
+    }
+}
@@ -1,16 +1,21 @@
 use bevy::prelude::*;
 use crate::game::gamestate::AppState;
+use crate::game::net::{is_net_client, is_lockstep};
+use crate::game::peek::is_peeking;
 
 pub mod resource;
+pub mod handler;
 mod effect;
 pub mod system;
-use system::{detect_special_card, handle_special_effects};
-use effect::{reveal_effect, shuffle_effect, swap_effect};
+use system::{detect_special_card, cancel_special_targeting, handle_special_effects, apply_special_effect};
+use resource::SpecialEffectRegistry;
 pub struct SpecialCardsPlugin;
 
 impl Plugin for SpecialCardsPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Update, (detect_special_card, handle_special_effects, reveal_effect, shuffle_effect, swap_effect)
+        // built-in effects, other plugins can register more in the registry
+        app.insert_resource(SpecialEffectRegistry::built_in())
+        .add_systems(Update, ((detect_special_card, cancel_special_targeting).run_if(not(is_net_client).and(not(is_lockstep)).and(not(is_peeking))), handle_special_effects, apply_special_effect)
         .run_if(in_state(AppState::PlayerTurn)));
     }
 }
\ No newline at end of file
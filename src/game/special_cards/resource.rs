@@ -1,4 +1,9 @@
 use bevy::prelude::{Resource, Entity};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde::de::Error;
+use std::sync::Mutex;
+use crate::game::special_cards::handler::{SpecialEffectHandler, Targeting, SpecialTargets};
+use crate::game::special_cards::effect::{RevealHandler, ShuffleHandler, SwapHandler, SkipHandler, FreezeHandler};
 
 #[derive(Resource, Default)]
 pub struct SpecialCardEffect {
@@ -8,12 +13,159 @@ pub struct SpecialCardEffect {
     pub target_player: Option<Entity>,
     pub target_card: Option<Entity>,
     pub awaiting_own_card: bool,
-    pub own_card: Option<Entity>
+    pub own_card: Option<Entity>,
+    pub target_cards: Vec<Entity>, // several: the opponent cards chosen so far
+    pub awaiting_decision: bool, // look and swap: both cards are shown, the player decides
+    pub swap_decided: Option<bool>,
 }
 
-#[derive(Debug, Clone)]
-pub enum SpecialEffect {
-    Shuffle,    // card 11
-    Reveal,     // card 9
-    Swap,       // card 7
+impl SpecialCardEffect {
+    // effect of a special card just activated, waiting for the targets it needs
+    pub fn new(card_entity: Entity, effect: SpecialEffect, targeting: Targeting) -> Self {
+        Self {
+            card_entity: Some(card_entity),
+            effect_type: Some(effect),
+            awaiting_target: targeting.needs_opponent_card(),
+            awaiting_own_card: targeting.needs_own_card(),
+            ..Default::default()
+        }
+    }
+
+    // effect with its targets already chosen (AI)
+    pub fn targeted(card_entity: Entity, effect: SpecialEffect, targets: SpecialTargets) -> Self {
+        Self {
+            card_entity: Some(card_entity),
+            effect_type: Some(effect),
+            target_player: targets.target_player,
+            target_card: targets.target_card,
+            own_card: targets.own_card,
+            target_cards: targets.target_cards,
+            ..Default::default()
+        }
+    }
+}
+
+// effect of a special card, the ruleset says which card value has each one
+// an open id: a new effect is a name with a label and a handler registered in the SpecialEffectRegistry
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SpecialEffect(&'static str);
+
+impl SpecialEffect {
+    pub const SHUFFLE: SpecialEffect = SpecialEffect("shuffle"); // shuffle an opponent's hand
+    pub const REVEAL: SpecialEffect = SpecialEffect("reveal"); // look at one of your cards
+    pub const SWAP: SpecialEffect = SpecialEffect("swap"); // blind swap: exchange one of your cards with an opponent card, without looking
+    pub const SPY: SpecialEffect = SpecialEffect("spy"); // look at one opponent card
+    pub const LOOK_AND_SWAP: SpecialEffect = SpecialEffect("look_and_swap"); // look at one of your cards and an opponent card, then decide whether to exchange them
+    pub const SKIP: SpecialEffect = SpecialEffect("skip"); // the next player loses their turn
+    pub const FREEZE: SpecialEffect = SpecialEffect("freeze"); // lock an opponent card, it can't be swapped this round
+
+    // longest name read from the network, and most names kept from it
+    const MAX_NAME: usize = 32;
+    const MAX_READ_NAMES: usize = 64;
+
+    pub const fn new(name: &'static str) -> Self {
+        Self(name)
+    }
+
+    // name in the bot protocol and on the server command line
+    pub fn name(&self) -> &'static str {
+        self.0
+    }
+}
+
+// names of the effects read from rules and saves, kept for the whole run
+static READ_NAMES: Mutex<Vec<&'static str>> = Mutex::new(Vec::new());
+
+impl Serialize for SpecialEffect {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.0)
+    }
+}
+
+// an effect unknown here is kept by name, the registry has no handler for it and it does nothing
+impl<'de> Deserialize<'de> for SpecialEffect {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        let mut read_names = READ_NAMES.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        if let Some(&known) = read_names.iter().find(|known| **known == name) {
+            return Ok(SpecialEffect(known));
+        }
+        if name.len() > Self::MAX_NAME || read_names.len() >= Self::MAX_READ_NAMES {
+            return Err(D::Error::custom(format!("unknown special effect {:?}", name)));
+        }
+        let name: &'static str = Box::leak(name.into_boxed_str());
+        read_names.push(name);
+        Ok(SpecialEffect(name))
+    }
+}
+
+// an effect with the label of the rules panel and its behaviour
+struct RegisteredEffect {
+    effect: SpecialEffect,
+    label: &'static str,
+    handler: Box<dyn SpecialEffectHandler>,
+}
+
+// handler of each special effect, in the order the rules panel cycles through them
+// built-in effects are registered by the SpecialCardsPlugin, other plugins can register more
+#[derive(Resource, Default)]
+pub struct SpecialEffectRegistry {
+    effects: Vec<RegisteredEffect>,
+}
+
+impl SpecialEffectRegistry {
+    // registry with the effects of the base game
+    pub fn built_in() -> Self {
+        let mut registry = Self::default();
+        registry.register(SpecialEffect::REVEAL, "REVEAL", RevealHandler { spy: false });
+        registry.register(SpecialEffect::SPY, "SPY", RevealHandler { spy: true });
+        registry.register(SpecialEffect::SWAP, "BLIND SWAP", SwapHandler { look: false });
+        registry.register(SpecialEffect::LOOK_AND_SWAP, "LOOK AND SWAP", SwapHandler { look: true });
+        registry.register(SpecialEffect::SHUFFLE, "SHUFFLE", ShuffleHandler);
+        registry.register(SpecialEffect::SKIP, "SKIP", SkipHandler);
+        registry.register(SpecialEffect::FREEZE, "FREEZE", FreezeHandler);
+        registry
+    }
+
+    // a name registered again keeps its place with the new label and handler
+    pub fn register(&mut self, effect: SpecialEffect, label: &'static str, handler: impl SpecialEffectHandler + 'static) {
+        let registered = RegisteredEffect { effect, label, handler: Box::new(handler) };
+        match self.effects.iter_mut().find(|other| other.effect == effect) {
+            Some(other) => *other = registered,
+            None => self.effects.push(registered),
+        }
+    }
+
+    pub fn get(&self, effect: SpecialEffect) -> Option<&dyn SpecialEffectHandler> {
+        self.effects.iter().find(|registered| registered.effect == effect).map(|registered| registered.handler.as_ref())
+    }
+
+    // registered effects, in registration order
+    pub fn effects(&self) -> impl Iterator<Item = SpecialEffect> + '_ {
+        self.effects.iter().map(|registered| registered.effect)
+    }
+
+    // registered effect with a name, None for an unknown name
+    pub fn find(&self, name: &str) -> Option<SpecialEffect> {
+        self.effects().find(|effect| effect.name() == name)
+    }
+
+    // name in the rules panel, the bare name for an effect without handler
+    pub fn label(&self, effect: SpecialEffect) -> &'static str {
+        self.effects.iter().find(|registered| registered.effect == effect).map_or(effect.name(), |registered| registered.label)
+    }
+
+    // what the player clicks for an effect, nothing for an effect without handler
+    pub fn targeting(&self, effect: SpecialEffect) -> Targeting {
+        self.get(effect).map_or(Targeting::Nothing, |handler| handler.targeting())
+    }
+
+    // frozen cards can't be chosen for an effect that moves cards between hands
+    pub fn exchanges_cards(&self, effect: SpecialEffect) -> bool {
+        self.get(effect).is_some_and(|handler| handler.exchanges_cards())
+    }
+
+    pub fn asks_decision(&self, effect: SpecialEffect) -> bool {
+        self.get(effect).is_some_and(|handler| handler.asks_decision())
+    }
 }
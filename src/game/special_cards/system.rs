@@ -1,16 +1,31 @@
 use bevy::prelude::*;
 use crate::game::{card::component::{Card, CardPosition}, turn_player::component::Turn};
-use crate::game::special_cards::resource::{SpecialCardEffect, SpecialEffect};
+use crate::game::match_config::resource::Viewer;
+use crate::game::special_cards::resource::{SpecialCardEffect, SpecialEffectRegistry};
+use crate::game::special_cards::handler::{EffectContext, EffectProgress};
+use crate::game::rules::resource::Ruleset;
 
 pub fn detect_special_card(
     mut commands: Commands,
     card_query: Query<(Entity, &Card)>,
     keyboard: Res<ButtonInput<KeyCode>>,
     turn_query: Res<Turn>,
-    special_effect: Option<Res<SpecialCardEffect>>,
+    special_effect: Option<ResMut<SpecialCardEffect>>,
+    viewer: Res<Viewer>,
+    (ruleset, registry): (Res<Ruleset>, Res<SpecialEffectRegistry>),
 ) {
-    // verify if have effect
-    if special_effect.as_ref().map_or(false, |s| s.awaiting_target) {
+    // only the player looking at the screen activates specials with the keyboard
+    if viewer.0 != Some(turn_query.current_player) { return; }
+
+    // look and swap: both cards are shown, Y swaps them and N keeps them
+    if let Some(mut effect) = special_effect.filter(|effect| effect.awaiting_target || effect.awaiting_own_card || effect.awaiting_decision) {
+        if effect.awaiting_decision && effect.swap_decided.is_none() {
+            if keyboard.just_pressed(KeyCode::KeyY) {
+                effect.swap_decided = Some(true);
+            } else if keyboard.just_pressed(KeyCode::KeyN) {
+                effect.swap_decided = Some(false);
+            }
+        }
         return;
     }
 
@@ -26,28 +41,12 @@ pub fn detect_special_card(
         });
 
     if let Some((card_entity, card)) = drawn_card {
-        // match if drawn card is special card
-        let special_effect_type = match card.value {
-            11 => Some(SpecialEffect::Shuffle),
-            9 => Some(SpecialEffect::Reveal), 
-            7 => Some(SpecialEffect::Swap),
-            _ => None,
-        };
-        
-        if let Some(effect) = special_effect_type {
+        // effect of the card value in the ruleset
+        if let Some(effect) = ruleset.special_for(card.value) {
             info!(target: "mygame", "Special card {} activated! Effect: {:?}", card.value, effect);
             
-            // create/update special effect
-            let new_effect = SpecialCardEffect {
-                card_entity: Some(card_entity),
-                effect_type: Some(effect.clone()),
-                awaiting_target: matches!(effect, SpecialEffect::Shuffle | SpecialEffect::Swap),
-                target_player: None,
-                target_card: None,
-                awaiting_own_card: matches!(effect, SpecialEffect::Swap),
-                own_card: None
-            };
-            commands.insert_resource(new_effect);
+            // create/update special effect, waiting for the targets it needs
+            commands.insert_resource(SpecialCardEffect::new(card_entity, effect, registry.targeting(effect)));
             return;
         }
     } else {
@@ -61,22 +60,69 @@ pub fn handle_special_effects(
 ) {
     // run if resource exist
     if let Some(effect) = special_effect {
-        if let Some(effect_type) = &effect.effect_type.clone() {
-            match effect_type {
-                SpecialEffect::Reveal => {
-                    info!(target: "mygame", "Reveal effect active");
-                },
-                SpecialEffect::Shuffle => {
-                    if effect.awaiting_target {
-                        info!(target: "mygame", "Waiting for target selection for shuffle effect...");
-                    }
-                },
-                SpecialEffect::Swap => {
-                    if effect.awaiting_target {
-                        info!(target: "mygame", "Waiting for target selection for swap effect...");
-                    }
-                },
+        if let Some(effect_type) = effect.effect_type {
+            if effect.awaiting_target {
+                info!(target: "mygame", "Waiting for target selection for {:?} effect...", effect_type);
+            } else if effect.awaiting_own_card {
+                info!(target: "mygame", "Waiting for own card selection for {:?} effect...", effect_type);
+            } else if effect.awaiting_decision && effect.swap_decided.is_none() {
+                info!(target: "mygame", "Waiting for the swap decision of {:?} effect...", effect_type);
             }
         }
     }
-}
\ No newline at end of file
+}
+
+// Esc or a right click backs out of choosing the targets, the drawn card can be swapped or discarded again
+pub fn cancel_special_targeting(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mouse_input: Res<ButtonInput<MouseButton>>,
+    special_effect: Option<ResMut<SpecialCardEffect>>,
+    turn_query: Res<Turn>,
+    viewer: Res<Viewer>,
+) {
+    if viewer.0 != Some(turn_query.current_player) { return; }
+    let Some(mut effect) = special_effect.filter(|effect| effect.awaiting_target || effect.awaiting_own_card) else { return; };
+
+    if cancel_pressed(&keyboard, &mouse_input) {
+        info!(target: "mygame", "Special {:?} cancelled, choose again what to do with the card", effect.effect_type);
+        *effect = SpecialCardEffect::default();
+    }
+}
+
+// auxiliar function - keys that back out of choosing the targets of a special
+pub fn cancel_pressed(keyboard: &ButtonInput<KeyCode>, mouse_input: &ButtonInput<MouseButton>) -> bool {
+    keyboard.just_pressed(KeyCode::Escape) || mouse_input.just_pressed(MouseButton::Right)
+}
+
+// apply the effect with its handler once the targets are chosen, invalid targets waste the special
+pub fn apply_special_effect(
+    special_effect: Option<ResMut<SpecialCardEffect>>,
+    registry: Res<SpecialEffectRegistry>,
+    mut ctx: EffectContext,
+) {
+    let Some(mut effect) = special_effect else { return; };
+    let Some(effect_type) = effect.effect_type else { return; };
+
+    // wait for the player to choose the targets
+    if effect.awaiting_target || effect.awaiting_own_card { return; }
+
+    match registry.get(effect_type) {
+        Some(handler) => {
+            // the targets are checked once, before the effect changes the table
+            if !effect.awaiting_decision && !handler.validate(&effect, &ctx) {
+                warn!(target: "mygame", "Invalid targets for {:?} effect", effect_type);
+            } else if handler.apply(&mut effect, &mut ctx) == EffectProgress::Waiting {
+                return;
+            }
+        }
+        None => warn!(target: "mygame", "No handler registered for {:?} effect", effect_type),
+    }
+
+    // reset from_deck so that the effect is used only once
+    if let Some(special_card_entity) = effect.card_entity && let Ok((_, _, mut special_card, _)) = ctx.cards.get_mut(special_card_entity) {
+        special_card.from_deck = false;
+    }
+
+    *effect = SpecialCardEffect::default();
+    info!(target: "mygame", "Effect completed");
+}
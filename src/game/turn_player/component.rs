@@ -4,4 +4,6 @@ use bevy::prelude::*;
 pub struct Turn {
     pub current_player: Entity, // current player's turn
     pub has_drawn_card: bool, // if player has drawn a card
+    pub skip_next: bool, // a skip was played, the next player loses their turn
+    pub number: u32, // turns played this round, it changes even when the same player plays again
 }
\ No newline at end of file
@@ -0,0 +1,17 @@
+use bevy::prelude::*;
+
+// messages of the actions a player takes during their turn, so other systems (AI memory) can observe them
+#[derive(Event, Message, Clone, Debug)]
+pub struct TurnAction {
+    pub player: Entity,
+    pub kind: TurnActionKind,
+}
+
+#[derive(Clone, Debug)]
+pub enum TurnActionKind {
+    // drawn card sent to the graveyard without swapping
+    Discard { value: u8, from_deck: bool },
+    // drawn card swapped into the hand, the replaced card goes face-up to the graveyard
+    // drawn_value is only public when the card was taken from the graveyard
+    Swap { drawn_card: Entity, drawn_value: Option<u8>, replaced_card: Entity, replaced_value: u8 },
+}
@@ -2,17 +2,22 @@ use bevy::prelude::*;
 use crate::game::gamestate::AppState;
 
 pub mod component;
+pub mod event;
 mod system;
 
 use system::{start_turn_system, end_round_system};
+use event::TurnAction;
 use crate::game::player::system::spawn_player;
+use crate::game::net::{is_net_client, is_lockstep};
+use crate::game::peek::is_peeking;
 
 pub struct TurnPlugin;
 
 impl Plugin for TurnPlugin {
     fn build(&self, app: &mut App) {
         app
+            .add_message::<TurnAction>()
             .add_systems(OnEnter(AppState::Setup), (spawn_player, start_turn_system).chain())
-            .add_systems(Update, end_round_system.run_if(in_state(AppState::PlayerTurn)));
+            .add_systems(Update, end_round_system.run_if(in_state(AppState::PlayerTurn).and(not(is_net_client)).and(not(is_lockstep)).and(not(is_peeking))));
     }
-}
\ No newline at end of file
+}
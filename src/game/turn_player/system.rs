@@ -1,17 +1,26 @@
 use bevy::prelude::*;
 use crate::game::{player::component::Player, gamestate::AppState};
+use crate::game::net::resource::NetSession;
+use crate::game::match_config::resource::Viewer;
+use crate::game::round_end::resource::RoundCaller;
 use super::component::Turn;
 
-// start first player turn
+// start first player turn, the first seat of the host
 pub fn start_turn_system(
     mut commands: Commands,
-    players: Query<Entity, With<Player>>,
+    players: Query<(Entity, &Player)>,
     mut next_state: ResMut<NextState<AppState>>,
+    net_session: Res<NetSession>,
 ) {
-    if let Some(first_player) = players.iter().next() {
+    let first_player = players.iter()
+        .min_by_key(|(_, player)| net_session.host_seat(player.seat))
+        .map(|(entity, _)| entity);
+    if let Some(first_player) = first_player {
         commands.insert_resource(Turn {
             current_player: first_player,
             has_drawn_card: false,
+            skip_next: false,
+            number: 0,
         });
         next_state.set(AppState::PlayerTurn);
         info!(target: "mygame","Shift started for player: {:?}", first_player);
@@ -20,12 +29,16 @@ pub fn start_turn_system(
     }
 }
 
-// finish game round
+// finish game round, only the player at this screen on their own turn can call it (the peek is over)
 pub fn end_round_system(
     keyboard: Res<ButtonInput<KeyCode>>,
+    viewer: Res<Viewer>,
+    turn: Res<Turn>,
+    mut round_caller: ResMut<RoundCaller>,
     mut next_state: ResMut<NextState<AppState>>,
 ) {
-    if keyboard.just_pressed(KeyCode::KeyR) {
+    if keyboard.just_pressed(KeyCode::KeyR) && viewer.0 == Some(turn.current_player) {
+            round_caller.0 = viewer.0;
             next_state.set(AppState::RoundEnd);
             info!(target: "mygame", "Round ended by player!");
     }
@@ -0,0 +1,29 @@
+use bevy::prelude::*;
+use crate::game::gamestate::AppState;
+use crate::game::net::{is_net_client, is_lockstep};
+use crate::game::peek::is_peeking;
+
+pub mod resource;
+mod system;
+
+use resource::{TurnTimeLimit, TurnClock};
+use system::{start_turn_clock, tick_turn_clock, play_timed_out_turn};
+
+// optional time limit of each turn, a player that runs out of time draws and discards automatically
+pub struct TurnTimerPlugin;
+
+impl Plugin for TurnTimerPlugin {
+    fn build(&self, app: &mut App) {
+        app
+        .init_resource::<TurnTimeLimit>()
+        .init_resource::<TurnClock>()
+        .add_systems(OnEnter(AppState::Setup), start_turn_clock)
+        // LAN clients only show the clock, the host (or the owner of the seat in lockstep) plays the timeout
+        .add_systems(
+            Update,
+            (tick_turn_clock, play_timed_out_turn.run_if(not(is_net_client).and(not(is_lockstep))))
+            .chain()
+            .run_if(in_state(AppState::PlayerTurn).and(not(is_peeking)))
+        );
+    }
+}
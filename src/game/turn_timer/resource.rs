@@ -0,0 +1,43 @@
+use bevy::prelude::*;
+
+// seconds of each turn chosen in the menu or the lobby, None plays without a clock
+#[derive(Resource, Default, Clone, Copy, PartialEq)]
+pub struct TurnTimeLimit(pub Option<f32>);
+
+impl TurnTimeLimit {
+    pub const OPTIONS: [Option<f32>; 4] = [None, Some(15.0), Some(30.0), Some(60.0)];
+
+    // cycle between the options
+    pub fn next(&self) -> Self {
+        let index = Self::OPTIONS.iter().position(|option| *option == self.0).map_or(0, |index| (index + 1) % Self::OPTIONS.len());
+        Self(Self::OPTIONS[index])
+    }
+
+    pub fn label(&self) -> String {
+        match self.0 {
+            Some(seconds) => format!("TURN TIMER: {}s", seconds),
+            None => String::from("TURN TIMER: OFF"),
+        }
+    }
+}
+
+// clock of the current turn
+#[derive(Resource, Default)]
+pub struct TurnClock {
+    pub limit: Option<f32>, // seconds of each turn in this match (the one of the host on a LAN client)
+    pub turn: Option<u32>, // number of the timed turn
+    pub timed: bool, // AI players never stall, their turns are not timed
+    pub remaining: f32,
+}
+
+impl TurnClock {
+    // part of the turn left, from 1 to 0
+    pub fn fraction(&self) -> Option<f32> {
+        let limit = self.limit.filter(|_| self.timed)?;
+        Some((self.remaining / limit).clamp(0.0, 1.0))
+    }
+
+    pub fn is_expired(&self) -> bool {
+        self.fraction().is_some_and(|fraction| fraction <= 0.0)
+    }
+}
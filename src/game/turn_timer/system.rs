@@ -0,0 +1,176 @@
+use bevy::prelude::*;
+use rand::seq::{IndexedRandom, SliceRandom};
+use crate::game::turn_timer::resource::{TurnTimeLimit, TurnClock};
+use crate::game::ai::component::AIPlayer;
+use crate::game::bot::component::{BotPlayer, BotState};
+use crate::game::card::component::{Card, CardPosition, Selected};
+use crate::game::card::utils::{card_swap, discard_card, free_hand_cards};
+use crate::game::card::refactor_handles::{handle_deck_logic, handle_graveyard_logic};
+use crate::game::{deck::component::Deck, graveyard::component::Graveyard, hand::component::Hand, player::component::Player, turn_player::component::Turn};
+use crate::game::turn_player::event::TurnAction;
+use crate::game::special_cards::resource::{SpecialCardEffect, SpecialEffectRegistry};
+use crate::game::special_cards::handler::Targeting;
+use crate::game::match_config::resource::GameSpeed;
+use crate::game::rules::resource::Ruleset;
+use crate::game::layout::TableLayout;
+use crate::game::net::resource::NetSession;
+use crate::ui::soundtrack::event::{PlayCardDraw, PlayCardPlace};
+
+// the limit chosen here, a LAN client keeps the one of the host given in the welcome
+pub fn start_turn_clock(
+    mut clock: ResMut<TurnClock>,
+    limit: Res<TurnTimeLimit>,
+    net_session: Res<NetSession>,
+) {
+    let limit = if net_session.is_client() { clock.limit } else { limit.0 };
+    *clock = TurnClock { limit, ..default() };
+}
+
+// restart the clock on every new turn, also a skip that gives the turn back to the same player,
+// it stops while the game is paused
+pub fn tick_turn_clock(
+    mut clock: ResMut<TurnClock>,
+    turn: Res<Turn>,
+    ai_query: Query<(), With<AIPlayer>>,
+    game_speed: Res<GameSpeed>,
+    time: Res<Time>,
+) {
+    let Some(limit) = clock.limit else { return; };
+
+    if clock.turn != Some(turn.number) {
+        clock.turn = Some(turn.number);
+        clock.timed = !ai_query.contains(turn.current_player);
+        clock.remaining = limit;
+        return;
+    }
+
+    if game_speed.is_running() {
+        clock.remaining = (clock.remaining - time.delta_secs()).max(0.0);
+    }
+}
+
+// out of time: give random targets to a waiting special, else draw from the deck and discard the drawn card
+// (a card taken from the graveyard replaces a random card when the rules forbid discarding it)
+pub fn play_timed_out_turn(
+    mut commands: Commands,
+    clock: Res<TurnClock>,
+    mut turn: ResMut<Turn>,
+    mut card_query: Query<(Entity, &mut Transform, &mut Card), With<Card>>,
+    mut graveyard_query: Query<&mut Graveyard>,
+    deck_query: Query<&mut Deck>,
+    player_query: Query<(Entity, &Player)>,
+    hand_query: Query<&mut Hand>,
+    (special_effect, registry): (Option<ResMut<SpecialCardEffect>>, Res<SpecialEffectRegistry>),
+    (layout, ruleset): (Res<TableLayout>, Res<Ruleset>),
+    selected_query: Query<Entity, With<Selected>>,
+    mut bot_query: Query<(&BotPlayer, &mut BotState)>,
+    (draw_message, place_message, action_message): (MessageWriter<PlayCardDraw>, MessageWriter<PlayCardPlace>, MessageWriter<TurnAction>),
+) {
+    if !clock.is_expired() { return; }
+    let player = turn.current_player;
+
+    // the effect systems finish the special once it has its targets
+    if let Some(mut effect) = special_effect && let Some(effect_type) = effect.effect_type {
+        let exchanges_cards = registry.exchanges_cards(effect_type);
+        if fill_random_targets(&mut effect, (registry.targeting(effect_type), exchanges_cards), player, &player_query, &hand_query.as_readonly(), &card_query) {
+            return;
+        }
+        *effect = SpecialCardEffect::default();
+    }
+
+    let drawn_card = card_query.iter()
+        .find(|(_, _, card)| card.position == CardPosition::DrawnCard(player))
+        .map(|(entity, _, _)| entity);
+
+    match drawn_card {
+        Some(drawn_card) => {
+            let must_swap = card_query.get(drawn_card).is_ok_and(|(_, _, card)| ruleset.must_swap(card));
+            let free_cards = player_query.get(player).ok()
+                .and_then(|(_, player)| hand_query.get(player.hand).ok())
+                .map(|hand| free_hand_cards(hand, &card_query))
+                .unwrap_or_default();
+            if must_swap && let Some(&target) = free_cards.choose(&mut rand::rng()) {
+                info!(target: "mygame", "Out of time, swapping the card taken from the graveyard");
+                card_swap(target, &mut card_query, &mut graveyard_query, turn, hand_query, &player_query, &layout, &mut commands, &selected_query, action_message);
+            } else {
+                info!(target: "mygame", "Out of time, discarding the drawn card");
+                discard_card(drawn_card, &mut card_query, &mut graveyard_query, turn, &player_query, &mut commands, &selected_query, place_message, action_message);
+            }
+        }
+        None => {
+            info!(target: "mygame", "Out of time, drawing a card");
+            if deck_query.single().is_ok_and(|deck| !deck.cards_values.is_empty()) {
+                handle_deck_logic(deck_query, turn, card_query, draw_message, player);
+            } else {
+                // with the deck empty the graveyard is the only draw left, even the card just discarded
+                handle_graveyard_logic(&mut graveyard_query, &mut turn, &mut card_query, player);
+            }
+
+            // a bot goes on from the drawn card, it must not draw again
+            if let Ok((_, mut state)) = bot_query.get_mut(player) {
+                *state = BotState::ExecutingDraw;
+            }
+        }
+    }
+
+    // late replies of a bot answer a question that is over
+    if let Ok((bot, _)) = bot_query.get(player) {
+        while bot.link.try_recv().is_ok() {}
+    }
+}
+
+// auxiliar function - random targets for a special still waiting for them, false when there are none
+// frozen cards are left out when the special exchanges cards
+fn fill_random_targets(
+    effect: &mut SpecialCardEffect,
+    (targeting, exchanges_cards): (Targeting, bool),
+    player: Entity,
+    player_query: &Query<(Entity, &Player)>,
+    hand_query: &Query<&Hand>,
+    card_query: &Query<(Entity, &mut Transform, &mut Card), With<Card>>,
+) -> bool {
+    let mut rng = rand::rng();
+    let hand_of = |owner: Entity| -> Vec<Entity> {
+        player_query.get(owner).ok()
+            .and_then(|(_, owner_player)| hand_query.get(owner_player.hand).ok())
+            .map(|hand| if exchanges_cards { free_hand_cards(hand, card_query) } else { hand.cards.clone() })
+            .unwrap_or_default()
+    };
+    let opponents: Vec<Entity> = player_query.iter()
+        .map(|(entity, _)| entity)
+        .filter(|&entity| entity != player && !hand_of(entity).is_empty())
+        .collect();
+
+    // out of time before deciding, the cards stay where they are
+    if effect.awaiting_decision {
+        effect.swap_decided.get_or_insert(false);
+        return true;
+    }
+
+    if targeting == Targeting::OpponentPlayer && effect.target_player.is_none() {
+        let Some(&target) = opponents.choose(&mut rng) else { return false; };
+        effect.target_player = Some(target);
+    }
+    if matches!(targeting, Targeting::OpponentCard | Targeting::OpponentAndOwnCard) && effect.target_card.is_none() {
+        let Some(&target) = opponents.choose(&mut rng) else { return false; };
+        effect.target_card = hand_of(target).choose(&mut rng).copied();
+        if effect.target_card.is_none() { return false; }
+    }
+    if let Targeting::Several(count) = targeting {
+        let mut free_cards: Vec<Entity> = opponents.iter()
+            .flat_map(|&opponent| hand_of(opponent))
+            .filter(|card_entity| !effect.target_cards.contains(card_entity))
+            .collect();
+        free_cards.shuffle(&mut rng);
+        let missing = (count as usize).saturating_sub(effect.target_cards.len());
+        if free_cards.len() < missing { return false; }
+        effect.target_cards.extend(free_cards.into_iter().take(missing));
+    }
+    if targeting.needs_own_card() && effect.own_card.is_none() {
+        effect.own_card = hand_of(player).choose(&mut rng).copied();
+        if effect.own_card.is_none() { return false; }
+    }
+    effect.awaiting_target = false;
+    effect.awaiting_own_card = false;
+    true
+}
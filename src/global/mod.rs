@@ -1,6 +1,19 @@
 use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+
+use crate::game::layout::TableLayout;
 
 // create camera
 pub fn setup_camera(mut commands: Commands) {
     commands.spawn(Camera2d::default());
-}
\ No newline at end of file
+}
+
+// keep the table size in sync with the window
+pub fn sync_table_layout(
+    windows: Query<&Window, (With<PrimaryWindow>, Changed<Window>)>,
+    mut layout: ResMut<TableLayout>,
+) {
+    let Ok(window) = windows.single() else { return; };
+    layout.width = window.width();
+    layout.height = window.height();
+}
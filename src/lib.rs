@@ -0,0 +1,3 @@
+pub mod global;
+pub mod game;
+pub mod ui;
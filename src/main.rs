@@ -1,12 +1,11 @@
 use bevy::prelude::*;
 
-mod global;
-mod game;
-mod ui;
-
-use game::GamePlugin;
-use ui::UiPlugin;
-use global::setup_camera;
+use gamename_test::game::GamePlugin;
+use gamename_test::ui::UiPlugin;
+use gamename_test::global::{setup_camera, sync_table_layout};
+use gamename_test::game::bot::component::BotCommands;
+use gamename_test::game::net::resource::NetOptions;
+use gamename_test::game::ai::component::AIThinkSettings;
 
 fn main() {
     App::new()
@@ -19,7 +18,11 @@ fn main() {
         }),
         ..default()
     }))
+    .insert_resource(BotCommands::from_args(std::env::args().skip(1)))
+    .insert_resource(NetOptions::from_args(std::env::args().skip(1)))
+    .insert_resource(AIThinkSettings::from_args(std::env::args().skip(1)))
     .add_systems(Startup, setup_camera)
+    .add_systems(PreUpdate, sync_table_layout)
     .add_plugins(GamePlugin)
     .add_plugins(UiPlugin)
     .run();
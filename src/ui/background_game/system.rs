@@ -57,17 +57,14 @@ pub fn update_all_positions(
         // Update cards positions in hand
         for (_player_entity, player) in player_query.iter() {
             if let Ok(hand) = hand_query.get(player.hand) {
-                // determine player
-                let player_index = if player.is_local_player { 0 } else { 1 };
-
                 // obtain window and new positions
                 let Ok(window) = window.single() else { return; };
-                let positions = get_player_positions(player_index, window.width(), window.height());
+                let positions = get_player_positions(player.seat, hand.cards.len(), window.width(), window.height());
 
                 // update each card
                 for (card_index, &card_entity) in hand.cards.iter().enumerate() {
                     if let Ok((mut transform, card)) = card_query.get_mut(card_entity) {
-                        if matches!(card.position, CardPosition::Hand(_)) && card_index < 4 {
+                        if matches!(card.position, CardPosition::Hand(_)) {
                             transform.translation = positions[card_index];
                         }
                     }
@@ -1,11 +1,14 @@
 use bevy::prelude::*;
 use crate::game::card::component::Card;
+use crate::game::match_config::resource::{GameSpeed, Viewer};
 use crate::ui::card_animation::component::{CardAnimation, AnimationType, AnimationState};
 
 pub fn animate_deal(
     mut commands: Commands,
     mut card_query: Query<(Entity, &mut Transform, &mut Sprite, &mut CardAnimation, &mut Card)>,
     time: Res<Time>,
+    game_speed: Res<GameSpeed>,
+    viewer: Res<Viewer>,
 ) {
     for (entity, mut transform, mut sprite, mut animation, mut card) in card_query.iter_mut() {
         
@@ -15,7 +18,7 @@ pub fn animate_deal(
             if animation.delay_elapsed < animation.delay {
                 transform.translation.z = -10.0; // keep Z down
                 sprite.color = Color::srgba(1.0, 1.0, 1.0, 0.0);
-                animation.delay_elapsed += time.delta_secs();
+                animation.delay_elapsed += game_speed.delta_secs(&time);
                 continue;
             }
             
@@ -31,7 +34,7 @@ pub fn animate_deal(
                 transform.translation = animation.original_position.lerp(target, t);
                 
                 // Update progress
-                animation.progress += time.delta_secs() / animation.duration;
+                animation.progress += game_speed.delta_secs(&time) / animation.duration;
                 
                 // when animation finish
                 if animation.progress >= 1.0 {
@@ -40,7 +43,7 @@ pub fn animate_deal(
                     transform.translation.z = 10.0;
                     
                     // to do flip
-                    if card.face_up {
+                    if viewer.can_see(&card) {
                         commands.entity(entity).insert(CardAnimation {
                             animation_type: AnimationType::Flip,
                             progress: 0.0,
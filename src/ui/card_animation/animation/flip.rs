@@ -1,14 +1,18 @@
 use bevy::prelude::*;
 use crate::game::card::component::{Card, CardPosition, PreviousCardPosition, PreviousFaceUp};
+use crate::game::match_config::resource::{GameSpeed, Viewer};
 use crate::ui::card_animation::component::{CardAnimation, AnimationState, AnimationType};
 
-// detect when face_up change
+// detect when the viewer starts or stops seeing a card of a hand
 pub fn detect_flip(
     mut commands: Commands,
     card_query: Query<(Entity, &Card, &Transform, Option<&PreviousCardPosition>, Option<&PreviousFaceUp>)>,
     animation_query: Query<&CardAnimation>,
+    viewer: Res<Viewer>,
 ) {
     for (entity, card, transform, previous_pos, previous_face) in card_query.iter() {
+        let face_up = viewer.can_see(card);
+
         // verify if is animating already
         if animation_query.get(entity).is_ok() {
             continue;
@@ -20,7 +24,7 @@ pub fn detect_flip(
             None => {
                 // save position
                 commands.entity(entity).insert(PreviousCardPosition(card.position.clone()));
-                commands.entity(entity).insert(PreviousFaceUp(card.face_up));
+                commands.entity(entity).insert(PreviousFaceUp(face_up));
                 continue;
             }
         };
@@ -29,14 +33,14 @@ pub fn detect_flip(
         let prev_face = match previous_face {
             Some(p) => p.0,
             None => {
-                commands.entity(entity).insert(PreviousFaceUp(card.face_up));
+                commands.entity(entity).insert(PreviousFaceUp(face_up));
                 continue;
             }
         };
 
         // verify if have changes
         let position_changed = prev_pos != &card.position;
-        let face_changed = prev_face != card.face_up;
+        let face_changed = prev_face != face_up;
 
         if !position_changed && !face_changed {
             continue; // do nothing if not changed
@@ -44,7 +48,9 @@ pub fn detect_flip(
 
         // detect when the card must animate
         let should_animate = match (prev_pos, &card.position) {
-            _ if !prev_face && card.face_up && matches!(card.position, CardPosition::Hand(_)) => true,
+            _ if !prev_face && face_up && matches!(card.position, CardPosition::Hand(_)) => true,
+            // a card shown for a while is turned face-down again where it is
+            _ if prev_face && !face_up && !position_changed && matches!(card.position, CardPosition::Hand(_)) => true,
             _ => false,
         };
         
@@ -69,7 +75,7 @@ pub fn detect_flip(
             commands.entity(entity).insert(PreviousCardPosition(card.position.clone()));
         }
         if face_changed {
-            commands.entity(entity).insert(PreviousFaceUp(card.face_up));
+            commands.entity(entity).insert(PreviousFaceUp(face_up));
         }
     }
 }
@@ -77,7 +83,8 @@ pub fn detect_flip(
 pub fn animate_flip(
     mut commands: Commands,
     mut card_query: Query<(Entity, &mut Transform, &mut CardAnimation), With<CardAnimation>>,
-    time: Res<Time>,   
+    time: Res<Time>,
+    game_speed: Res<GameSpeed>,
 ) {
     for (entity, mut transform, mut animation) in card_query.iter_mut() {
         if animation.animation_type == AnimationType::Flip {
@@ -120,7 +127,7 @@ pub fn animate_flip(
                 transform.translation.z = animation.original_position.z + ((1.0 - t) * 5.0);
             }
 
-            animation.progress += time.delta_secs() / animation.duration;
+            animation.progress += game_speed.delta_secs(&time) / animation.duration;
             
             if animation.progress >= 1.0 {
                 transform.scale = animation.original_scale;
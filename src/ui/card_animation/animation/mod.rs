@@ -2,10 +2,12 @@ use bevy::prelude::*;
 use crate::ui::card_animation::animation::flip::{detect_flip, animate_flip};
 use crate::ui::card_animation::animation::movement::{detect_movement, animate_movement};
 use crate::ui::card_animation::animation::deal::animate_deal;
+use crate::ui::card_animation::animation::shake::animate_shake;
 
 mod flip;
 mod deal;
 mod movement;
+mod shake;
 
 pub struct AnimatePlugin;
 
@@ -14,6 +16,6 @@ impl Plugin for AnimatePlugin {
         app
         .add_systems(Update, (detect_flip, detect_movement))
         .add_systems(Update, (animate_flip, animate_movement).after(detect_flip).after(detect_movement))
-        .add_systems(Update, animate_deal);
+        .add_systems(Update, (animate_deal, animate_shake));
     }
 }
\ No newline at end of file
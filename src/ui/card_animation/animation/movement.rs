@@ -1,6 +1,7 @@
 use bevy::prelude::*;
 use bevy::window::PrimaryWindow;
 use crate::game::card::component::{Card, PreviousCardPosition, CardPosition, PreviousTranslation};
+use crate::game::match_config::resource::GameSpeed;
 use crate::ui::card_animation::component::{CardAnimation, AnimationState, AnimationType};
 use crate::game::graveyard::component::Graveyard;
 
@@ -126,6 +127,7 @@ pub fn animate_movement(
     mut commands: Commands,
     mut card_query: Query<(Entity, &mut Transform, &mut CardAnimation, &mut Card)>,
     time: Res<Time>,
+    game_speed: Res<GameSpeed>,
 ) {
     for (entity, mut transform, mut animation, mut card) in card_query.iter_mut() {
         if animation.animation_type == AnimationType::Movement {
@@ -136,7 +138,7 @@ pub fn animate_movement(
                 transform.translation = animation.original_position.lerp(target, t);
 
                 // update progress
-                animation.progress += time.delta_secs() / animation.duration;
+                animation.progress += game_speed.delta_secs(&time) / animation.duration;
                 
                 // when the progress has finished, remove card animation
                 if animation.progress >= 1.0 {
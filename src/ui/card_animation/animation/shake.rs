@@ -0,0 +1,26 @@
+use bevy::prelude::*;
+use std::f32::consts::TAU;
+use crate::game::match_config::resource::GameSpeed;
+use crate::ui::card_animation::component::{CardAnimation, AnimationType};
+
+// a card hit by a skip or a freeze wiggles in place
+pub fn animate_shake(
+    mut commands: Commands,
+    mut card_query: Query<(Entity, &mut Transform, &mut CardAnimation)>,
+    time: Res<Time>,
+    game_speed: Res<GameSpeed>,
+) {
+    for (entity, mut transform, mut animation) in card_query.iter_mut() {
+        if animation.animation_type != AnimationType::Shake { continue; }
+
+        // four wiggles that fade out
+        let offset = (animation.progress * TAU * 4.0).sin() * 8.0 * (1.0 - animation.progress);
+        transform.translation.x = animation.original_position.x + offset;
+
+        animation.progress += game_speed.delta_secs(&time) / animation.duration;
+        if animation.progress >= 1.0 {
+            transform.translation = animation.original_position;
+            commands.entity(entity).remove::<CardAnimation>();
+        }
+    }
+}
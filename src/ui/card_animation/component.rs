@@ -14,11 +14,46 @@ pub struct CardAnimation {
     pub delay_elapsed: f32,
 }
 
+impl CardAnimation {
+    // wiggle in place, the card stays where it is
+    pub fn shake(transform: &Transform) -> Self {
+        Self {
+            animation_type: AnimationType::Shake,
+            progress: 0.0,
+            duration: 0.5,
+            state: AnimationState::Animating,
+            original_scale: transform.scale,
+            original_position: transform.translation,
+            original_rotation: transform.rotation,
+            target_position: None,
+            delay: 0.0,
+            delay_elapsed: 0.0,
+        }
+    }
+
+    // slide from where the card is to a new position
+    pub fn movement(transform: &Transform, target: Vec3) -> Self {
+        Self {
+            animation_type: AnimationType::Movement,
+            progress: 0.0,
+            duration: 0.25,
+            state: AnimationState::Animating,
+            original_scale: transform.scale,
+            original_position: transform.translation,
+            original_rotation: transform.rotation,
+            target_position: Some(target),
+            delay: 0.0,
+            delay_elapsed: 0.0,
+        }
+    }
+}
+
 #[derive(PartialEq)]
 pub enum AnimationType {
     Flip,
     Movement,
     Deal,
+    Shake,
 }
 
 #[derive(PartialEq)]
@@ -0,0 +1,37 @@
+use bevy::prelude::*;
+use std::collections::HashSet;
+use crate::game::chat::event::Emote;
+
+// parts of the quick chat panel, bottom left of the table
+#[derive(Component, Clone, Copy, PartialEq)]
+pub enum ChatSection {
+    Panel, // whole panel, hidden for spectators
+    Wheel, // rows shown only while the wheel is open
+}
+
+// free text being typed, or the key to start typing
+#[derive(Component)]
+pub struct ChatTypingText;
+
+// chat text buttons
+#[derive(Component, Clone, Copy, PartialEq)]
+pub enum ChatButton {
+    Toggle, // open or close the wheel
+    Emote(Emote),
+    Mute(usize), // mute or unmute a seat
+}
+
+// speech bubble next to the hand of a seat
+#[derive(Component)]
+pub struct ChatBubble {
+    pub seat: usize,
+    pub timer: Timer,
+}
+
+// chat state of this screen, mutes are kept for the whole game
+#[derive(Resource, Default)]
+pub struct ChatState {
+    pub open: bool,
+    pub typing: Option<String>,
+    pub muted: HashSet<usize>,
+}
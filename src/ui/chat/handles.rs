@@ -0,0 +1,99 @@
+use bevy::prelude::*;
+use bevy::input::keyboard::{Key, KeyboardInput};
+use crate::ui::chat::component::{ChatButton, ChatBubble, ChatState};
+use crate::ui::chat::system::local_speaker;
+use crate::ui::soundtrack::event::PlayButtonClick;
+use crate::game::chat::event::{ChatContent, SayChat};
+use crate::game::chat::resource::MAX_TEXT_LENGTH;
+use crate::game::match_config::resource::Viewer;
+use crate::game::player::component::Player;
+
+// handle the chat wheel buttons
+pub fn handle_chat_buttons(
+    mut commands: Commands,
+    button_query: Query<(&Interaction, &ChatButton), Changed<Interaction>>,
+    mut chat_state: ResMut<ChatState>,
+    viewer: Res<Viewer>,
+    player_query: Query<&Player>,
+    bubble_query: Query<(Entity, &ChatBubble)>,
+    mut say_messages: MessageWriter<SayChat>,
+    mut button_click_message: MessageWriter<PlayButtonClick>,
+) {
+    for (interaction, button) in &button_query {
+        if *interaction != Interaction::Pressed { continue; }
+
+        match button {
+            ChatButton::Toggle => {
+                chat_state.open = !chat_state.open;
+                button_click_message.write(PlayButtonClick);
+            }
+            // the emote has its own sound once it is shown
+            ChatButton::Emote(emote) => {
+                let Some(seat) = local_speaker(&viewer, &player_query) else { continue; };
+                say_messages.write(SayChat { seat, content: ChatContent::Emote(*emote) });
+                chat_state.open = false;
+            }
+            ChatButton::Mute(seat) => {
+                button_click_message.write(PlayButtonClick);
+                if !chat_state.muted.remove(seat) {
+                    chat_state.muted.insert(*seat);
+                    for (entity, bubble) in bubble_query.iter() {
+                        if bubble.seat == *seat {
+                            commands.entity(entity).despawn();
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+// T starts a free text message, Enter sends it and Escape cancels it
+pub fn type_chat(
+    mut keyboard_messages: MessageReader<KeyboardInput>,
+    mut chat_state: ResMut<ChatState>,
+    viewer: Res<Viewer>,
+    player_query: Query<&Player>,
+    mut say_messages: MessageWriter<SayChat>,
+) {
+    let Some(seat) = local_speaker(&viewer, &player_query) else {
+        chat_state.typing = None;
+        return;
+    };
+
+    for message in keyboard_messages.read() {
+        if !message.state.is_pressed() { continue; }
+
+        let Some(text) = chat_state.typing.as_mut() else {
+            if matches!(&message.logical_key, Key::Character(characters) if characters.eq_ignore_ascii_case("t")) {
+                chat_state.typing = Some(String::new());
+            }
+            continue;
+        };
+
+        match &message.logical_key {
+            Key::Enter => {
+                if let Some(text) = chat_state.typing.take() {
+                    say_messages.write(SayChat { seat, content: ChatContent::Text(text) });
+                }
+            }
+            Key::Escape => chat_state.typing = None,
+            Key::Backspace => { text.pop(); }
+            Key::Space if text.chars().count() < MAX_TEXT_LENGTH => text.push(' '),
+            Key::Character(characters) if text.chars().count() < MAX_TEXT_LENGTH => {
+                text.extend(characters.chars().filter(|c| !c.is_control()));
+            }
+            _ => {}
+        }
+    }
+}
+
+// while typing, the keys never reach the game shortcuts (E, R, N, F, Space)
+pub fn capture_chat_keyboard(
+    chat_state: Res<ChatState>,
+    mut keyboard: ResMut<ButtonInput<KeyCode>>,
+) {
+    if chat_state.typing.is_some() {
+        keyboard.reset_all();
+    }
+}
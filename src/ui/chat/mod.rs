@@ -0,0 +1,27 @@
+use bevy::prelude::*;
+use bevy::input::InputSystems;
+use crate::game::gamestate::AppState;
+
+pub mod component;
+mod system;
+mod handles;
+
+use component::ChatState;
+use system::{spawn_chat_panel, show_chat_bubbles, expire_chat_bubbles, update_chat_panel, reset_chat};
+use handles::{handle_chat_buttons, type_chat, capture_chat_keyboard};
+
+// quick chat wheel, free text and the speech bubbles of every seat
+pub struct ChatUIPlugin;
+
+impl Plugin for ChatUIPlugin {
+    fn build(&self, app: &mut App) {
+        app
+        .init_resource::<ChatState>()
+        .add_systems(OnEnter(AppState::Setup), spawn_chat_panel)
+        // the game shortcuts are off while typing
+        .add_systems(PreUpdate, capture_chat_keyboard.after(InputSystems))
+        .add_systems(Update, (handle_chat_buttons, type_chat, show_chat_bubbles, expire_chat_bubbles, update_chat_panel).chain()
+            .run_if(in_state(AppState::Setup).or(in_state(AppState::PlayerTurn)).or(in_state(AppState::RoundEnd))))
+        .add_systems(OnEnter(AppState::MainMenu), reset_chat);
+    }
+}
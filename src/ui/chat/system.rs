@@ -0,0 +1,187 @@
+use bevy::prelude::*;
+use crate::ui::chat::component::{ChatSection, ChatTypingText, ChatButton, ChatBubble, ChatState};
+use crate::ui::text_button::system::text_button;
+use crate::ui::soundtrack::event::PlayEmote;
+use crate::game::chat::event::{ChatContent, ChatMessage, Emote};
+use crate::game::gamestate::GameEntity;
+use crate::game::hand::{component::Hand, system::get_player_positions};
+use crate::game::layout::TableLayout;
+use crate::game::match_config::resource::Viewer;
+use crate::game::player::component::Player;
+
+// seconds a speech bubble stays on the table
+const BUBBLE_TIME: f32 = 3.0;
+
+// spawn the chat panel once per game, it survives the rounds
+pub fn spawn_chat_panel(
+    mut commands: Commands,
+    panel_query: Query<(), With<ChatSection>>,
+) {
+    if !panel_query.is_empty() { return; }
+
+    commands.spawn((
+        Node {
+            position_type: PositionType::Absolute,
+            left: Val::Px(12.0),
+            bottom: Val::Px(12.0),
+            flex_direction: FlexDirection::Column,
+            align_items: AlignItems::FlexStart,
+            row_gap: Val::Px(6.0),
+            ..default()
+        },
+        ChatSection::Panel,
+        GameEntity,
+    )).with_children(|parent| {
+        // seats to mute, only the ones at the table are shown
+        parent.spawn((
+            Node { column_gap: Val::Px(6.0), ..default() },
+            ChatSection::Wheel,
+        )).with_children(|row| {
+            for seat in 0..4 {
+                row.spawn(text_button("")).insert(ChatButton::Mute(seat));
+            }
+        });
+        parent.spawn((
+            Node { column_gap: Val::Px(6.0), ..default() },
+            ChatSection::Wheel,
+        )).with_children(|row| {
+            for emote in Emote::ALL {
+                row.spawn(text_button(emote.label())).insert(ChatButton::Emote(emote));
+            }
+        });
+        parent.spawn((
+            Text::new(""),
+            TextFont { font_size: 18.0, ..default() },
+            TextColor(Color::WHITE),
+            ChatTypingText,
+        ));
+        parent.spawn(text_button("CHAT")).insert(ChatButton::Toggle);
+    });
+}
+
+// seat that talks from this screen: the local player holding the screen
+pub fn local_speaker(viewer: &Viewer, player_query: &Query<&Player>) -> Option<usize> {
+    let player = player_query.get(viewer.0?).ok()?;
+    player.is_local_player.then_some(player.seat)
+}
+
+// show a bubble next to the hand of the seat that talked, unless it is muted
+pub fn show_chat_bubbles(
+    mut commands: Commands,
+    mut chat_messages: MessageReader<ChatMessage>,
+    chat_state: Res<ChatState>,
+    bubble_query: Query<(Entity, &ChatBubble)>,
+    player_query: Query<&Player>,
+    layout: Res<TableLayout>,
+    mut emote_messages: MessageWriter<PlayEmote>,
+) {
+    for message in chat_messages.read() {
+        if chat_state.muted.contains(&message.seat) { continue; }
+        let Some(player) = player_query.iter().find(|player| player.seat == message.seat) else { continue; };
+
+        // one bubble per seat, the new one replaces the old
+        for (entity, bubble) in bubble_query.iter() {
+            if bubble.seat == message.seat {
+                commands.entity(entity).despawn();
+            }
+        }
+
+        // the bottom hand talks below its cards, the others above
+        let positions = get_player_positions(message.seat, Hand::SIZE, layout.width, layout.height);
+        let center = positions.iter().sum::<Vec3>() / positions.len() as f32;
+        let offset = if message.seat == 0 { -0.12 } else { 0.12 } * layout.height;
+
+        let label = format!("{}: {}", player.name, message.content.label());
+        let width = label.chars().count() as f32 * 9.0 + 24.0;
+        commands.spawn((
+            Sprite::from_color(Color::srgba(1.0, 1.0, 1.0, 0.9), Vec2::new(width, 34.0)),
+            Transform::from_xyz(center.x, center.y + offset, 50.0),
+            ChatBubble { seat: message.seat, timer: Timer::from_seconds(BUBBLE_TIME, TimerMode::Once) },
+            GameEntity,
+            children![(
+                Text2d::new(label),
+                TextFont { font_size: 16.0, ..default() },
+                TextColor(Color::BLACK),
+                Transform::from_xyz(0.0, 0.0, 1.0),
+            )],
+        ));
+
+        let emote = match &message.content {
+            ChatContent::Emote(emote) => Some(*emote),
+            ChatContent::Text(_) => None,
+        };
+        emote_messages.write(PlayEmote(emote));
+    }
+}
+
+// remove the bubbles after a few seconds
+pub fn expire_chat_bubbles(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut bubble_query: Query<(Entity, &mut ChatBubble)>,
+) {
+    for (entity, mut bubble) in bubble_query.iter_mut() {
+        if bubble.timer.tick(time.delta()).just_finished() {
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+// show the panel rows that apply and keep the labels up to date
+pub fn update_chat_panel(
+    chat_state: Res<ChatState>,
+    viewer: Res<Viewer>,
+    player_query: Query<&Player>,
+    mut section_query: Query<(&ChatSection, &mut Node), Without<ChatButton>>,
+    mut button_query: Query<(&ChatButton, &mut Node, &Children)>,
+    typing_query: Query<Entity, With<ChatTypingText>>,
+    mut text_query: Query<&mut Text>,
+) {
+    // spectators only read the chat
+    let speaker = local_speaker(&viewer, &player_query);
+    for (section, mut node) in &mut section_query {
+        let visible = match section {
+            ChatSection::Panel => speaker.is_some(),
+            ChatSection::Wheel => chat_state.open,
+        };
+        let display = if visible { Display::Flex } else { Display::None };
+        if node.display != display {
+            node.display = display;
+        }
+    }
+
+    let typing = match &chat_state.typing {
+        Some(text) => format!("Say: {}_  (Enter to send, Esc to cancel)", text),
+        None => String::from("T: type a message"),
+    };
+    if let Ok(entity) = typing_query.single() && let Ok(mut text) = text_query.get_mut(entity) && text.0 != typing {
+        text.0 = typing;
+    }
+
+    for (button, mut node, children) in &mut button_query {
+        let label = match button {
+            ChatButton::Toggle => String::from(if chat_state.open { "CLOSE" } else { "CHAT" }),
+            ChatButton::Emote(emote) => String::from(emote.label()),
+            ChatButton::Mute(seat) => {
+                let player = player_query.iter().find(|player| player.seat == *seat);
+                let display = if player.is_some() && speaker != Some(*seat) { Display::Flex } else { Display::None };
+                if node.display != display {
+                    node.display = display;
+                }
+                let Some(player) = player else { continue; };
+                let action = if chat_state.muted.contains(seat) { "UNMUTE" } else { "MUTE" };
+                format!("{} {}", action, player.name)
+            }
+        };
+        for &child in children {
+            if let Ok(mut text) = text_query.get_mut(child) && text.0 != label {
+                text.0 = label.clone();
+            }
+        }
+    }
+}
+
+// a new game starts with the chat closed and nobody muted
+pub fn reset_chat(mut chat_state: ResMut<ChatState>) {
+    *chat_state = ChatState::default();
+}
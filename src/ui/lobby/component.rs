@@ -0,0 +1,46 @@
+use bevy::prelude::*;
+use std::net::SocketAddr;
+use crate::game::rules::resource::RuleOption;
+
+// lobby screen
+#[derive(Component)]
+pub struct LobbyUI;
+
+// lobby text buttons
+#[derive(Component, Clone, Copy, PartialEq)]
+pub enum LobbyButton {
+    Host,
+    Seats,
+    AiFill,
+    SpectatorHands, // public cards only, or every hand
+    SpectatorDelay,
+    Sync, // the host runs the rules, or every peer in lockstep
+    TurnTime,
+    Rule(RuleOption), // cycle one of the rules of the table
+    Start,
+    Join,
+    Watch, // join as a read-only spectator
+    Ready,
+    Back, // leaves the table, or the lobby when not at a table
+    Table(SocketAddr), // join a table found on the LAN, or watch it once it started
+}
+
+// what the lobby is doing: looking for tables, hosting or joined
+#[derive(Component)]
+pub struct LobbyStatusText;
+
+// players waiting at the table
+#[derive(Component)]
+pub struct LobbyPlayersText;
+
+// address typed for manual join
+#[derive(Component)]
+pub struct LobbyAddressText;
+
+// container of the tables found on the LAN, rebuilt when the list changes
+#[derive(Component)]
+pub struct LobbyTableList;
+
+// address for manual join, starts with the one given on the command line (--join <address>)
+#[derive(Resource)]
+pub struct LobbyAddress(pub String);
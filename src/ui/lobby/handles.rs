@@ -0,0 +1,143 @@
+use bevy::prelude::*;
+use bevy::input::keyboard::{Key, KeyboardInput};
+use std::net::{IpAddr, SocketAddr};
+use std::time::Duration;
+use crate::ui::lobby::component::{LobbyButton, LobbyAddress};
+use crate::ui::soundtrack::event::PlayButtonClick;
+use crate::game::gamestate::AppState;
+use crate::game::lobby::resource::{LobbySettings, LobbyDiscovery, local_name};
+use crate::game::match_config::resource::{MatchConfig, GameSpeed};
+use crate::game::net::resource::{NetSession, NetHost, NetClient, NetConnection, NetOptions, Lockstep};
+use crate::game::net::protocol::{ClientHello, DEFAULT_PORT};
+use crate::game::turn_timer::resource::TurnTimeLimit;
+use crate::game::rules::resource::Ruleset;
+use crate::game::ai::component::AIThinkSettings;
+use crate::game::special_cards::resource::SpecialEffectRegistry;
+
+// handle lobby text buttons
+pub fn handle_lobby_buttons(
+    button_query: Query<(&Interaction, &LobbyButton), Changed<Interaction>>,
+    mut net_session: ResMut<NetSession>,
+    mut settings: ResMut<LobbySettings>,
+    mut turn_time: ResMut<TurnTimeLimit>,
+    mut ruleset: ResMut<Ruleset>,
+    mut address: ResMut<LobbyAddress>,
+    discovery: Res<LobbyDiscovery>,
+    (net_options, ai_think, registry): (Res<NetOptions>, Res<AIThinkSettings>, Res<SpecialEffectRegistry>),
+    mut next_state: ResMut<NextState<AppState>>,
+    mut button_click_message: MessageWriter<PlayButtonClick>,
+    mut commands: Commands,
+) {
+    for (interaction, button) in &button_query {
+        if *interaction != Interaction::Pressed { continue; }
+        button_click_message.write(PlayButtonClick);
+
+        match (button, &mut *net_session) {
+            (LobbyButton::Host, NetSession::Offline) => match NetHost::bind(net_options.port) {
+                Ok(host) => {
+                    *net_session = NetSession::Host(host);
+                    info!(target: "mygame", "Hosting {} on port {}", settings.name, net_options.port);
+                }
+                Err(error) => warn!(target: "mygame", "Could not host on port {}: {}", net_options.port, error),
+            },
+            // cycle between 2 and 4 seats
+            (LobbyButton::Seats, NetSession::Host(_)) => {
+                settings.seats = if settings.seats >= LobbySettings::MAX_SEATS { LobbySettings::MIN_SEATS } else { settings.seats + 1 };
+            }
+            (LobbyButton::AiFill, NetSession::Host(_)) => settings.ai_fill = !settings.ai_fill,
+            (LobbyButton::SpectatorHands, NetSession::Host(_)) => settings.open_hands_for_spectators = !settings.open_hands_for_spectators,
+            (LobbyButton::SpectatorDelay, NetSession::Host(_)) => {
+                let delays = LobbySettings::SPECTATOR_DELAYS;
+                let next = delays.iter().position(|&delay| delay == settings.spectator_delay).map_or(0, |index| (index + 1) % delays.len());
+                settings.spectator_delay = delays[next];
+            }
+            (LobbyButton::Sync, NetSession::Host(_)) => settings.lockstep = !settings.lockstep,
+            (LobbyButton::TurnTime, NetSession::Host(_)) => *turn_time = turn_time.next(),
+            (LobbyButton::Rule(option), NetSession::Host(_)) => ruleset.cycle(*option, &registry),
+            (LobbyButton::Start, NetSession::Host(host)) => {
+                let all_ready = host.peers.iter().all(|peer| peer.ready);
+                if settings.can_start(host.peers.len(), all_ready) {
+                    let match_config = MatchConfig::lan_host(host.peers.len());
+                    if settings.fills_with_ai() {
+                        commands.insert_resource(match_config.with_ai_fill(settings.seats).with_think_times(&ai_think));
+                    } else {
+                        commands.insert_resource(match_config);
+                    }
+                    // the seed of each round is drawn when it is set up
+                    host.lockstep = settings.lockstep.then(Lockstep::default);
+                    commands.insert_resource(GameSpeed::default());
+                    next_state.set(AppState::Setup);
+                    info!(target: "mygame", "Starting LAN game with {} clients...", host.peers.len());
+                } else {
+                    info!(target: "mygame", "Waiting for every player to be ready, or for the empty seats to be filled");
+                }
+            }
+            (LobbyButton::Join, NetSession::Offline) => join_table(&mut net_session, &address.0, false),
+            (LobbyButton::Watch, NetSession::Offline) => join_table(&mut net_session, &address.0, true),
+            // a table that already started can only be watched
+            (LobbyButton::Table(table_address), NetSession::Offline) => {
+                let playing = discovery.tables.iter().any(|table| table.address == *table_address && table.info.playing);
+                address.0 = table_address.to_string();
+                join_table(&mut net_session, &address.0, playing);
+            }
+            (LobbyButton::Ready, NetSession::Client(client)) => {
+                client.ready = !client.ready;
+                if let Err(error) = client.connection.send(&ClientHello::Ready { ready: client.ready }) {
+                    warn!(target: "mygame", "Could not reach the host: {}", error);
+                }
+            }
+            (LobbyButton::Back, NetSession::Offline) => next_state.set(AppState::MainMenu),
+            (LobbyButton::Back, _) => {
+                *net_session = NetSession::Offline;
+                info!(target: "mygame", "Left the table");
+            }
+            _ => {}
+        }
+    }
+}
+
+// join a table without freezing the lobby when nobody answers at the address
+fn join_table(net_session: &mut NetSession, address: &str, watch: bool) {
+    let Some(socket_address) = parse_join_address(address) else {
+        warn!(target: "mygame", "Could not join {}: not an IP address", address);
+        return;
+    };
+    let hello = if watch { ClientHello::Watch { name: local_name() } } else { ClientHello::Hello { name: local_name() } };
+    let connection = NetConnection::connect_timeout(socket_address, Duration::from_millis(300))
+        .and_then(|connection| connection.send(&hello).map(|_| connection));
+    match connection {
+        Ok(connection) => {
+            let mut client = NetClient::new(connection);
+            client.spectator = watch;
+            *net_session = NetSession::Client(client);
+            info!(target: "mygame", "{} {}, waiting for the host", if watch { "Watching" } else { "Joined" }, address);
+        }
+        Err(error) => warn!(target: "mygame", "Could not join {}: {}", address, error),
+    }
+}
+
+// auxiliar function - read "ip:port", a bare IP joins on the default port
+fn parse_join_address(address: &str) -> Option<SocketAddr> {
+    let address = address.trim();
+    address.parse::<SocketAddr>().ok()
+        .or_else(|| address.parse::<IpAddr>().ok().map(|ip| SocketAddr::new(ip, DEFAULT_PORT)))
+}
+
+// type the address to join, while not at a table
+pub fn type_lobby_address(
+    mut keyboard_messages: MessageReader<KeyboardInput>,
+    mut address: ResMut<LobbyAddress>,
+    net_session: Res<NetSession>,
+) {
+    for message in keyboard_messages.read() {
+        if !message.state.is_pressed() || !matches!(*net_session, NetSession::Offline) { continue; }
+
+        match &message.logical_key {
+            Key::Backspace => { address.0.pop(); }
+            Key::Character(characters) => {
+                address.0.extend(characters.chars().filter(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | ':' | '-')));
+            }
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,21 @@
+use bevy::prelude::*;
+use crate::game::gamestate::AppState;
+
+pub mod component;
+mod system;
+mod handles;
+
+use system::{spawn_lobby, clean_lobby, update_lobby_ui, update_table_list};
+use handles::{handle_lobby_buttons, type_lobby_address};
+
+// LAN lobby screen: host a table or browse and join one
+pub struct LobbyUIPlugin;
+
+impl Plugin for LobbyUIPlugin {
+    fn build(&self, app: &mut App) {
+        app
+        .add_systems(OnEnter(AppState::Lobby), spawn_lobby)
+        .add_systems(Update, (handle_lobby_buttons, type_lobby_address, update_lobby_ui, update_table_list).chain().run_if(in_state(AppState::Lobby)))
+        .add_systems(OnExit(AppState::Lobby), clean_lobby);
+    }
+}
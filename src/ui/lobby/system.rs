@@ -0,0 +1,267 @@
+use bevy::prelude::*;
+use crate::ui::lobby::component::{LobbyUI, LobbyButton, LobbyStatusText, LobbyPlayersText, LobbyAddressText, LobbyTableList, LobbyAddress};
+use crate::ui::text_button::system::text_button;
+use crate::game::lobby::resource::{LobbyDiscovery, LobbySettings};
+use crate::game::lobby::protocol::LobbyView;
+use crate::game::net::resource::{NetSession, NetOptions};
+use crate::game::turn_timer::resource::TurnTimeLimit;
+use crate::game::rules::resource::{Ruleset, RuleOption};
+use crate::game::special_cards::resource::SpecialEffectRegistry;
+
+// spawn the lobby screen: host settings on the left, table browser on the right
+pub fn spawn_lobby(
+    mut commands: Commands,
+    net_options: Res<NetOptions>,
+) {
+    commands.insert_resource(LobbyAddress(net_options.join_address.clone()));
+
+    commands.spawn((
+        Node {
+            position_type: PositionType::Absolute,
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            flex_direction: FlexDirection::Column,
+            align_items: AlignItems::Center,
+            padding: UiRect::all(Val::Px(24.0)),
+            row_gap: Val::Px(16.0),
+            ..default()
+        },
+        BackgroundColor(Color::srgb(0.05, 0.08, 0.06)),
+        LobbyUI,
+    )).with_children(|parent| {
+        parent.spawn((
+            Text::new("LAN LOBBY"),
+            TextFont { font_size: 40.0, ..default() },
+            TextColor(Color::WHITE),
+        ));
+        parent.spawn((
+            Text::new(""),
+            TextFont { font_size: 18.0, ..default() },
+            TextColor(Color::srgb(0.8, 0.8, 0.8)),
+            LobbyStatusText,
+        ));
+
+        parent.spawn(Node {
+            width: Val::Percent(100.0),
+            flex_grow: 1.0,
+            justify_content: JustifyContent::SpaceEvenly,
+            ..default()
+        }).with_children(|columns| {
+            // host a table
+            columns.spawn(Node {
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                row_gap: Val::Px(10.0),
+                ..default()
+            }).with_children(|column| {
+                column.spawn(text_button("HOST TABLE")).insert(LobbyButton::Host);
+                column.spawn(text_button("")).insert(LobbyButton::Seats);
+                column.spawn(text_button("")).insert(LobbyButton::AiFill);
+                column.spawn(text_button("")).insert(LobbyButton::SpectatorHands);
+                column.spawn(text_button("")).insert(LobbyButton::SpectatorDelay);
+                column.spawn(text_button("")).insert(LobbyButton::Sync);
+                column.spawn(text_button("")).insert(LobbyButton::TurnTime);
+                // the rules wrap in rows, there are too many for the column
+                column.spawn(Node {
+                    width: Val::Px(560.0),
+                    flex_wrap: FlexWrap::Wrap,
+                    justify_content: JustifyContent::Center,
+                    row_gap: Val::Px(10.0),
+                    column_gap: Val::Px(10.0),
+                    ..default()
+                }).with_children(|rules| {
+                    for option in RuleOption::ALL.into_iter().filter(|option| option.on_lan()) {
+                        rules.spawn(text_button("")).insert(LobbyButton::Rule(option));
+                    }
+                });
+                column.spawn(text_button("START")).insert(LobbyButton::Start);
+                column.spawn((
+                    Text::new(""),
+                    TextFont { font_size: 18.0, ..default() },
+                    TextColor(Color::WHITE),
+                    LobbyPlayersText,
+                ));
+            });
+
+            // join a table
+            columns.spawn(Node {
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                row_gap: Val::Px(10.0),
+                ..default()
+            }).with_children(|column| {
+                column.spawn(Node {
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    row_gap: Val::Px(6.0),
+                    ..default()
+                }).insert(LobbyTableList);
+                column.spawn(Node {
+                    align_items: AlignItems::Center,
+                    column_gap: Val::Px(10.0),
+                    ..default()
+                }).with_children(|row| {
+                    row.spawn((
+                        Text::new(""),
+                        TextFont { font_size: 18.0, ..default() },
+                        TextColor(Color::WHITE),
+                        LobbyAddressText,
+                    ));
+                    row.spawn(text_button("JOIN")).insert(LobbyButton::Join);
+                    row.spawn(text_button("WATCH")).insert(LobbyButton::Watch);
+                });
+                column.spawn(text_button("")).insert(LobbyButton::Ready);
+            });
+        });
+
+        parent.spawn(text_button("")).insert(LobbyButton::Back);
+    });
+}
+
+// clean the lobby screen
+pub fn clean_lobby(
+    mut commands: Commands,
+    lobby_query: Query<Entity, With<LobbyUI>>,
+) {
+    for entity in lobby_query.iter() {
+        commands.entity(entity).despawn();
+    }
+}
+
+// show the lobby state: status, players, address and the buttons that apply
+pub fn update_lobby_ui(
+    net_session: Res<NetSession>,
+    settings: Res<LobbySettings>,
+    turn_time: Res<TurnTimeLimit>,
+    ruleset: Res<Ruleset>,
+    registry: Res<SpecialEffectRegistry>,
+    address: Res<LobbyAddress>,
+    mut button_query: Query<(&LobbyButton, &mut Node, &Children)>,
+    mut text_query: Query<&mut Text>,
+    status_query: Query<Entity, With<LobbyStatusText>>,
+    players_query: Query<Entity, With<LobbyPlayersText>>,
+    address_query: Query<Entity, With<LobbyAddressText>>,
+) {
+    let (status, view) = match &*net_session {
+        NetSession::Offline => (String::from("Looking for tables on the LAN... type an address to join one directly"), None),
+        NetSession::Host(host) => {
+            let port = host.listener.local_addr().map(|address| address.port()).unwrap_or_default();
+            let all_ready = host.peers.iter().all(|peer| peer.ready);
+            let status = if settings.can_start(host.peers.len(), all_ready) {
+                format!("Hosting {} on port {}, everybody is ready", settings.name, port)
+            } else {
+                format!("Hosting {} on port {}, waiting for players", settings.name, port)
+            };
+            (status, None)
+        }
+        NetSession::Client(client) if client.spectator => (String::from("Watching, waiting for the host to start"), None),
+        NetSession::Client(client) => (String::from("Joined, waiting for the host to start"), client.lobby.as_ref()),
+    };
+
+    // players at the table
+    let players = match &*net_session {
+        NetSession::Host(host) => {
+            let mut lines = vec![String::from("You (host)")];
+            lines.extend(host.peers.iter().map(|peer| format!("{}{}", peer.name, if peer.ready { " - ready" } else { "" })));
+            table_lines(lines, settings.seats, settings.fills_with_ai())
+        }
+        _ => view.map(lobby_lines).unwrap_or_default(),
+    };
+
+    let address_line = if matches!(*net_session, NetSession::Offline) {
+        format!("Address: {}_", address.0)
+    } else {
+        String::new()
+    };
+
+    for (entity, value) in [(status_query.single(), &status), (players_query.single(), &players), (address_query.single(), &address_line)] {
+        if let Ok(entity) = entity && let Ok(mut text) = text_query.get_mut(entity) && text.0 != *value {
+            text.0 = value.clone();
+        }
+    }
+
+    // only the buttons that apply are shown
+    for (button, mut node, children) in &mut button_query {
+        let (visible, label) = match (button, &*net_session) {
+            (LobbyButton::Host, NetSession::Offline) => (true, None),
+            (LobbyButton::Seats, NetSession::Host(_)) => (true, Some(format!("SEATS: {}", settings.seats))),
+            (LobbyButton::AiFill, NetSession::Host(_)) if !settings.lockstep => (true, Some(format!("AI FILL: {}", if settings.ai_fill { "ON" } else { "OFF" }))),
+            (LobbyButton::SpectatorHands, NetSession::Host(_)) => {
+                (true, Some(format!("SPECTATORS SEE: {}", if settings.open_hands_for_spectators { "EVERY HAND" } else { "PUBLIC CARDS" })))
+            }
+            (LobbyButton::SpectatorDelay, NetSession::Host(_)) => (true, Some(format!("SPECTATOR DELAY: {}s", settings.spectator_delay))),
+            (LobbyButton::Sync, NetSession::Host(_)) => (true, Some(format!("SYNC: {}", if settings.lockstep { "LOCKSTEP" } else { "HOST RULES" }))),
+            (LobbyButton::TurnTime, NetSession::Host(_)) => (true, Some(turn_time.label())),
+            (LobbyButton::Rule(option), NetSession::Host(_)) if ruleset.offers(*option) => (true, Some(ruleset.label(*option, &registry))),
+            (LobbyButton::Start, NetSession::Host(_)) => (true, None),
+            (LobbyButton::Watch, NetSession::Offline) => (true, None),
+            (LobbyButton::Join, NetSession::Offline) => (true, None),
+            (LobbyButton::Table(_), NetSession::Offline) => (true, None),
+            (LobbyButton::Ready, NetSession::Client(client)) if !client.spectator => (true, Some(String::from(if client.ready { "READY" } else { "NOT READY" }))),
+            (LobbyButton::Back, NetSession::Offline) => (true, Some(String::from("BACK"))),
+            (LobbyButton::Back, _) => (true, Some(String::from("LEAVE TABLE"))),
+            _ => (false, None),
+        };
+
+        let display = if visible { Display::Flex } else { Display::None };
+        if node.display != display {
+            node.display = display;
+        }
+        let Some(label) = label else { continue; };
+        for &child in children {
+            if let Ok(mut text) = text_query.get_mut(child) && text.0 != label {
+                text.0 = label.clone();
+            }
+        }
+    }
+}
+
+// players of the lobby sent by the host
+fn lobby_lines(view: &LobbyView) -> String {
+    let lines = view.players.iter().map(|player| {
+        let role = if player.host { " (host)" } else if player.ready { " - ready" } else { "" };
+        format!("{}{}", player.name, role)
+    }).collect();
+    table_lines(lines, view.seats, view.ai_fill)
+}
+
+// player list with the empty seats
+fn table_lines(mut lines: Vec<String>, seats: usize, ai_fill: bool) -> String {
+    let joined = lines.len();
+    for _ in joined..seats {
+        lines.push(String::from(if ai_fill { "(AI)" } else { "(empty)" }));
+    }
+    format!("Players {}/{}\n{}", joined, seats, lines.join("\n"))
+}
+
+// rebuild the table list when discovery finds or loses a table
+pub fn update_table_list(
+    mut commands: Commands,
+    discovery: Res<LobbyDiscovery>,
+    list_query: Query<Entity, With<LobbyTableList>>,
+    mut shown: Local<Option<u32>>,
+) {
+    if *shown == Some(discovery.generation) { return; }
+    let Ok(list) = list_query.single() else { return; };
+    *shown = Some(discovery.generation);
+
+    commands.entity(list).despawn_related::<Children>();
+    commands.entity(list).with_children(|parent| {
+        if discovery.tables.is_empty() {
+            parent.spawn((
+                Text::new("No tables found yet"),
+                TextFont { font_size: 18.0, ..default() },
+                TextColor(Color::srgb(0.8, 0.8, 0.8)),
+            ));
+        }
+        for table in &discovery.tables {
+            let info = &table.info;
+            let label = format!(
+                "{}{} - {}/{} seats{} - {} - {}",
+                if info.playing { "WATCH " } else { "" },
+                info.name, info.joined, info.seats, if info.ai_fill { " (AI fill)" } else { "" }, info.rules, table.address
+            );
+            parent.spawn(text_button(&label)).insert(LobbyButton::Table(table.address));
+        }
+    });
+}
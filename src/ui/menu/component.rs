@@ -16,6 +16,50 @@ pub struct PlayButton;
 #[derive(Component)]
 pub struct ExitButton;
 
+// watch AI vs AI button
+#[derive(Component)]
+pub struct WatchButton;
+
+// choose how many AI players to watch
+#[derive(Component)]
+pub struct AiCountButton;
+
+// number of AI players for spectator mode
+#[derive(Resource)]
+pub struct SpectatorPlayers(pub usize);
+
+impl Default for SpectatorPlayers {
+    fn default() -> Self {
+        Self(2)
+    }
+}
+
+// hot-seat (pass-and-play) button
+#[derive(Component)]
+pub struct HotSeatButton;
+
+// choose how many humans share the screen
+#[derive(Component)]
+pub struct HumanCountButton;
+
+// number of human players for hot-seat mode
+#[derive(Resource)]
+pub struct HotSeatPlayers(pub usize);
+
+impl Default for HotSeatPlayers {
+    fn default() -> Self {
+        Self(2)
+    }
+}
+
+// open the LAN lobby
+#[derive(Component)]
+pub struct LanLobbyButton;
+
+// cycle the time limit of each turn
+#[derive(Component)]
+pub struct TurnTimeButton;
+
 // button images
 #[derive(Component)]
 pub struct ButtonImages {
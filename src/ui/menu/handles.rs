@@ -1,8 +1,12 @@
 use bevy::prelude::*;
 
 use bevy::window::PrimaryWindow;
-use crate::ui::menu::component::{ButtonState, ButtonImages, ExitButton, PlayButton, MainMenuUI};
+use crate::ui::menu::component::{ButtonState, ButtonImages, ExitButton, PlayButton, MainMenuUI, WatchButton, AiCountButton, SpectatorPlayers, HotSeatButton, HumanCountButton, HotSeatPlayers, LanLobbyButton, TurnTimeButton};
+use crate::game::turn_timer::resource::TurnTimeLimit;
 use crate::game::gamestate::AppState;
+use crate::game::match_config::resource::{MatchConfig, GameSpeed};
+use crate::game::bot::component::BotCommands;
+use crate::game::ai::component::AIThinkSettings;
 use crate::ui::soundtrack::event::PlayButtonClick;
 
 // detect click in PLAY and change to Setup, detect click in EXIT and close game
@@ -16,6 +20,8 @@ pub fn handle_button_clicks(
     mut next_state: ResMut<NextState<AppState>>,
     mut exit_query: MessageWriter<AppExit>,
     mut button_click_message: MessageWriter<PlayButtonClick>,
+    mut commands: Commands,
+    (bot_commands, ai_think): (Res<BotCommands>, Res<AIThinkSettings>),
 ) {
     // only if click left mouse button
     if mouse_input.just_pressed(MouseButton::Left) {
@@ -57,6 +63,8 @@ pub fn handle_button_clicks(
                 if detect_button(world_pos, transform, image) {
                     if play.is_some() {
                         button_click_message.write(PlayButtonClick);
+                        commands.insert_resource(MatchConfig::default().with_think_times(&ai_think).with_bots(&bot_commands.0));
+                        commands.insert_resource(GameSpeed::default());
                         next_state.set(AppState::Setup);
                         info!(target: "mygame", "Starting game...");
                     } else if exit.is_some() {
@@ -140,4 +148,98 @@ pub fn update_button_hover(
             *state = ButtonState::Normal;
         }
     }
-}
\ No newline at end of file
+}
+
+// handle spectator mode text buttons
+pub fn handle_watch_buttons(
+    watch_query: Query<&Interaction, (Changed<Interaction>, With<WatchButton>)>,
+    ai_count_query: Query<(&Interaction, &Children, &AiCountButton), Changed<Interaction>>,
+    mut text_query: Query<&mut Text>,
+    mut spectator_players: ResMut<SpectatorPlayers>,
+    mut next_state: ResMut<NextState<AppState>>,
+    mut button_click_message: MessageWriter<PlayButtonClick>,
+    mut commands: Commands,
+    (bot_commands, ai_think): (Res<BotCommands>, Res<AIThinkSettings>),
+) {
+    // every seat is played by the AI (or the bots given on the command line)
+    if watch_query.iter().any(|interaction| *interaction == Interaction::Pressed) {
+        button_click_message.write(PlayButtonClick);
+        commands.insert_resource(MatchConfig::spectator(spectator_players.0).with_think_times(&ai_think).with_bots(&bot_commands.0));
+        commands.insert_resource(GameSpeed::default());
+        next_state.set(AppState::Setup);
+        info!(target: "mygame", "Starting AI vs AI game with {} players...", spectator_players.0);
+    }
+
+    // cycle between 2 and 4 AI players
+    for (interaction, children, _) in &ai_count_query {
+        if *interaction != Interaction::Pressed { continue; }
+
+        button_click_message.write(PlayButtonClick);
+        spectator_players.0 = if spectator_players.0 >= 4 { 2 } else { spectator_players.0 + 1 };
+        for &child in children {
+            if let Ok(mut text) = text_query.get_mut(child) {
+                text.0 = format!("AI PLAYERS: {}", spectator_players.0);
+            }
+        }
+    }
+}
+
+// handle hot-seat mode text buttons
+pub fn handle_hot_seat_buttons(
+    hot_seat_query: Query<&Interaction, (Changed<Interaction>, With<HotSeatButton>)>,
+    human_count_query: Query<(&Interaction, &Children, &HumanCountButton), Changed<Interaction>>,
+    mut text_query: Query<&mut Text>,
+    mut hot_seat_players: ResMut<HotSeatPlayers>,
+    mut next_state: ResMut<NextState<AppState>>,
+    mut button_click_message: MessageWriter<PlayButtonClick>,
+    mut commands: Commands,
+) {
+    // every seat is a human on this screen
+    if hot_seat_query.iter().any(|interaction| *interaction == Interaction::Pressed) {
+        button_click_message.write(PlayButtonClick);
+        commands.insert_resource(MatchConfig::hot_seat(hot_seat_players.0));
+        commands.insert_resource(GameSpeed::default());
+        next_state.set(AppState::Setup);
+        info!(target: "mygame", "Starting hot-seat game with {} players...", hot_seat_players.0);
+    }
+
+    // cycle between 2 and 4 human players
+    for (interaction, children, _) in &human_count_query {
+        if *interaction != Interaction::Pressed { continue; }
+
+        button_click_message.write(PlayButtonClick);
+        hot_seat_players.0 = if hot_seat_players.0 >= 4 { 2 } else { hot_seat_players.0 + 1 };
+        for &child in children {
+            if let Ok(mut text) = text_query.get_mut(child) {
+                text.0 = format!("HUMANS: {}", hot_seat_players.0);
+            }
+        }
+    }
+}
+
+// open the LAN lobby, cycle the turn timer
+pub fn handle_lan_buttons(
+    lobby_query: Query<&Interaction, (Changed<Interaction>, With<LanLobbyButton>)>,
+    turn_time_query: Query<(&Interaction, &Children, &TurnTimeButton), Changed<Interaction>>,
+    mut text_query: Query<&mut Text>,
+    mut turn_time: ResMut<TurnTimeLimit>,
+    mut next_state: ResMut<NextState<AppState>>,
+    mut button_click_message: MessageWriter<PlayButtonClick>,
+) {
+    if lobby_query.iter().any(|interaction| *interaction == Interaction::Pressed) {
+        button_click_message.write(PlayButtonClick);
+        next_state.set(AppState::Lobby);
+    }
+
+    for (interaction, children, _) in &turn_time_query {
+        if *interaction != Interaction::Pressed { continue; }
+
+        button_click_message.write(PlayButtonClick);
+        *turn_time = turn_time.next();
+        for &child in children {
+            if let Ok(mut text) = text_query.get_mut(child) {
+                text.0 = turn_time.label();
+            }
+        }
+    }
+}
@@ -5,16 +5,19 @@ pub mod component;
 mod system;
 mod handles;
 
-use crate::ui::menu::system::{spawn_background, spawn_logo, spawn_buttons, adjust_background, clean_menu, initial_adjust_background};
-use crate::ui::menu::handles::{handle_button_clicks, update_button_visuals, update_button_hover};
+use crate::ui::menu::system::{spawn_background, spawn_logo, spawn_buttons, spawn_watch_buttons, spawn_hot_seat_buttons, spawn_lan_buttons, adjust_background, clean_menu, initial_adjust_background};
+use crate::ui::menu::handles::{handle_button_clicks, update_button_visuals, update_button_hover, handle_watch_buttons, handle_hot_seat_buttons, handle_lan_buttons};
+use crate::ui::menu::component::{SpectatorPlayers, HotSeatPlayers};
 pub struct MenuPlugin;
 
 impl Plugin for MenuPlugin {
     fn build(&self, app: &mut App) {
         app
-        .add_systems(OnEnter(AppState::MainMenu), (spawn_background, spawn_logo, spawn_buttons))
+        .init_resource::<SpectatorPlayers>()
+        .init_resource::<HotSeatPlayers>()
+        .add_systems(OnEnter(AppState::MainMenu), (spawn_background, spawn_logo, spawn_buttons, spawn_watch_buttons, spawn_hot_seat_buttons, spawn_lan_buttons))
         .add_systems(Update, initial_adjust_background.run_if(in_state(AppState::MainMenu)))
-        .add_systems(Update, (adjust_background, update_button_hover, handle_button_clicks, update_button_visuals).run_if(in_state(AppState::MainMenu)))
+        .add_systems(Update, (adjust_background, update_button_hover, handle_button_clicks, update_button_visuals, handle_watch_buttons, handle_hot_seat_buttons, handle_lan_buttons).run_if(in_state(AppState::MainMenu)))
         .add_systems(OnExit(AppState::MainMenu), clean_menu);
     }
 }
\ No newline at end of file
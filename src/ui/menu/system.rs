@@ -1,6 +1,9 @@
 use bevy::prelude::*;
 
-use crate::{ui::menu::component::{ButtonImages, ButtonState, ExitButton, MainMenuUI, MenuBackground, PlayButton}};
+use crate::{ui::menu::component::{ButtonImages, ButtonState, ExitButton, MainMenuUI, MenuBackground, PlayButton, WatchButton, AiCountButton, SpectatorPlayers, HotSeatButton, HumanCountButton, HotSeatPlayers, LanLobbyButton, TurnTimeButton}};
+use crate::game::turn_timer::resource::TurnTimeLimit;
+use crate::ui::rules::component::RulesToggleButton;
+use crate::ui::text_button::system::text_button;
 use bevy::window::{PrimaryWindow, WindowResized};
 
 // spawn_background
@@ -133,4 +136,67 @@ pub fn clean_menu(
     for entity in menu_query.iter() {
         commands.entity(entity).despawn();
     }
-}
\ No newline at end of file
+}
+// spawn text buttons for spectator mode (AI vs AI)
+pub fn spawn_watch_buttons(
+    mut commands: Commands,
+    spectator_players: Res<SpectatorPlayers>,
+) {
+    commands.spawn((
+        Node {
+            position_type: PositionType::Absolute,
+            bottom: Val::Percent(18.0),
+            width: Val::Percent(100.0),
+            justify_content: JustifyContent::Center,
+            column_gap: Val::Px(16.0),
+            ..default()
+        },
+        MainMenuUI,
+    )).with_children(|parent| {
+        parent.spawn(text_button("WATCH AI VS AI")).insert(WatchButton);
+        parent.spawn(text_button(&format!("AI PLAYERS: {}", spectator_players.0))).insert(AiCountButton);
+    });
+}
+
+// spawn text buttons for hot-seat mode (humans sharing the screen)
+pub fn spawn_hot_seat_buttons(
+    mut commands: Commands,
+    hot_seat_players: Res<HotSeatPlayers>,
+) {
+    commands.spawn((
+        Node {
+            position_type: PositionType::Absolute,
+            bottom: Val::Percent(10.0),
+            width: Val::Percent(100.0),
+            justify_content: JustifyContent::Center,
+            column_gap: Val::Px(16.0),
+            ..default()
+        },
+        MainMenuUI,
+    )).with_children(|parent| {
+        parent.spawn(text_button("HOT SEAT")).insert(HotSeatButton);
+        parent.spawn(text_button(&format!("HUMANS: {}", hot_seat_players.0))).insert(HumanCountButton);
+    });
+}
+
+// spawn text buttons for LAN games, and the turn timer and rules of every mode
+pub fn spawn_lan_buttons(
+    mut commands: Commands,
+    turn_time: Res<TurnTimeLimit>,
+) {
+    commands.spawn((
+        Node {
+            position_type: PositionType::Absolute,
+            bottom: Val::Percent(2.0),
+            width: Val::Percent(100.0),
+            justify_content: JustifyContent::Center,
+            column_gap: Val::Px(16.0),
+            ..default()
+        },
+        MainMenuUI,
+    )).with_children(|parent| {
+        parent.spawn(text_button("LAN LOBBY")).insert(LanLobbyButton);
+        parent.spawn(text_button(&turn_time.label())).insert(TurnTimeButton);
+        parent.spawn(text_button("RULES")).insert(RulesToggleButton);
+    });
+}
@@ -6,6 +6,16 @@ pub mod back_button;
 pub mod turn_indicator;
 pub mod card_animation;
 pub mod soundtrack;
+pub mod spectator;
+pub mod text_button;
+pub mod pass_curtain;
+pub mod lobby;
+pub mod chat;
+pub mod turn_timer;
+pub mod peek;
+pub mod rules;
+pub mod special_prompt;
+pub mod round_results;
 
 use background_game::BackgroundPlugin;
 use menu::MenuPlugin;
@@ -13,6 +23,16 @@ use back_button::BackButtonPlugin;
 use turn_indicator::TurnIndicatorPlugin;
 use card_animation::CardAnimationPlugin;
 use soundtrack::AudioPlugin;
+use spectator::SpectatorPlugin;
+use text_button::TextButtonPlugin;
+use pass_curtain::PassCurtainPlugin;
+use lobby::LobbyUIPlugin;
+use chat::ChatUIPlugin;
+use turn_timer::TurnTimerUIPlugin;
+use peek::PeekUIPlugin;
+use rules::RulesUIPlugin;
+use special_prompt::SpecialPromptUIPlugin;
+use round_results::RoundResultsUIPlugin;
 
 pub struct UiPlugin;
 
@@ -24,6 +44,16 @@ impl Plugin for UiPlugin {
         .add_plugins(BackButtonPlugin)
         .add_plugins(TurnIndicatorPlugin)
         .add_plugins(CardAnimationPlugin)
-        .add_plugins(AudioPlugin);
+        .add_plugins(AudioPlugin)
+        .add_plugins(SpectatorPlugin)
+        .add_plugins(TextButtonPlugin)
+        .add_plugins(PassCurtainPlugin)
+        .add_plugins(LobbyUIPlugin)
+        .add_plugins(ChatUIPlugin)
+        .add_plugins(TurnTimerUIPlugin)
+        .add_plugins(PeekUIPlugin)
+        .add_plugins(RulesUIPlugin)
+        .add_plugins(SpecialPromptUIPlugin)
+        .add_plugins(RoundResultsUIPlugin);
     }
 }
\ No newline at end of file
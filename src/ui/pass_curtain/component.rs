@@ -0,0 +1,5 @@
+use bevy::prelude::*;
+
+// full screen curtain shown between hot-seat turns, for the player who takes the screen
+#[derive(Component)]
+pub struct PassCurtainUI(pub Entity);
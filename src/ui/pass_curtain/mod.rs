@@ -0,0 +1,18 @@
+use bevy::prelude::*;
+use crate::game::gamestate::AppState;
+use crate::game::match_config::is_hot_seat;
+
+pub mod component;
+mod system;
+
+use system::{update_pass_curtain_ui, despawn_pass_curtain_ui};
+
+pub struct PassCurtainPlugin;
+
+impl Plugin for PassCurtainPlugin {
+    fn build(&self, app: &mut App) {
+        app
+        .add_systems(Update, update_pass_curtain_ui.run_if(in_state(AppState::PlayerTurn).and(is_hot_seat)))
+        .add_systems(OnExit(AppState::PlayerTurn), despawn_pass_curtain_ui);
+    }
+}
@@ -0,0 +1,62 @@
+use bevy::prelude::*;
+use crate::ui::pass_curtain::component::PassCurtainUI;
+use crate::game::match_config::resource::PassCurtain;
+use crate::game::player::component::Player;
+use crate::game::gamestate::GameEntity;
+
+// show the curtain while the screen is being passed, hide it when the player takes it
+pub fn update_pass_curtain_ui(
+    mut commands: Commands,
+    pass_curtain: Res<PassCurtain>,
+    curtain_query: Query<(Entity, &PassCurtainUI)>,
+    player_query: Query<&Player>,
+) {
+    // remove a curtain that is no longer for the waiting player
+    for (entity, curtain) in curtain_query.iter() {
+        if pass_curtain.waiting_for != Some(curtain.0) {
+            commands.entity(entity).despawn();
+        }
+    }
+
+    let Some(player_entity) = pass_curtain.waiting_for else { return; };
+    if curtain_query.iter().any(|(_, curtain)| curtain.0 == player_entity) { return; }
+    let Ok(player) = player_query.get(player_entity) else { return; };
+
+    commands.spawn((
+        Node {
+            position_type: PositionType::Absolute,
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            flex_direction: FlexDirection::Column,
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            row_gap: Val::Px(12.0),
+            ..default()
+        },
+        BackgroundColor(Color::srgb(0.05, 0.08, 0.06)),
+        GlobalZIndex(100),
+        PassCurtainUI(player_entity),
+        GameEntity,
+    )).with_children(|parent| {
+        parent.spawn((
+            Text::new(format!("Pass to {}", player.name)),
+            TextFont { font_size: 40.0, ..default() },
+            TextColor(Color::WHITE),
+        ));
+        parent.spawn((
+            Text::new("Click or press Space when ready"),
+            TextFont { font_size: 18.0, ..default() },
+            TextColor(Color::srgb(0.8, 0.8, 0.8)),
+        ));
+    });
+}
+
+// the round is over, every card is public
+pub fn despawn_pass_curtain_ui(
+    mut commands: Commands,
+    curtain_query: Query<Entity, With<PassCurtainUI>>,
+) {
+    for entity in curtain_query.iter() {
+        commands.entity(entity).despawn();
+    }
+}
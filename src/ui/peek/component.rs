@@ -0,0 +1,5 @@
+use bevy::prelude::*;
+
+// text at the top of the table while the players peek at their cards
+#[derive(Component)]
+pub struct PeekPrompt;
@@ -0,0 +1,19 @@
+use bevy::prelude::*;
+use crate::game::gamestate::AppState;
+use crate::game::peek::is_peeking;
+
+pub mod component;
+mod system;
+
+use system::{update_peek_prompt, despawn_peek_prompt};
+
+pub struct PeekUIPlugin;
+
+impl Plugin for PeekUIPlugin {
+    fn build(&self, app: &mut App) {
+        app
+        .add_systems(Update, update_peek_prompt.run_if(in_state(AppState::PlayerTurn).and(is_peeking)))
+        .add_systems(Update, despawn_peek_prompt.run_if(in_state(AppState::PlayerTurn).and(not(is_peeking))))
+        .add_systems(OnExit(AppState::PlayerTurn), despawn_peek_prompt);
+    }
+}
@@ -0,0 +1,60 @@
+use bevy::prelude::*;
+use crate::ui::peek::component::PeekPrompt;
+use crate::game::peek::resource::PeekPhase;
+use crate::game::match_config::resource::Viewer;
+use crate::game::gamestate::GameEntity;
+
+// tell the player on the screen what to do, with the seconds left to choose
+pub fn update_peek_prompt(
+    mut commands: Commands,
+    peek_phase: Res<PeekPhase>,
+    viewer: Res<Viewer>,
+    mut prompt_query: Query<&mut Text, With<PeekPrompt>>,
+    time: Res<Time>,
+) {
+    let now = time.elapsed_secs();
+    let peeking = viewer.0.and_then(|player| peek_phase.players.iter().find(|peeking| peeking.local && peeking.player == player));
+
+    let message = match peeking {
+        Some(peeking) if peeking.is_choosing() => {
+            let left = peeking.deadline.map_or(PeekPhase::CHOOSE_SECONDS, |deadline| (deadline - now).max(0.0));
+            let missing = peek_phase.count.saturating_sub(peeking.picked.len());
+            format!("Choose {} cards to peek at ({:.0}s)", missing, left.ceil())
+        }
+        Some(peeking) if !peeking.done => "Remember them!".to_string(),
+        _ => "Waiting for the other players to peek".to_string(),
+    };
+
+    if let Ok(mut text) = prompt_query.single_mut() {
+        if text.0 != message {
+            text.0 = message;
+        }
+        return;
+    }
+
+    commands.spawn((
+        Text::new(message),
+        TextFont { font_size: 24.0, ..default() },
+        TextColor(Color::WHITE),
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(12.0),
+            width: Val::Percent(100.0),
+            justify_content: JustifyContent::Center,
+            ..default()
+        },
+        TextLayout::new_with_justify(Justify::Center),
+        PeekPrompt,
+        GameEntity,
+    ));
+}
+
+// the first turn starts
+pub fn despawn_peek_prompt(
+    mut commands: Commands,
+    prompt_query: Query<Entity, With<PeekPrompt>>,
+) {
+    for entity in prompt_query.iter() {
+        commands.entity(entity).despawn();
+    }
+}
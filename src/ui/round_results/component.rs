@@ -0,0 +1,5 @@
+use bevy::prelude::*;
+
+// results of the round, in the middle of the table until the next round starts
+#[derive(Component)]
+pub struct RoundResults;
@@ -0,0 +1,18 @@
+use bevy::prelude::*;
+use crate::game::gamestate::AppState;
+use crate::game::round_end::system::calculate_scores;
+
+pub mod component;
+mod system;
+
+use system::{spawn_round_results, despawn_round_results};
+
+pub struct RoundResultsUIPlugin;
+
+impl Plugin for RoundResultsUIPlugin {
+    fn build(&self, app: &mut App) {
+        app
+        .add_systems(OnEnter(AppState::RoundEnd), spawn_round_results.after(calculate_scores))
+        .add_systems(OnExit(AppState::RoundEnd), despawn_round_results);
+    }
+}
@@ -0,0 +1,65 @@
+use bevy::prelude::*;
+use crate::ui::round_results::component::RoundResults;
+use crate::game::round_end::resource::RoundScores;
+use crate::game::net::resource::NetSession;
+use crate::game::gamestate::GameEntity;
+
+// breakdown of every score, with the winners
+pub fn spawn_round_results(
+    mut commands: Commands,
+    round_scores: Res<RoundScores>,
+    net_session: Res<NetSession>,
+) {
+    let winners: Vec<&str> = round_scores.winners().map(|winner| winner.name.as_str()).collect();
+    let next_round = if net_session.is_client() { "Waiting for the host" } else { "Press N for a new round" };
+
+    commands.spawn((
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Percent(30.0),
+            width: Val::Percent(100.0),
+            flex_direction: FlexDirection::Column,
+            align_items: AlignItems::Center,
+            ..default()
+        },
+        RoundResults,
+        GameEntity,
+    )).with_children(|parent| {
+        parent.spawn(Node {
+            flex_direction: FlexDirection::Column,
+            align_items: AlignItems::Center,
+            row_gap: Val::Px(6.0),
+            padding: UiRect::all(Val::Px(16.0)),
+            ..default()
+        }).insert(BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.7))).with_children(|panel| {
+            for score in &round_scores.players {
+                let caller = if score.caller { " (ended the round)" } else { "" };
+                panel.spawn((
+                    Text::new(format!("{}{}", score.breakdown(), caller)),
+                    TextFont { font_size: 20.0, ..default() },
+                    TextColor(if score.winner { Color::srgb(1.0, 0.85, 0.2) } else { Color::WHITE }),
+                ));
+            }
+            panel.spawn((
+                Text::new(format!("Winner: {}", winners.join(", "))),
+                TextFont { font_size: 24.0, ..default() },
+                TextColor(Color::srgb(1.0, 0.85, 0.2)),
+            ));
+            panel.spawn((
+                Text::new(next_round),
+                TextFont { font_size: 16.0, ..default() },
+                TextColor(Color::WHITE),
+            ));
+        });
+    });
+}
+
+// the next round starts
+pub fn despawn_round_results(
+    mut commands: Commands,
+    results_query: Query<Entity, With<RoundResults>>,
+) {
+    for entity in results_query.iter() {
+        commands.entity(entity).despawn();
+    }
+}
@@ -0,0 +1,14 @@
+use bevy::prelude::*;
+use crate::game::rules::resource::RuleOption;
+
+// open or close the rules panel
+#[derive(Component)]
+pub struct RulesToggleButton;
+
+// panel with a button for each rule
+#[derive(Component)]
+pub struct RulesPanel;
+
+// cycle one rule
+#[derive(Component)]
+pub struct RuleButton(pub RuleOption);
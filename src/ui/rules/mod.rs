@@ -0,0 +1,17 @@
+use bevy::prelude::*;
+use crate::game::gamestate::AppState;
+
+pub mod component;
+mod system;
+
+use system::{toggle_rules_panel, handle_rule_buttons, refresh_rule_buttons};
+
+// rules panel of the main menu, the lobby shows the same options to the host
+pub struct RulesUIPlugin;
+
+impl Plugin for RulesUIPlugin {
+    fn build(&self, app: &mut App) {
+        app
+        .add_systems(Update, (toggle_rules_panel, handle_rule_buttons, refresh_rule_buttons).chain().run_if(in_state(AppState::MainMenu)));
+    }
+}
@@ -0,0 +1,79 @@
+use bevy::prelude::*;
+use crate::ui::rules::component::{RulesToggleButton, RulesPanel, RuleButton};
+use crate::ui::menu::component::MainMenuUI;
+use crate::ui::text_button::system::text_button;
+use crate::ui::soundtrack::event::PlayButtonClick;
+use crate::game::rules::resource::{Ruleset, RuleOption};
+use crate::game::special_cards::resource::SpecialEffectRegistry;
+
+// show the rules above the menu buttons, or hide them
+pub fn toggle_rules_panel(
+    mut commands: Commands,
+    toggle_query: Query<&Interaction, (Changed<Interaction>, With<RulesToggleButton>)>,
+    panel_query: Query<Entity, With<RulesPanel>>,
+    ruleset: Res<Ruleset>,
+    registry: Res<SpecialEffectRegistry>,
+    mut button_click_message: MessageWriter<PlayButtonClick>,
+) {
+    if !toggle_query.iter().any(|interaction| *interaction == Interaction::Pressed) { return; }
+    button_click_message.write(PlayButtonClick);
+
+    if let Ok(panel) = panel_query.single() {
+        commands.entity(panel).despawn();
+        return;
+    }
+
+    commands.spawn((
+        Node {
+            position_type: PositionType::Absolute,
+            bottom: Val::Percent(28.0),
+            left: Val::Percent(10.0),
+            width: Val::Percent(80.0),
+            flex_wrap: FlexWrap::Wrap,
+            justify_content: JustifyContent::Center,
+            row_gap: Val::Px(8.0),
+            column_gap: Val::Px(8.0),
+            ..default()
+        },
+        RulesPanel,
+        MainMenuUI,
+    )).with_children(|parent| {
+        for option in RuleOption::ALL {
+            parent.spawn(text_button(&ruleset.label(option, &registry))).insert(RuleButton(option));
+        }
+    });
+}
+
+// move a rule to its next value
+pub fn handle_rule_buttons(
+    rule_query: Query<(&Interaction, &RuleButton), Changed<Interaction>>,
+    mut ruleset: ResMut<Ruleset>,
+    registry: Res<SpecialEffectRegistry>,
+    mut button_click_message: MessageWriter<PlayButtonClick>,
+) {
+    for (interaction, rule_button) in &rule_query {
+        if *interaction != Interaction::Pressed { continue; }
+
+        button_click_message.write(PlayButtonClick);
+        ruleset.cycle(rule_button.0, &registry);
+    }
+}
+
+// labels of the rule buttons, the specials of values missing from the deck are hidden
+pub fn refresh_rule_buttons(
+    mut button_query: Query<(Ref<RuleButton>, &Children, &mut Node)>,
+    mut text_query: Query<&mut Text>,
+    ruleset: Res<Ruleset>,
+    registry: Res<SpecialEffectRegistry>,
+) {
+    for (rule_button, children, mut node) in &mut button_query {
+        if !ruleset.is_changed() && !rule_button.is_added() { continue; }
+
+        node.display = if ruleset.offers(rule_button.0) { Display::Flex } else { Display::None };
+        for &child in children {
+            if let Ok(mut text) = text_query.get_mut(child) {
+                text.0 = ruleset.label(rule_button.0, &registry);
+            }
+        }
+    }
+}
@@ -3,6 +3,7 @@ use bevy::audio::{PlaybackMode, Volume};
 use rand::Rng;
 use crate::ui::soundtrack::resource::GameAudio;
 use crate::ui::soundtrack::event::*;
+use crate::game::chat::event::Emote;
 
 // Replay card steal effect
 pub fn play_card_draw(
@@ -75,4 +76,32 @@ pub fn button_effect(
             },
         ));
     }
+}
+
+// chat bubble pop, each emote has its own pitch
+pub fn emote_effect(
+    mut commands: Commands,
+    audio: Option<Res<GameAudio>>,
+    mut events: MessageReader<PlayEmote>,
+) {
+    let Some(audio) = audio else { return; };
+
+    for PlayEmote(emote) in events.read() {
+        let speed = match emote {
+            Some(Emote::Nice) => 1.4,
+            Some(Emote::Ouch) => 0.6,
+            Some(Emote::HurryUp) => 1.8,
+            Some(Emote::WellPlayed) => 1.1,
+            None => 1.0,
+        };
+        commands.spawn((
+            AudioPlayer::new(audio.button.clone()),
+            PlaybackSettings {
+                mode: PlaybackMode::Despawn,
+                volume: Volume::Linear(0.4),
+                speed,
+                ..default()
+            },
+        ));
+    }
 }
\ No newline at end of file
@@ -1,4 +1,5 @@
 use bevy::prelude::*;
+use crate::game::chat::event::Emote;
 
 // messages/events for play effects
 #[derive(Event, Message)]
@@ -8,4 +9,8 @@ pub struct PlayCardDraw;
 pub struct PlayCardPlace;
 
 #[derive(Event, Message)]
-pub struct PlayButtonClick;
\ No newline at end of file
+pub struct PlayButtonClick;
+
+// chat bubble shown, None for free text
+#[derive(Event, Message)]
+pub struct PlayEmote(pub Option<Emote>);
\ No newline at end of file
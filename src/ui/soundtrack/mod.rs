@@ -2,8 +2,8 @@ use bevy::prelude::*;
 use crate::game::gamestate::AppState;
 use crate::ui::soundtrack::music_system::{load_audio, play_menu, play_game, stop_music};
 use crate::ui::soundtrack::resource::CurrentMusic;
-use crate::ui::soundtrack::event::{PlayCardDraw, PlayCardPlace, PlayButtonClick};
-use crate::ui::soundtrack::effect_system::{play_card_draw, play_card_place, button_effect};
+use crate::ui::soundtrack::event::{PlayCardDraw, PlayCardPlace, PlayButtonClick, PlayEmote};
+use crate::ui::soundtrack::effect_system::{play_card_draw, play_card_place, button_effect, emote_effect};
 
 pub mod resource;
 mod music_system;
@@ -21,6 +21,7 @@ impl Plugin for AudioPlugin {
         .add_message::<PlayCardDraw>()
         .add_message::<PlayCardPlace>()
         .add_message::<PlayButtonClick>()
+        .add_message::<PlayEmote>()
         
         // load audio at first
         .add_systems(PreStartup, load_audio)
@@ -39,6 +40,6 @@ impl Plugin for AudioPlugin {
         .add_systems(OnEnter(AppState::MainMenu), stop_music)
         
         // sound effects
-        .add_systems(Update, (play_card_draw, play_card_place, button_effect));
+        .add_systems(Update, (play_card_draw, play_card_place, button_effect, emote_effect));
     }
 }
\ No newline at end of file
@@ -0,0 +1,5 @@
+use bevy::prelude::*;
+
+// text at the top of the table while the player on the screen chooses the targets of a special
+#[derive(Component)]
+pub struct SpecialPrompt;
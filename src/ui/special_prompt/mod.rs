@@ -0,0 +1,18 @@
+use bevy::prelude::*;
+use crate::game::gamestate::AppState;
+use crate::game::peek::is_peeking;
+
+pub mod component;
+mod system;
+
+use system::{update_special_prompt, despawn_special_prompt};
+
+pub struct SpecialPromptUIPlugin;
+
+impl Plugin for SpecialPromptUIPlugin {
+    fn build(&self, app: &mut App) {
+        app
+        .add_systems(Update, update_special_prompt.run_if(in_state(AppState::PlayerTurn).and(not(is_peeking))))
+        .add_systems(OnExit(AppState::PlayerTurn), despawn_special_prompt);
+    }
+}
@@ -0,0 +1,113 @@
+use bevy::prelude::*;
+use crate::ui::special_prompt::component::SpecialPrompt;
+use crate::game::special_cards::resource::{SpecialCardEffect, SpecialEffectRegistry};
+use crate::game::special_cards::handler::Targeting;
+use crate::game::net::resource::{NetSession, ClientTargeting};
+use crate::game::card::component::{Card, CardPosition};
+use crate::game::rules::resource::Ruleset;
+use crate::game::match_config::resource::Viewer;
+use crate::game::turn_player::component::Turn;
+use crate::game::gamestate::GameEntity;
+
+// tell the player on the screen which card to click for the special, and the keys of a look and swap
+pub fn update_special_prompt(
+    mut commands: Commands,
+    special_effect: Option<Res<SpecialCardEffect>>,
+    net_session: Res<NetSession>,
+    viewer: Res<Viewer>,
+    turn: Res<Turn>,
+    card_query: Query<&Card>,
+    (ruleset, registry): (Res<Ruleset>, Res<SpecialEffectRegistry>),
+    mut prompt_query: Query<(Entity, &mut Text), With<SpecialPrompt>>,
+) {
+    let message = if viewer.0 != Some(turn.current_player) {
+        None
+    } else if let Some(targeting) = net_session.targeting() {
+        // the effect of the drawn card names the prompt
+        let label = card_query.iter()
+            .find(|card| card.position == CardPosition::DrawnCard(turn.current_player))
+            .and_then(|card| ruleset.special_for(card.value))
+            .map(|effect| registry.label(effect));
+        let step = match targeting {
+            ClientTargeting::OpponentPlayer => target_step(Targeting::OpponentPlayer),
+            ClientTargeting::OpponentCard | ClientTargeting::OpponentAndOwnCard { target: None } => target_step(Targeting::OpponentCard),
+            ClientTargeting::OwnCard | ClientTargeting::OpponentAndOwnCard { target: Some(_) } => target_step(Targeting::OwnCard),
+            ClientTargeting::Several(chosen) => target_step(Targeting::Several(chosen.iter().filter(|card| card.is_none()).count() as u8)),
+            ClientTargeting::Decide => DECIDE_STEP.to_string(),
+        };
+        Some(label.map_or(step.to_string(), |label| format!("{}: {}", label, step)))
+    } else {
+        special_effect.as_ref().and_then(|effect| effect_prompt(effect, &registry))
+    };
+
+    let Some(message) = message else {
+        for (entity, _) in prompt_query.iter() {
+            commands.entity(entity).despawn();
+        }
+        return;
+    };
+
+    if let Ok((_, mut text)) = prompt_query.single_mut() {
+        if text.0 != message {
+            text.0 = message;
+        }
+        return;
+    }
+
+    commands.spawn((
+        Text::new(message),
+        TextFont { font_size: 24.0, ..default() },
+        TextColor(Color::WHITE),
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(12.0),
+            width: Val::Percent(100.0),
+            justify_content: JustifyContent::Center,
+            ..default()
+        },
+        TextLayout::new_with_justify(Justify::Center),
+        SpecialPrompt,
+        GameEntity,
+    ));
+}
+
+// the round is over
+pub fn despawn_special_prompt(
+    mut commands: Commands,
+    prompt_query: Query<Entity, With<SpecialPrompt>>,
+) {
+    for entity in prompt_query.iter() {
+        commands.entity(entity).despawn();
+    }
+}
+
+const DECIDE_STEP: &str = "Y: swap the cards / N: keep them";
+
+// auxiliar function - step of a special played on this screen, None when it needs nothing from the player
+fn effect_prompt(effect: &SpecialCardEffect, registry: &SpecialEffectRegistry) -> Option<String> {
+    let effect_type = effect.effect_type?;
+    let step = if effect.awaiting_target {
+        match registry.targeting(effect_type) {
+            Targeting::Several(count) => target_step(Targeting::Several(count.saturating_sub(effect.target_cards.len() as u8))),
+            targeting => target_step(targeting),
+        }
+    } else if effect.awaiting_own_card {
+        target_step(Targeting::OwnCard)
+    } else if effect.awaiting_decision && effect.swap_decided.is_none() {
+        DECIDE_STEP.to_string()
+    } else {
+        return None;
+    };
+    Some(format!("{}: {}", registry.label(effect_type), step))
+}
+
+// auxiliar function - what to click for a targeting, and how to back out
+fn target_step(targeting: Targeting) -> String {
+    let step = match targeting {
+        Targeting::OpponentPlayer => String::from("click a card of the hand to target"),
+        Targeting::OwnCard => String::from("click one of your cards"),
+        Targeting::Several(count) => format!("click {} more opponent cards", count),
+        _ => String::from("click an opponent card"),
+    };
+    format!("{} (ESC: back / click the drawn card: discard it)", step)
+}
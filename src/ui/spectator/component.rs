@@ -0,0 +1,5 @@
+use bevy::prelude::*;
+
+// spectator controls text
+#[derive(Component)]
+pub struct SpectatorHud;
@@ -0,0 +1,25 @@
+use bevy::prelude::*;
+use crate::game::gamestate::AppState;
+use crate::game::match_config::resource::MatchConfig;
+use crate::game::net::resource::NetSession;
+
+pub mod component;
+mod system;
+
+use system::{spawn_spectator_hud, spectator_controls, finish_step, update_spectator_hud};
+
+pub struct SpectatorPlugin;
+
+impl Plugin for SpectatorPlugin {
+    fn build(&self, app: &mut App) {
+        app
+        .add_systems(OnEnter(AppState::Setup), spawn_spectator_hud.run_if(is_spectating))
+        .add_systems(Update, (spectator_controls, finish_step, update_spectator_hud).chain()
+            .run_if(in_state(AppState::PlayerTurn).and(is_spectating)));
+    }
+}
+
+// LAN spectators only watch, the pace and the open hands are decided by the host
+fn is_spectating(match_config: Res<MatchConfig>, net_session: Res<NetSession>) -> bool {
+    match_config.spectator && !net_session.is_client()
+}
@@ -0,0 +1,86 @@
+use bevy::prelude::*;
+use crate::ui::spectator::component::SpectatorHud;
+use crate::game::match_config::resource::{MatchConfig, GameSpeed};
+use crate::game::turn_player::component::Turn;
+use crate::game::gamestate::GameEntity;
+
+// spawn controls text for spectator mode
+pub fn spawn_spectator_hud(
+    mut commands: Commands,
+    existing_hud: Query<Entity, With<SpectatorHud>>,
+) {
+    // spawn if not exists now (it stays between rounds)
+    if !existing_hud.is_empty() {
+        return;
+    }
+
+    commands.spawn((
+        Text::new(""),
+        TextFont { font_size: 16.0, ..default() },
+        TextColor(Color::WHITE),
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(12.0),
+            right: Val::Px(12.0),
+            ..default()
+        },
+        SpectatorHud,
+        GameEntity,
+    ));
+}
+
+// pause, step one turn, change speed and toggle open hands
+pub fn spectator_controls(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut game_speed: ResMut<GameSpeed>,
+    mut match_config: ResMut<MatchConfig>,
+    turn: Res<Turn>,
+) {
+    if keyboard.just_pressed(KeyCode::Space) {
+        game_speed.paused = !game_speed.paused;
+        game_speed.step_from = None;
+        info!(target: "mygame", "Spectator paused: {}", game_speed.paused);
+    }
+
+    // play until the current turn ends, then pause again
+    if keyboard.just_pressed(KeyCode::ArrowRight) && game_speed.paused {
+        game_speed.step_from = Some(turn.current_player);
+    }
+
+    if keyboard.just_pressed(KeyCode::ArrowUp) {
+        game_speed.scale = (game_speed.scale * 2.0).min(GameSpeed::MAX_SCALE);
+    }
+    if keyboard.just_pressed(KeyCode::ArrowDown) {
+        game_speed.scale = (game_speed.scale / 2.0).max(GameSpeed::MIN_SCALE);
+    }
+
+    if keyboard.just_pressed(KeyCode::KeyH) {
+        match_config.show_hands = !match_config.show_hands;
+    }
+}
+
+// stop stepping when the turn changes
+pub fn finish_step(
+    mut game_speed: ResMut<GameSpeed>,
+    turn: Res<Turn>,
+) {
+    if game_speed.step_from.is_some_and(|player| player != turn.current_player) {
+        game_speed.step_from = None;
+    }
+}
+
+// show speed and state
+pub fn update_spectator_hud(
+    mut hud_query: Query<&mut Text, With<SpectatorHud>>,
+    game_speed: Res<GameSpeed>,
+    match_config: Res<MatchConfig>,
+) {
+    let Ok(mut text) = hud_query.single_mut() else { return; };
+
+    let state = if game_speed.is_running() { "PLAYING" } else { "PAUSED" };
+    let hands = if match_config.show_hands { "open" } else { "hidden" };
+    text.0 = format!(
+        "SPECTATOR - {} - speed x{} - hands {}\nSpace: pause | Right: step turn | Up/Down: speed | H: hands",
+        state, game_speed.scale, hands
+    );
+}
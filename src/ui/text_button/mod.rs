@@ -0,0 +1,12 @@
+use bevy::prelude::*;
+
+pub mod system;
+use system::text_button_visuals;
+
+pub struct TextButtonPlugin;
+
+impl Plugin for TextButtonPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, text_button_visuals);
+    }
+}
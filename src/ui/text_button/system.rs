@@ -0,0 +1,34 @@
+use bevy::prelude::*;
+
+// colors of text buttons
+pub const TEXT_BUTTON_NORMAL: Color = Color::srgba(0.0, 0.0, 0.0, 0.55);
+pub const TEXT_BUTTON_HOVERED: Color = Color::srgba(0.0, 0.0, 0.0, 0.8);
+
+// text button bundle, for screens without button textures
+pub fn text_button(label: &str) -> impl Bundle {
+    (
+        Button,
+        Node {
+            padding: UiRect::axes(Val::Px(14.0), Val::Px(8.0)),
+            ..default()
+        },
+        BackgroundColor(TEXT_BUTTON_NORMAL),
+        children![(
+            Text::new(label),
+            TextFont { font_size: 20.0, ..default() },
+            TextColor(Color::WHITE),
+        )],
+    )
+}
+
+// highlight text buttons on hover
+pub fn text_button_visuals(
+    mut button_query: Query<(&Interaction, &mut BackgroundColor), Changed<Interaction>>,
+) {
+    for (interaction, mut background) in &mut button_query {
+        background.0 = match interaction {
+            Interaction::None => TEXT_BUTTON_NORMAL,
+            Interaction::Hovered | Interaction::Pressed => TEXT_BUTTON_HOVERED,
+        };
+    }
+}
@@ -1,7 +1,7 @@
 use bevy::prelude::*;
 
 use crate::game::gamestate::AppState;
-use crate::ui::turn_indicator::system::{add_card_opacity, update_turn_indicator, animation_opacity};
+use crate::ui::turn_indicator::system::{add_card_opacity, update_turn_indicator, animation_opacity, tint_frozen_cards};
 
 pub mod component;
 mod system;
@@ -13,6 +13,6 @@ impl Plugin for TurnIndicatorPlugin {
         app
         .add_systems(Update, add_card_opacity.run_if(in_state(AppState::Setup)))
         .add_systems(Update, add_card_opacity.run_if(in_state(AppState::PlayerTurn)))
-        .add_systems(Update, (update_turn_indicator, animation_opacity).chain().run_if(in_state(AppState::PlayerTurn)));
+        .add_systems(Update, (update_turn_indicator, animation_opacity, tint_frozen_cards).chain().run_if(in_state(AppState::PlayerTurn)));
     }
 }
\ No newline at end of file
@@ -2,6 +2,7 @@ use bevy::prelude::*;
 use crate::ui::turn_indicator::component::CardOpacity;
 use crate::game::card::component::{Card, CardPosition};
 use crate::game::turn_player::component::Turn;
+use crate::game::peek::resource::PeekPhase;
 
 // add CardOpacity component to cards
 pub fn add_card_opacity(
@@ -17,9 +18,13 @@ pub fn add_card_opacity(
 pub fn update_turn_indicator(
     mut card_query: Query<(&Card, &mut CardOpacity)>,
     turn_query: Res<Turn>,
+    peek_phase: Res<PeekPhase>,
 ) {
     for (card, mut opacity) in card_query.iter_mut() {
-        if let CardPosition::Hand(hand_owner) = card.position {
+        // every hand is lit while the players peek
+        if peek_phase.active {
+            opacity.target = 1.0
+        } else if let CardPosition::Hand(hand_owner) = card.position {
             if hand_owner == turn_query.current_player {
                 // if is turn of player
                 opacity.target = 1.0
@@ -50,4 +55,17 @@ pub fn animation_opacity(
             opacity.current = opacity.target;
         }
     }
-}
\ No newline at end of file
+}
+// frozen cards are tinted blue until the end of the round, the opacity is kept
+pub fn tint_frozen_cards(
+    mut card_query: Query<(&Card, &mut Sprite)>,
+) {
+    const FROZEN_TINT: Color = Color::srgb(0.6, 0.8, 1.0);
+    for (card, mut sprite) in card_query.iter_mut() {
+        let tint = if card.frozen { FROZEN_TINT } else { Color::WHITE };
+        let color = tint.with_alpha(sprite.color.alpha());
+        if sprite.color != color {
+            sprite.color = color;
+        }
+    }
+}
@@ -0,0 +1,16 @@
+use bevy::prelude::*;
+
+use crate::game::gamestate::AppState;
+use crate::game::peek::is_peeking;
+use crate::ui::turn_timer::system::draw_turn_ring;
+
+mod system;
+
+pub struct TurnTimerUIPlugin;
+
+impl Plugin for TurnTimerUIPlugin {
+    fn build(&self, app: &mut App) {
+        app
+        .add_systems(Update, draw_turn_ring.run_if(in_state(AppState::PlayerTurn).and(not(is_peeking))));
+    }
+}
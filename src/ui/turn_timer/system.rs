@@ -0,0 +1,29 @@
+use bevy::prelude::*;
+use std::f32::consts::TAU;
+use crate::game::turn_timer::resource::TurnClock;
+use crate::game::{player::component::Player, turn_player::component::Turn};
+use crate::game::hand::{component::Hand, system::get_player_positions};
+use crate::game::layout::TableLayout;
+
+// ring around the hand of the current player, it empties and turns red as the time runs out
+pub fn draw_turn_ring(
+    mut gizmos: Gizmos,
+    clock: Res<TurnClock>,
+    turn: Res<Turn>,
+    player_query: Query<&Player>,
+    layout: Res<TableLayout>,
+) {
+    let Some(fraction) = clock.fraction() else { return; };
+    let Ok(player) = player_query.get(turn.current_player) else { return; };
+
+    // center of the hand
+    let positions = get_player_positions(player.seat, Hand::SIZE, layout.width, layout.height);
+    let center = positions.iter().map(|position| position.truncate()).sum::<Vec2>() / positions.len() as f32;
+    let radius = layout.width * 0.06 * 2.2;
+    let color = Color::srgb(1.0 - fraction, fraction, 0.2);
+
+    // drawn twice for a thicker line
+    for offset in [0.0, 3.0] {
+        gizmos.arc_2d(Isometry2d::from_translation(center), TAU * fraction, radius + offset, color).resolution(64);
+    }
+}